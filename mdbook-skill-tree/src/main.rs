@@ -107,7 +107,7 @@ fn handle_preprocessing() -> Result<(), Error> {
 
 fn handle_supports(sub_args: &ArgMatches) -> ! {
     let renderer = sub_args.value_of("renderer").expect("Required argument");
-    let supported = SkillTreePreprocessor.supports_renderer(&renderer);
+    let supported = SkillTreePreprocessor.supports_renderer(renderer);
 
     // Signal whether the renderer is supported by exiting with 1 or 0.
     if supported {
@@ -184,7 +184,7 @@ fn handle_install(sub_args: &ArgMatches) {
 #[throws(anyhow::Error)]
 fn write_static_file(output_path: &Path, file_text: &[u8]) {
     let mut file = File::create(output_path)?;
-    file.write_all(&file_text)?;
+    file.write_all(file_text)?;
 }
 
 fn add_additional_files(doc: &mut Document) -> bool {
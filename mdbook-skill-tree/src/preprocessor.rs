@@ -1,11 +1,17 @@
+use anyhow::Context;
 use mdbook::book::{Book, BookItem};
 use mdbook::errors::{Error, Result};
 use mdbook::preprocess::{Preprocessor, PreprocessorContext};
 use pulldown_cmark::{CodeBlockKind::*, Event, Options, Parser, Tag};
 use pulldown_cmark_to_cmark::cmark;
+#[cfg(not(feature = "mermaid"))]
 use serde_json::json;
+#[cfg(feature = "mermaid")]
+use skill_tree::render::Renderers;
 use skill_tree::SkillTree;
+#[cfg(not(feature = "mermaid"))]
 use std::fmt::Write;
+use std::path::Path;
 
 #[derive(Default)]
 pub struct SkillTreePreprocessor;
@@ -15,7 +21,8 @@ impl Preprocessor for SkillTreePreprocessor {
         "skill-tree"
     }
 
-    fn run(&self, _ctx: &PreprocessorContext, mut book: Book) -> Result<Book> {
+    fn run(&self, ctx: &PreprocessorContext, mut book: Book) -> Result<Book> {
+        let src_dir = ctx.root.join(&ctx.config.book.src);
         let mut counter = 0;
         let mut res = None;
         book.for_each_mut(|item: &mut BookItem| {
@@ -24,9 +31,18 @@ impl Preprocessor for SkillTreePreprocessor {
             }
 
             if let BookItem::Chapter(chapter) = item {
-                res = Some(add_skill_tree(&chapter.content, &mut counter).map(|md| {
-                    chapter.content = md;
-                }));
+                // File references are relative to the chapter's directory.
+                let chapter_dir = match chapter.path.as_ref().and_then(|p| p.parent()) {
+                    Some(parent) => src_dir.join(parent),
+                    None => src_dir.clone(),
+                };
+                res = Some(
+                    add_skill_tree(&chapter.content, &chapter_dir, &mut counter)
+                        .with_context(|| format!("in chapter `{}`", chapter.name))
+                        .map(|md| {
+                            chapter.content = md;
+                        }),
+                );
             }
         });
 
@@ -38,9 +54,16 @@ impl Preprocessor for SkillTreePreprocessor {
     }
 }
 
-fn add_skill_tree(content: &str, counter: &mut usize) -> Result<String> {
+/// Replaces the skill-tree code blocks in `content`. A block is either
+/// the TOML source of a tree, or empty with a path after `skill-tree` in
+/// its info string (```` ```skill-tree roadmap.toml ````), which is loaded
+/// relative to `chapter_dir`. Trees that fail to load or validate are
+/// reported as errors, failing the build.
+fn add_skill_tree(content: &str, chapter_dir: &Path, counter: &mut usize) -> Result<String> {
     let mut buf = String::with_capacity(content.len());
+    let mut events = vec![];
     let mut skill_tree_content = String::new();
+    let mut skill_tree_file = None;
     let mut in_skill_tree_block = false;
 
     let mut opts = Options::empty();
@@ -49,79 +72,103 @@ fn add_skill_tree(content: &str, counter: &mut usize) -> Result<String> {
     opts.insert(Options::ENABLE_STRIKETHROUGH);
     opts.insert(Options::ENABLE_TASKLISTS);
 
-    let events = Parser::new_ext(content, opts).map(|e| {
-        if let Event::Start(Tag::CodeBlock(Fenced(code))) = e.clone() {
-            if &*code == "skill-tree" {
+    for e in Parser::new_ext(content, opts) {
+        if let Event::Start(Tag::CodeBlock(Fenced(code))) = &e {
+            let mut words = code.split_whitespace();
+            if words.next() == Some("skill-tree") {
                 in_skill_tree_block = true;
                 skill_tree_content.clear();
-                return None;
-            } else {
-                return Some(e);
+                skill_tree_file = words.next().map(|path| chapter_dir.join(path));
+                continue;
             }
         }
 
         if !in_skill_tree_block {
-            return Some(e);
+            events.push(e);
+            continue;
         }
 
         match e {
             Event::End(Tag::CodeBlock(Fenced(code))) => {
                 assert_eq!(
-                    "skill-tree", &*code,
-                    "After an opening sjill-tree code block we expect it to close again"
+                    Some("skill-tree"),
+                    code.split_whitespace().next(),
+                    "After an opening skill-tree code block we expect it to close again"
                 );
                 in_skill_tree_block = false;
 
-                let graphviz_text_or_err = SkillTree::parse(&skill_tree_content)
-                    .and_then(|skill_tree| skill_tree.to_graphviz());
-                let js_value = match graphviz_text_or_err {
-                    Ok(text) => json!({
-                        "dot_text": text,
-                        "error": "",
-                    }),
-
-                    // FIXME -- we should serialize this into something that displays the error
-                    // when rendered
-                    Err(e) => panic!("encountered error {} parsing {:?}", e, skill_tree_content),
+                let skill_tree = match &skill_tree_file {
+                    Some(path) => SkillTree::load(path)?,
+                    None => SkillTree::parse(&skill_tree_content)
+                        .context("parsing a skill-tree code block")?,
                 };
+                skill_tree.validate()?;
 
                 // Get a fresh id for this block.
                 let id = *counter;
                 *counter += 1;
 
-                // Generate a "div" where the rendered code will go with
-                // a unique `id`.
-                let mut html_code = String::new();
-                write!(&mut html_code, "<div id='skill-tree-{}'>", id).unwrap();
-                write!(&mut html_code, "</div>\n\n").unwrap();
-
-                // Generate a script tag to insert the rendered skill-tree
-                // content. It is given a string argument with the graphviz
-                // output we can pass to viz-js.
-                write!(
-                    &mut html_code,
-                    r#"<script>
-                    if (!window.SKILL_TREES) window.SKILL_TREES = [];
-                    window.SKILL_TREES.push({{id:'skill-tree-{}', value:{}}});
-                    </script>"#,
-                    id, js_value
-                )
-                .unwrap();
-                return Some(Event::Html(html_code.into()));
+                events.push(Event::Html(render(&skill_tree, id)?.into()));
             }
             Event::Text(code) => {
                 skill_tree_content.push_str(&code);
             }
-            _ => return Some(e),
+            e => events.push(e),
         }
+    }
 
-        None
-    });
-    let events = events.filter_map(|e| e);
-    cmark(events, &mut buf, None)
+    cmark(events.into_iter(), &mut buf, None)
         .map(|_| buf)
         .map_err(|err| Error::msg(format!("Markdown serialization failed: {}", err)))
 }
 
+/// Renders a tree as a Mermaid flowchart, which mermaid.js draws when the
+/// page loads.
+#[cfg(feature = "mermaid")]
+fn render(skill_tree: &SkillTree, id: usize) -> Result<String> {
+    let flowchart = Renderers::default().render_to_string("mermaid", skill_tree)?;
+    Ok(format!(
+        "<pre class='mermaid' id='skill-tree-{}'>\n{}</pre>\n\n",
+        id,
+        escape_html(&flowchart)
+    ))
+}
+
+#[cfg(feature = "mermaid")]
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Renders a tree as graphviz text, drawn by viz.js when the page loads.
+#[cfg(not(feature = "mermaid"))]
+fn render(skill_tree: &SkillTree, id: usize) -> Result<String> {
+    let js_value = json!({
+        "dot_text": skill_tree.to_graphviz()?,
+        "error": "",
+    });
+
+    // Generate a "div" where the rendered code will go with
+    // a unique `id`.
+    let mut html_code = String::new();
+    write!(&mut html_code, "<div id='skill-tree-{}'>", id).unwrap();
+    write!(&mut html_code, "</div>\n\n").unwrap();
+
+    // Generate a script tag to insert the rendered skill-tree
+    // content. It is given a string argument with the graphviz
+    // output we can pass to viz-js.
+    write!(
+        &mut html_code,
+        r#"<script>
+                    if (!window.SKILL_TREES) window.SKILL_TREES = [];
+                    window.SKILL_TREES.push({{id:'skill-tree-{}', value:{}}});
+                    </script>"#,
+        id, js_value
+    )
+    .unwrap();
+    Ok(html_code)
+}
+
 #[cfg(test)]
 mod test;
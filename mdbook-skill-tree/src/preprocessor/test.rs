@@ -1,6 +1,8 @@
 use super::add_skill_tree;
+use std::path::Path;
 
 #[test]
+#[cfg(not(feature = "mermaid"))]
 fn adds_skill_tree() {
     let content = r#"# Chapter
 
@@ -14,7 +16,7 @@ items = [ ]
 Text
 "#;
 
-    let output = add_skill_tree(content, &mut 0).unwrap();
+    let output = add_skill_tree(content, Path::new("."), &mut 0).unwrap();
     println!("output:\n{}", output);
     assert!(output.contains(r#"<div id='skill-tree-0'></div>"#));
 }
@@ -38,7 +40,10 @@ fn leaves_tables_untouched() {
 |------|------|
 |Row 1|Row 2|"#;
 
-    assert_eq!(expected, add_skill_tree(content, &mut 0).unwrap());
+    assert_eq!(
+        expected,
+        add_skill_tree(content, Path::new("."), &mut 0).unwrap()
+    );
 }
 
 #[test]
@@ -65,5 +70,71 @@ fn leaves_html_untouched() {
 </del>
 "#;
 
-    assert_eq!(expected, add_skill_tree(content, &mut 0).unwrap());
+    assert_eq!(
+        expected,
+        add_skill_tree(content, Path::new("."), &mut 0).unwrap()
+    );
+}
+
+#[test]
+#[cfg(not(feature = "mermaid"))]
+fn adds_skill_tree_from_file() {
+    let content = r#"# Chapter
+
+```skill-tree avd_snippet.toml
+```
+"#;
+
+    let output =
+        add_skill_tree(content, Path::new("../skill-tree-core/test-data"), &mut 0).unwrap();
+    assert!(output.contains(r#"<div id='skill-tree-0'></div>"#));
+    assert!(output.contains("Type alias impl Trait"));
+}
+
+#[test]
+#[cfg(feature = "mermaid")]
+fn adds_mermaid_flowchart() {
+    let content = r#"# Chapter
+
+```skill-tree
+[[group]]
+name = "a"
+label = "A & B"
+items = [ ]
+
+[[group]]
+name = "b"
+requires = ["a"]
+items = [ ]
+```
+"#;
+
+    let output = add_skill_tree(content, Path::new("."), &mut 0).unwrap();
+    assert!(output.contains("<pre class='mermaid' id='skill-tree-0'>\nflowchart"));
+    assert!(output.contains("A &amp; B"));
+    assert!(output.contains("--&gt;"));
+}
+
+#[test]
+fn reports_missing_file() {
+    let content = r#"```skill-tree missing.toml
+```
+"#;
+
+    let error = add_skill_tree(content, Path::new("."), &mut 0).unwrap_err();
+    assert!(format!("{:#}", error).contains("missing.toml"));
+}
+
+#[test]
+fn reports_invalid_tree() {
+    let content = r#"```skill-tree
+[[group]]
+name = "a"
+requires = ["nowhere"]
+items = [ ]
+```
+"#;
+
+    let error = add_skill_tree(content, Path::new("."), &mut 0).unwrap_err();
+    assert!(format!("{:#}", error).contains("nowhere"));
 }
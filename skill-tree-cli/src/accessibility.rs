@@ -0,0 +1,161 @@
+//! Accessibility metadata for the SVG and HTML outputs.
+//!
+//! graphviz titles each node with its id (the group name) and gives
+//! screen readers nothing else to go on. We rewrite each group's node to
+//! carry its label as `<title>`, a `<desc>` summarizing its status,
+//! progress, items and dependencies, and `role="graphics-symbol"`. The
+//! group name moves to a `data-group` attribute. Edges are hidden from
+//! assistive technology, since the descriptions already cover them.
+
+use skill_tree_core::{Group, ItemExt, SkillTree};
+
+/// Adds accessibility metadata to `svg`, which graphviz rendered from
+/// `tree`.
+pub fn accessible_svg(tree: &SkillTree, svg: &str) -> String {
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+    let mut output = String::with_capacity(svg.len());
+    let mut rest = svg;
+
+    if let Some(start) = rest.find("<svg") {
+        let end = start + "<svg".len();
+        output.push_str(&rest[..end]);
+        output.push_str(&format!(
+            " role=\"graphics-document document\" aria-label=\"{}\"",
+            escape(&title)
+        ));
+        rest = &rest[end..];
+    }
+
+    const NODE: &str = " class=\"node\">";
+    const EDGE: &str = " class=\"edge\">";
+    loop {
+        let node = rest.find(NODE);
+        let edge = rest.find(EDGE);
+        match (node, edge) {
+            (Some(n), e) if e.is_none_or(|e| n < e) => {
+                output.push_str(&rest[..n]);
+                rest = &rest[n + NODE.len()..];
+                rest = rewrite_node(tree, rest, &mut output);
+            }
+            (_, Some(e)) => {
+                output.push_str(&rest[..e]);
+                output.push_str(" class=\"edge\" aria-hidden=\"true\">");
+                rest = &rest[e + EDGE.len()..];
+            }
+            _ => break,
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Writes the rest of a node's opening tag and its new `<title>` and
+/// `<desc>` to `output`. `rest` starts just after graphviz's
+/// `class="node">`; returns what follows the node's `<title>`.
+fn rewrite_node<'s>(tree: &SkillTree, rest: &'s str, output: &mut String) -> &'s str {
+    let title = rest.find("<title>").and_then(|start| {
+        let end = start + rest[start..].find("</title>")?;
+        Some((start, end))
+    });
+    let (start, end) = match title {
+        Some(title) => title,
+        None => {
+            output.push_str(" class=\"node\">");
+            return rest;
+        }
+    };
+
+    let id = htmlescape::decode_html(&rest[start + "<title>".len()..end])
+        .unwrap_or_else(|_| rest[start + "<title>".len()..end].to_string());
+    let stub_of = id.strip_prefix("stub:");
+    let group = tree.group_named(stub_of.unwrap_or(&id));
+    let (label, desc) = match (group, stub_of) {
+        (Some(group), None) => (group_label(group), describe(tree, group)),
+        (Some(group), Some(_)) => (
+            group_label(group),
+            tree.translate("Shown on another page; follow the link to see it.")
+                .to_string(),
+        ),
+        (None, _) => (id.clone(), String::new()),
+    };
+
+    output.push_str(&format!(
+        " class=\"node\" role=\"graphics-symbol\" data-group=\"{}\">",
+        escape(&id)
+    ));
+    output.push_str(&rest[..start]);
+    output.push_str(&format!("<title>{}</title>", escape(&label)));
+    if !desc.is_empty() {
+        output.push_str(&format!("\n<desc>{}</desc>", escape(&desc)));
+    }
+    &rest[end + "</title>".len()..]
+}
+
+fn group_label(group: &Group) -> String {
+    group.label.clone().unwrap_or_else(|| group.name.clone())
+}
+
+/// A sentence or two a screen reader can read out for `group`.
+pub fn describe(tree: &SkillTree, group: &Group) -> String {
+    let mut sentences = vec![];
+    if let Some(status) = &group.status {
+        sentences.push(
+            tree.translate("Status: {status}.")
+                .replace("{status}", tree.status_name(status)),
+        );
+    }
+
+    let (done, total) = group.progress(tree);
+    let items: Vec<&str> = group
+        .items()
+        .filter_map(|item| item.label().map(String::as_str))
+        .collect();
+    if !items.is_empty() {
+        sentences.push(
+            tree.translate("{done} of {total} items done: {items}.")
+                .replace("{done}", &done.to_string())
+                .replace("{total}", &total.to_string())
+                .replace("{items}", &items.join(", ")),
+        );
+    }
+
+    let requires: Vec<String> = group
+        .dependencies()
+        .filter_map(|(requirement, _)| tree.group_named(requirement.group()))
+        .map(group_label)
+        .collect();
+    if !requires.is_empty() {
+        sentences.push(
+            tree.translate("Requires {groups}.")
+                .replace("{groups}", &requires.join(", ")),
+        );
+    }
+
+    let required_by: Vec<String> = tree
+        .groups()
+        .filter(|other| {
+            other.dependencies().any(|(requirement, _)| {
+                tree.group_named(requirement.group())
+                    .is_some_and(|g| g.name == group.name)
+            })
+        })
+        .map(group_label)
+        .collect();
+    if !required_by.is_empty() {
+        sentences.push(
+            tree.translate("Required by {groups}.")
+                .replace("{groups}", &required_by.join(", ")),
+        );
+    }
+
+    sentences.join(" ")
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,81 @@
+use super::{accessible_svg, describe};
+use skill_tree_core::SkillTree;
+
+const TREE: &str = r#"
+[doc]
+title = "Compiler"
+
+[[group]]
+name = "lexer"
+label = "Lexer"
+status = "Complete"
+items = [{ label = "Tokens" }]
+
+[[group]]
+name = "parser-core"
+label = "Parser & AST"
+status = "Assigned"
+requires = ["lexer"]
+items = [{ label = "Expressions", status = "Complete" }, { label = "Items" }]
+"#;
+
+#[test]
+fn describes_groups() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let parser = tree.group_named("parser-core").unwrap();
+    assert_eq!(
+        describe(&tree, parser),
+        "Status: Assigned. 1 of 2 items done: Expressions, Items. Requires Lexer."
+    );
+    let lexer = tree.group_named("lexer").unwrap();
+    assert_eq!(
+        describe(&tree, lexer),
+        "Status: Complete. 1 of 1 items done: Tokens. Required by Parser & AST."
+    );
+}
+
+#[test]
+fn describes_groups_in_locale() {
+    let tree = SkillTree::parse(&TREE.replace("[doc]", "[doc]\nlocale = \"de\"")).unwrap();
+    let lexer = tree.group_named("lexer").unwrap();
+    assert_eq!(
+        describe(&tree, lexer),
+        "Status: Erledigt. 1 von 1 Einträgen erledigt: Tokens. Benötigt von Parser & AST."
+    );
+}
+
+#[test]
+fn rewrites_nodes_and_edges() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    // Trimmed-down `dot -Tsvg` output.
+    let svg = "<svg width=\"100pt\">\n\
+               <g id=\"graph0\" class=\"graph\">\n\
+               <title>Compiler</title>\n\
+               <!-- parser&#45;core -->\n\
+               <g id=\"node1\" class=\"node\">\n\
+               <title>parser&#45;core</title>\n\
+               <polygon/>\n\
+               </g>\n\
+               <g id=\"edge1\" class=\"edge\">\n\
+               <title>lexer&#45;&gt;parser&#45;core</title>\n\
+               </g>\n\
+               </g>\n\
+               </svg>\n";
+    assert_eq!(
+        accessible_svg(&tree, svg),
+        "<svg role=\"graphics-document document\" aria-label=\"Compiler\" width=\"100pt\">\n\
+         <g id=\"graph0\" class=\"graph\">\n\
+         <title>Compiler</title>\n\
+         <!-- parser&#45;core -->\n\
+         <g id=\"node1\" class=\"node\" role=\"graphics-symbol\" data-group=\"parser-core\">\n\
+         <title>Parser &amp; AST</title>\n\
+         <desc>Status: Assigned. 1 of 2 items done: Expressions, Items. Requires Lexer.</desc>\n\
+         <polygon/>\n\
+         </g>\n\
+         <g id=\"edge1\" class=\"edge\" aria-hidden=\"true\">\n\
+         <title>lexer&#45;&gt;parser&#45;core</title>\n\
+         </g>\n\
+         </g>\n\
+         </svg>\n"
+    );
+}
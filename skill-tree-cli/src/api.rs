@@ -0,0 +1,223 @@
+//! `skill-tree serve` (with the `http-api` feature): a read-only REST API
+//! over one tree, for dashboards that want live data without access to
+//! the files. The tree is loaded again whenever one of its files changes,
+//! as in the daemon.
+//!
+//! * `GET /tree`: the title, clusters and every group;
+//! * `GET /groups/{name}`: one group (404 if there is none by that name);
+//! * `GET /render.svg`: the tree drawn by graphviz;
+//! * `GET /stats`: counts of groups and items, by status;
+//! * `POST /graphql`: a GraphQL query (see `graphql`).
+//!
+//! Groups are JSON objects with their `name`, `label`, `cluster`,
+//! `status`, `href`, the names of the groups they `requires` and
+//! `suggests`, their `items` (each column as a key) and `progress`
+//! (`{done, total}`).
+
+use crate::daemon::Daemon;
+use crate::graphql;
+use crate::graphviz;
+use crate::uri::percent_decode;
+use anyhow::Context;
+use fehler::throws;
+use serde_json::{json, Value};
+use skill_tree_core::{Group, Requirement, SkillTree};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// An answer to a request.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn json(status: u16, value: Value) -> Response {
+        Response {
+            status,
+            content_type: "application/json",
+            body: value.to_string().into_bytes(),
+        }
+    }
+
+    fn error(status: u16, message: String) -> Response {
+        Response::json(status, json!({ "error": message }))
+    }
+}
+
+pub struct Api {
+    path: PathBuf,
+    daemon: Mutex<Daemon>,
+    schema: graphql::Schema,
+}
+
+impl Api {
+    pub fn new(path: &Path) -> Api {
+        Api {
+            path: path.to_owned(),
+            daemon: Mutex::default(),
+            schema: graphql::schema(),
+        }
+    }
+
+    /// The tree, as it is on disk now.
+    fn tree(&self) -> anyhow::Result<Arc<SkillTree>> {
+        self.daemon
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .tree(&self.path)
+    }
+
+    /// Answers `GET url`.
+    pub fn get(&self, url: &str) -> Response {
+        let path = url.split(['?', '#']).next().unwrap_or("");
+        let path = path.trim_end_matches('/');
+        let tree = match self.tree() {
+            Ok(tree) => tree,
+            Err(error) => return Response::error(500, format!("{:#}", error)),
+        };
+        let tree = &*tree;
+        match path {
+            "/tree" => Response::json(200, tree_json(tree)),
+            "/stats" => Response::json(200, stats(tree)),
+            "/render.svg" => match render_svg(tree) {
+                Ok(svg) => Response {
+                    status: 200,
+                    content_type: "image/svg+xml",
+                    body: svg.into_bytes(),
+                },
+                Err(error) => Response::error(500, format!("{:#}", error)),
+            },
+            _ => match path.strip_prefix("/groups/").map(percent_decode) {
+                Some(name) => match tree.group_named(&name) {
+                    Some(group) => Response::json(200, group_json(tree, group)),
+                    None => Response::error(404, format!("no group named `{}`", name)),
+                },
+                None => Response::error(404, format!("nothing at `{}`", path)),
+            },
+        }
+    }
+
+    /// Answers `POST /graphql` with `body`.
+    pub fn graphql(&self, body: &[u8]) -> Response {
+        let tree = match self.tree() {
+            Ok(tree) => tree,
+            Err(error) => return Response::error(500, format!("{:#}", error)),
+        };
+        let (ok, response) = graphql::execute(&self.schema, tree, body);
+        Response::json(if ok { 200 } else { 400 }, response)
+    }
+}
+
+fn tree_json(tree: &SkillTree) -> Value {
+    let clusters: Vec<Value> = tree
+        .cluster
+        .iter()
+        .flatten()
+        .map(|cluster| json!({ "name": cluster.name, "label": cluster.label }))
+        .collect();
+    let groups: Vec<Value> = tree.groups().map(|group| group_json(tree, group)).collect();
+    json!({
+        "title": tree.title(),
+        "clusters": clusters,
+        "groups": groups,
+    })
+}
+
+fn group_json(tree: &SkillTree, group: &Group) -> Value {
+    let names = |requirements: &Option<Vec<Requirement>>| -> Vec<String> {
+        requirements
+            .iter()
+            .flatten()
+            .map(|requirement| match requirement.tree() {
+                Some(tree) => format!("{}::{}", tree, requirement.group()),
+                None => requirement.group().to_string(),
+            })
+            .collect()
+    };
+    let (done, total) = group.progress(tree);
+    json!({
+        "name": group.name,
+        "label": group.label.as_ref().unwrap_or(&group.name),
+        "cluster": group.cluster,
+        "status": group.status.as_ref().map(|status| tree.status_name(status)),
+        "href": group.href,
+        "requires": names(&group.requires),
+        "suggests": names(&group.suggests),
+        "items": group.items().collect::<Vec<_>>(),
+        "progress": { "done": done, "total": total },
+    })
+}
+
+/// How many groups have each status (`null` counting those without one),
+/// and how many items are done.
+fn stats(tree: &SkillTree) -> Value {
+    let mut statuses: BTreeMap<String, usize> = BTreeMap::new();
+    let mut unset = 0;
+    for group in tree.groups() {
+        match &group.status {
+            Some(status) => {
+                *statuses
+                    .entry(tree.status_name(status).to_string())
+                    .or_default() += 1
+            }
+            None => unset += 1,
+        }
+    }
+    let mut statuses = json!(statuses);
+    if unset > 0 {
+        statuses["null"] = json!(unset);
+    }
+    let (done, total) = tree.progress(tree.groups());
+    json!({
+        "groups": tree.groups().count(),
+        "statuses": statuses,
+        "items": { "done": done, "total": total },
+    })
+}
+
+#[throws(anyhow::Error)]
+fn render_svg(tree: &SkillTree) -> String {
+    tree.validate()?;
+    graphviz::render_svg(&tree.to_graphviz()?, tree.title().as_deref())?
+}
+
+/// Serves `api` on `address` (such as `127.0.0.1:8080`) until killed.
+#[throws(anyhow::Error)]
+pub fn run(api: &Api, address: &str) {
+    let server = tiny_http::Server::http(address)
+        .map_err(|error| anyhow::anyhow!("{}", error))
+        .with_context(|| format!("listening on `{}`", address))?;
+    eprintln!("serving on http://{}", server.server_addr());
+    for mut request in server.incoming_requests() {
+        let is_graphql = request.url().split('?').next() == Some("/graphql");
+        let response = match request.method() {
+            tiny_http::Method::Get => api.get(request.url()),
+            tiny_http::Method::Post if is_graphql => {
+                let mut body = vec![];
+                match request.as_reader().read_to_end(&mut body) {
+                    Ok(_) => api.graphql(&body),
+                    Err(error) => Response::error(400, error.to_string()),
+                }
+            }
+            _ => Response::error(
+                405,
+                "only GET (and POST to /graphql) is supported".to_string(),
+            ),
+        };
+        let header = tiny_http::Header::from_bytes("Content-Type", response.content_type)
+            .expect("a valid header");
+        let reply = tiny_http::Response::from_data(response.body)
+            .with_status_code(response.status)
+            .with_header(header);
+        if let Err(error) = request.respond(reply) {
+            eprintln!("skill-tree serve: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,104 @@
+use super::Api;
+use crate::test::scratch;
+use serde_json::{json, Value};
+
+const TREE: &str = r#"[[cluster]]
+name = "front"
+label = "Front end"
+
+[[group]]
+name = "lexer"
+label = "Lexer"
+cluster = "front"
+status = "Complete"
+items = [{ label = "Tokens", status = "Complete" }]
+
+[[group]]
+name = "type checker"
+requires = ["lexer"]
+items = [{ label = "Inference" }]
+"#;
+
+fn get(api: &Api, url: &str) -> (u16, Value) {
+    let response = api.get(url);
+    assert_eq!(response.content_type, "application/json");
+    (
+        response.status,
+        serde_json::from_slice(&response.body).unwrap(),
+    )
+}
+
+#[test]
+fn serves_groups_and_stats() {
+    let dir = scratch("api");
+    let path = dir.join("tree.toml");
+    std::fs::write(&path, TREE).unwrap();
+    let api = Api::new(&path);
+
+    let (status, tree) = get(&api, "/tree");
+    assert_eq!(status, 200);
+    assert_eq!(
+        tree["clusters"],
+        json!([{ "name": "front", "label": "Front end" }])
+    );
+    assert_eq!(tree["groups"].as_array().unwrap().len(), 2);
+
+    let (status, group) = get(&api, "/groups/type%20checker");
+    assert_eq!(status, 200);
+    assert_eq!(
+        group,
+        json!({
+            "name": "type checker",
+            "label": "type checker",
+            "cluster": null,
+            "status": null,
+            "href": null,
+            "requires": ["lexer"],
+            "suggests": [],
+            "items": [{ "label": "Inference" }],
+            "progress": { "done": 0, "total": 1 },
+        })
+    );
+
+    let (status, stats) = get(&api, "/stats?pretty");
+    assert_eq!(status, 200);
+    assert_eq!(
+        stats,
+        json!({
+            "groups": 2,
+            "statuses": { "Complete": 1, "null": 1 },
+            "items": { "done": 1, "total": 2 },
+        })
+    );
+
+    assert_eq!(get(&api, "/groups/parser").0, 404);
+    assert_eq!(get(&api, "/trees").0, 404);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn follows_changes() {
+    let dir = scratch("api-reload");
+    let path = dir.join("tree.toml");
+    std::fs::write(&path, TREE).unwrap();
+    let api = Api::new(&path);
+    assert_eq!(get(&api, "/stats").1["groups"], 2);
+
+    std::fs::write(
+        &path,
+        format!("{}\n[[group]]\nname = \"parser\"\nitems = []\n", TREE),
+    )
+    .unwrap();
+    assert_eq!(get(&api, "/stats").1["groups"], 3);
+
+    std::fs::write(&path, "[[group]\n").unwrap();
+    let (status, error) = get(&api, "/tree");
+    assert_eq!(status, 500);
+    assert!(error["error"]
+        .as_str()
+        .unwrap()
+        .contains("loading skill tree"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
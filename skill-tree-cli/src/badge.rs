@@ -0,0 +1,115 @@
+//! `skill-tree badge`: a small shields.io-style SVG showing how many items
+//! of a group (or of the whole tree) are done, e.g. `parser | 4/7 done`,
+//! for READMEs and dashboards.
+
+use fehler::throws;
+use skill_tree_core::SkillTree;
+
+/// The badge for the group `name`, or for every group of `tree` if there
+/// is no name. The label is the group's label, or the tree's title.
+#[throws(anyhow::Error)]
+pub fn progress_badge(tree: &SkillTree, name: Option<&str>) -> String {
+    let (label, (done, total)) = match name {
+        Some(name) => {
+            let group = match tree.group_named(name) {
+                Some(group) => group,
+                None => anyhow::bail!("there is no group `{}`", name),
+            };
+            let label = group.label.clone().unwrap_or_else(|| group.name.clone());
+            (label, group.progress(tree))
+        }
+        None => {
+            let label = tree
+                .title()
+                .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+            (label, tree.progress(tree.groups()))
+        }
+    };
+    let message = tree
+        .translate("{done}/{total} done")
+        .replace("{done}", &done.to_string())
+        .replace("{total}", &total.to_string());
+    badge(&label, &message, color(done, total))
+}
+
+/// Green once everything is done, yellow once something is, else grey.
+fn color(done: usize, total: usize) -> &'static str {
+    if total > 0 && done == total {
+        "#4c1"
+    } else if done > 0 {
+        "#dfb317"
+    } else {
+        "#9f9f9f"
+    }
+}
+
+/// Space on either side of the text in each half of the badge.
+const PADDING: f64 = 5.0;
+
+/// A flat badge with `label` on grey and `message` on `color`.
+pub fn badge(label: &str, message: &str, color: &str) -> String {
+    let label_width = (text_width(label) + 2.0 * PADDING).round();
+    let message_width = (text_width(message) + 2.0 * PADDING).round();
+    let width = label_width + message_width;
+    let title = escape(&format!("{}: {}", label, message));
+    let text = |x: f64, text: &str| {
+        let text = escape(text);
+        format!(
+            "<text x=\"{x}\" y=\"15\" fill=\"#010101\" fill-opacity=\".3\">{t}</text>\
+             <text x=\"{x}\" y=\"14\">{t}</text>",
+            x = x,
+            t = text
+        )
+    };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"20\" role=\"img\" \
+         aria-label=\"{title}\">\
+         <title>{title}</title>\
+         <linearGradient id=\"s\" x2=\"0\" y2=\"100%\">\
+         <stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/>\
+         <stop offset=\"1\" stop-opacity=\".1\"/>\
+         </linearGradient>\
+         <clipPath id=\"r\"><rect width=\"{w}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>\
+         <g clip-path=\"url(#r)\">\
+         <rect width=\"{lw}\" height=\"20\" fill=\"#555\"/>\
+         <rect x=\"{lw}\" width=\"{mw}\" height=\"20\" fill=\"{color}\"/>\
+         <rect width=\"{w}\" height=\"20\" fill=\"url(#s)\"/>\
+         </g>\
+         <g fill=\"#fff\" text-anchor=\"middle\" \
+         font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">\
+         {label}{message}\
+         </g>\
+         </svg>\n",
+        w = width,
+        lw = label_width,
+        mw = message_width,
+        title = title,
+        color = color,
+        label = text(label_width / 2.0, label),
+        message = text(label_width + message_width / 2.0, message),
+    )
+}
+
+/// Roughly how wide `text` is in 11px Verdana. Viewers don't tell us, so
+/// this only needs to be close enough that the text fits.
+fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '!' | '|' => 3.5,
+            'f' | 'r' | 't' | ' ' | '(' | ')' | '/' | '-' => 5.0,
+            'm' | 'w' | 'M' | 'W' => 10.0,
+            c if c.is_ascii_uppercase() => 7.5,
+            c if c.is_ascii() => 6.5,
+            // Emoji and CJK characters are about twice as wide as letters.
+            c if (c as u32) >= 0x1100 => 12.0,
+            _ => 7.0,
+        })
+        .sum()
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,62 @@
+use super::{badge, progress_badge};
+use skill_tree_core::SkillTree;
+
+const TREE: &str = r#"
+[doc]
+title = "Compiler"
+
+[[group]]
+name = "parser"
+label = "Parser"
+items = [
+    { label = "Expressions", status = "Complete" },
+    { label = "Statements", status = "Complete" },
+    { label = "Macros" },
+]
+
+[[group]]
+name = "lexer"
+status = "Complete"
+items = [{ label = "Tokens" }]
+"#;
+
+#[test]
+fn group_badge() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let svg = progress_badge(&tree, Some("parser")).unwrap();
+    assert!(svg.contains("<title>Parser: 2/3 done</title>"));
+    assert!(svg.contains("aria-label=\"Parser: 2/3 done\""));
+    assert!(svg.contains("fill=\"#dfb317\""));
+}
+
+#[test]
+fn whole_tree_badge() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let svg = progress_badge(&tree, None).unwrap();
+    assert!(svg.contains("<title>Compiler: 3/4 done</title>"));
+
+    let svg = progress_badge(&tree, Some("lexer")).unwrap();
+    assert!(svg.contains("<title>lexer: 1/1 done</title>"));
+    assert!(svg.contains("fill=\"#4c1\""));
+}
+
+#[test]
+fn unknown_group() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let error = progress_badge(&tree, Some("parsr")).unwrap_err();
+    assert_eq!(error.to_string(), "there is no group `parsr`");
+}
+
+#[test]
+fn halves_fit_their_text() {
+    let svg = badge("a \"b\" <c>", "1/2 done", "#dfb317");
+    assert!(svg.contains("aria-label=\"a &quot;b&quot; &lt;c&gt;: 1/2 done\""));
+
+    let short = badge("ab", "1/2 done", "#dfb317");
+    let long = badge("abcdefgh", "1/2 done", "#dfb317");
+    let width = |svg: &str| -> f64 {
+        let start = svg.find("width=\"").unwrap() + "width=\"".len();
+        svg[start..].split('"').next().unwrap().parse().unwrap()
+    };
+    assert!(width(&long) > width(&short));
+}
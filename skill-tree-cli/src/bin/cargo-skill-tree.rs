@@ -0,0 +1,32 @@
+//! Entry point for `cargo skill-tree`. Cargo runs this as
+//! `cargo-skill-tree skill-tree <args>`; we forward to `skill-tree cargo
+//! <args>`, using the `skill-tree` installed next to us if there is one.
+
+use std::env;
+use std::process::{exit, Command};
+
+fn main() {
+    let mut args: Vec<_> = env::args_os().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "skill-tree") {
+        args.remove(0);
+    }
+
+    let exe = if cfg!(windows) {
+        "skill-tree.exe"
+    } else {
+        "skill-tree"
+    };
+    let sibling = env::current_exe()
+        .ok()
+        .map(|path| path.with_file_name(exe))
+        .filter(|path| path.is_file());
+    let program = sibling.unwrap_or_else(|| exe.into());
+
+    match Command::new(&program).arg("cargo").args(args).status() {
+        Ok(status) => exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("error: running `{}`: {}", program.display(), e);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! `skill-tree burndown`: how many groups of each cluster were open and
+//! complete over time, worked out from the groups' `status_history`, as
+//! CSV or as an SVG line chart.
+
+use fehler::throws;
+use skill_tree_core::date::Date;
+use skill_tree_core::SkillTree;
+use std::collections::BTreeSet;
+
+/// The groups of one cluster on one date.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Point {
+    pub date: Date,
+    pub open: usize,
+    pub complete: usize,
+}
+
+/// A line of the chart: a cluster (`None` for groups outside any
+/// cluster) and its counts on each date.
+#[derive(Debug)]
+pub struct Series<'t> {
+    pub cluster: Option<&'t str>,
+    pub points: Vec<Point>,
+}
+
+/// Counts open and complete groups per cluster on every date that some
+/// group's status changed. A group is complete if its status on that date
+/// is done (see `SkillTree::is_done`).
+#[throws(anyhow::Error)]
+pub fn burndown(tree: &SkillTree) -> Vec<Series<'_>> {
+    let dates: BTreeSet<Date> = tree
+        .groups()
+        .flat_map(|group| group.status_history.iter().flatten())
+        .map(|change| change.date)
+        .collect();
+    if dates.is_empty() {
+        anyhow::bail!("no group has a `status_history` to chart");
+    }
+    tree.pages()
+        .into_iter()
+        .map(|cluster| {
+            let points = dates
+                .iter()
+                .map(|&date| {
+                    let (mut open, mut complete) = (0, 0);
+                    for group in tree.groups_in(cluster) {
+                        match group.status_on(date) {
+                            Some(status) if tree.is_done(status.name()) => complete += 1,
+                            _ => open += 1,
+                        }
+                    }
+                    Point {
+                        date,
+                        open,
+                        complete,
+                    }
+                })
+                .collect();
+            Series { cluster, points }
+        })
+        .collect()
+}
+
+/// One row per cluster and date: `date,cluster,open,complete`.
+pub fn csv(series: &[Series<'_>]) -> String {
+    let mut csv = String::from("date,cluster,open,complete\n");
+    for line in series {
+        for point in &line.points {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                point.date,
+                csv_field(cluster_name(line.cluster)),
+                point.open,
+                point.complete
+            ));
+        }
+    }
+    csv
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn cluster_name(cluster: Option<&str>) -> &str {
+    cluster.unwrap_or("unclustered")
+}
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 360.0;
+const MARGIN: f64 = 40.0;
+const LEGEND_WIDTH: f64 = 160.0;
+const COLORS: &[&str] = &[
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+];
+
+/// A line chart of the open groups in each cluster over time, with the
+/// dates spaced by how far apart they are.
+pub fn svg(tree: &SkillTree, series: &[Series<'_>]) -> String {
+    let dates: Vec<Date> = series[0].points.iter().map(|point| point.date).collect();
+    let first = dates[0].days();
+    let span = (dates[dates.len() - 1].days() - first).max(1) as f64;
+    let max = series
+        .iter()
+        .flat_map(|line| &line.points)
+        .map(|point| point.open)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+
+    let plot_width = WIDTH - 2.0 * MARGIN - LEGEND_WIDTH;
+    let plot_height = HEIGHT - 2.0 * MARGIN;
+    let x = |date: Date| MARGIN + (date.days() - first) as f64 / span * plot_width;
+    let y = |open: usize| MARGIN + plot_height - open as f64 / max * plot_height;
+
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        w = WIDTH,
+        h = HEIGHT
+    );
+    svg.push_str(&format!("<title>{}</title>\n", escape(&title)));
+    svg.push_str(&format!(
+        "<path d=\"M{l} {t} V{b} H{r}\" fill=\"none\" stroke=\"black\"/>\n",
+        l = MARGIN,
+        t = MARGIN,
+        b = MARGIN + plot_height,
+        r = MARGIN + plot_width,
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+        MARGIN - 4.0,
+        MARGIN + 4.0,
+        max
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">0</text>\n",
+        MARGIN - 4.0,
+        MARGIN + plot_height + 4.0,
+    ));
+    for (date, anchor) in [(dates[0], "start"), (dates[dates.len() - 1], "end")] {
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"{}\">{}</text>\n",
+            x(date),
+            MARGIN + plot_height + 16.0,
+            anchor,
+            date
+        ));
+    }
+
+    for (index, line) in series.iter().enumerate() {
+        let color = COLORS[index % COLORS.len()];
+        let points: Vec<String> = line
+            .points
+            .iter()
+            .map(|point| format!("{:.1},{:.1}", x(point.date), y(point.open)))
+            .collect();
+        svg.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+            points.join(" "),
+            color
+        ));
+
+        let label = match line.cluster {
+            Some(name) => tree.cluster_named(name).map_or(name, |c| &c.label[..]),
+            None => tree.translate("Other groups"),
+        };
+        let legend_y = MARGIN + 16.0 * index as f64;
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"10\" height=\"10\" fill=\"{}\"/>\n",
+            WIDTH - LEGEND_WIDTH,
+            legend_y,
+            color
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">{}</text>\n",
+            WIDTH - LEGEND_WIDTH + 16.0,
+            legend_y + 10.0,
+            escape(label)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,90 @@
+use super::{burndown, csv, svg, Point};
+use skill_tree_core::SkillTree;
+
+const TREE: &str = r#"
+[doc.statuses]
+Shipped = { done = true }
+
+[[cluster]]
+name = "frontend"
+label = "Frontend"
+
+[[group]]
+name = "lexer"
+cluster = "frontend"
+status = "Complete"
+status_history = [
+    { status = "Assigned", date = "2024-01-01" },
+    { status = "Complete", date = "2024-02-01" },
+]
+items = []
+
+[[group]]
+name = "parser"
+cluster = "frontend"
+status = "Shipped"
+status_history = [{ status = "Shipped", date = "2024-03-01" }]
+items = []
+
+[[group]]
+name = "docs"
+status = "Unassigned"
+items = []
+"#;
+
+#[test]
+fn counts_groups_per_cluster() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let series = burndown(&tree).unwrap();
+    let counts = |index: usize| -> Vec<(usize, usize)> {
+        let points = &series[index].points;
+        points.iter().map(|p| (p.open, p.complete)).collect()
+    };
+    assert_eq!(series[0].cluster, Some("frontend"));
+    assert_eq!(counts(0), [(2, 0), (1, 1), (0, 2)]);
+    assert_eq!(series[1].cluster, None);
+    assert_eq!(counts(1), [(1, 0), (1, 0), (1, 0)]);
+    assert_eq!(
+        series[0].points[0],
+        Point {
+            date: "2024-01-01".parse().unwrap(),
+            open: 2,
+            complete: 0
+        }
+    );
+}
+
+#[test]
+fn writes_csv() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let csv = csv(&burndown(&tree).unwrap());
+    assert_eq!(
+        csv.lines().take(3).collect::<Vec<_>>(),
+        [
+            "date,cluster,open,complete",
+            "2024-01-01,frontend,2,0",
+            "2024-02-01,frontend,1,1",
+        ]
+    );
+    assert!(csv.ends_with("2024-03-01,unclustered,1,0\n"), "{}", csv);
+}
+
+#[test]
+fn writes_svg() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let svg = svg(&tree, &burndown(&tree).unwrap());
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains(">Frontend</text>"), "{}", svg);
+    assert!(svg.contains(">2024-03-01</text>"), "{}", svg);
+}
+
+#[test]
+fn needs_history() {
+    let tree = SkillTree::parse("[[group]]\nname = \"a\"\nitems = []\n").unwrap();
+    let error = burndown(&tree).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "no group has a `status_history` to chart"
+    );
+}
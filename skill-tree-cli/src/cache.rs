@@ -0,0 +1,96 @@
+//! The cache behind `check --quick`: the report for each tree, kept until
+//! one of the files it was loaded from changes. Each tree gets a file in
+//! the cache directory, named after a hash of its path and the lint
+//! levels it was checked with.
+
+use crate::check::Report;
+use serde_derive::{Deserialize, Serialize};
+use skill_tree_core::lint::Level;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Overrides the cache directory.
+pub const CACHE_ENV_VAR: &str = "SKILL_TREE_CACHE";
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// The skill-tree version that wrote the entry; others ignore it.
+    version: String,
+
+    /// Each file the tree was loaded from, with a hash of its contents.
+    files: Vec<(PathBuf, u64)>,
+    report: Report,
+}
+
+/// Where cached reports are kept: `$SKILL_TREE_CACHE`, or `skill-tree`
+/// under `$XDG_CACHE_HOME` (defaulting to `~/.cache`), or under
+/// `%LOCALAPPDATA%` on Windows.
+pub fn dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(CACHE_ENV_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+    let dir = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => PathBuf::from(env::var_os("LOCALAPPDATA")?),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(dir.join("skill-tree"))
+}
+
+/// The report cached in `dir` for the tree at `path`, if none of its
+/// files changed since.
+pub fn get(dir: &Path, path: &Path, lints: &[(&str, Level)]) -> Option<Report> {
+    let text = std::fs::read_to_string(entry_path(dir, path, lints)).ok()?;
+    let entry: Entry = serde_json::from_str(&text).ok()?;
+    if entry.version != env!("CARGO_PKG_VERSION") {
+        return None;
+    }
+    for (file, hash) in &entry.files {
+        if hash_file(file)? != *hash {
+            return None;
+        }
+    }
+    Some(entry.report)
+}
+
+/// Caches `report` for the tree at `path` in `dir`. The cache is only an
+/// optimization, so failing to write it is ignored.
+pub fn put(dir: &Path, path: &Path, lints: &[(&str, Level)], report: &Report) {
+    let files = report
+        .files
+        .iter()
+        .map(|file| Some((file.clone(), hash_file(file)?)))
+        .collect();
+    let files = match files {
+        Some(files) => files,
+        None => return,
+    };
+    let entry = Entry {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        files,
+        report: report.clone(),
+    };
+    let _ = std::fs::create_dir_all(dir).and_then(|()| {
+        std::fs::write(entry_path(dir, path, lints), serde_json::to_string(&entry)?)
+    });
+}
+
+fn entry_path(dir: &Path, path: &Path, lints: &[(&str, Level)]) -> PathBuf {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    format!("{:?}", lints).hash(&mut hasher);
+    dir.join(format!("check-{:016x}.json", hasher.finish()))
+}
+
+fn hash_file(path: &Path) -> Option<u64> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,28 @@
+use super::{get, put};
+use crate::check::check;
+use crate::test::scratch;
+
+#[test]
+fn reused_until_a_file_changes() {
+    let dir = scratch("cache");
+    let root = dir.join("tree.toml");
+    let included = dir.join("included.toml");
+    std::fs::write(&root, "[doc]\ninclude = [\"included.toml\"]\n").unwrap();
+    std::fs::write(
+        &included,
+        "[[group]]\nname = \"a\"\nrequires = [\"b\"]\nitems = []\n",
+    )
+    .unwrap();
+    let cache = dir.join("cache");
+
+    assert!(get(&cache, &root, &[]).is_none());
+    let report = check(&root, &[]);
+    assert_eq!(report.files, [root.clone(), included.clone()]);
+    put(&cache, &root, &[], &report);
+    assert_eq!(get(&cache, &root, &[]).unwrap().errors, report.errors);
+
+    std::fs::write(&included, "[[group]]\nname = \"a\"\nitems = []\n").unwrap();
+    assert!(get(&cache, &root, &[]).is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,124 @@
+//! `cargo skill-tree`: renders the skill trees kept at the root of a cargo
+//! workspace into `target/skill-tree/`.
+//!
+//! Cargo runs `cargo-skill-tree` for `cargo skill-tree`; that binary (see
+//! `src/bin/cargo-skill-tree.rs`) just forwards to `skill-tree cargo`.
+
+use crate::{RenderJob, RenderOpts};
+use anyhow::Context;
+use fehler::throws;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where we look for a tree at the workspace root.
+const TREE_FILE: &str = "skill-tree.toml";
+
+/// A directory at the workspace root holding several trees.
+const TREE_DIR: &str = ".skill-tree";
+
+#[throws(anyhow::Error)]
+pub fn run(emit: &str, stamp: bool) {
+    let root = workspace_root()?;
+    let trees = discover(&root)?;
+    if trees.is_empty() {
+        anyhow::bail!(
+            "no `{}` file or `{}` directory found in `{}`",
+            TREE_FILE,
+            TREE_DIR,
+            root.display()
+        );
+    }
+
+    let out_dir = match env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => root.join("target"),
+    }
+    .join("skill-tree");
+    fs::create_dir_all(&out_dir).with_context(|| format!("creating `{}`", out_dir.display()))?;
+
+    let opts = RenderOpts {
+        paths: vec![],
+        output: None,
+        separate: false,
+        base_dir: None,
+        emit: Some(emit.to_string()),
+        stamp,
+        split_by: None,
+        pan_zoom: false,
+        twemoji: false,
+        pull_requests: false,
+        offline: false,
+        paper: None,
+        orientation: None,
+        rankdir: None,
+        set: vec![],
+        columns: None,
+        hide_unreachable: false,
+        select: None,
+        as_of: None,
+        lints: Default::default(),
+        tile: false,
+    };
+    for tree in trees {
+        let stem = tree.file_stem().unwrap_or_default();
+        let output_path = out_dir.join(stem).with_extension(emit);
+        crate::render(&RenderJob {
+            opts: &opts,
+            skill_tree: tree,
+            merge: vec![],
+            output_path: Some(output_path.clone()),
+        })?;
+        eprintln!("wrote `{}`", output_path.display());
+    }
+}
+
+/// Asks cargo for the root of the workspace containing the current
+/// directory.
+#[throws(anyhow::Error)]
+fn workspace_root() -> PathBuf {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .output()
+        .context("running `cargo locate-project`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "could not find the cargo workspace: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let manifest = PathBuf::from(String::from_utf8(output.stdout)?.trim());
+    manifest.parent().unwrap().to_path_buf()
+}
+
+/// Finds the trees to render under `root`: `skill-tree.toml`, plus every
+/// `.toml` file directly inside `.skill-tree/`, in name order.
+#[throws(anyhow::Error)]
+pub fn discover(root: &Path) -> Vec<PathBuf> {
+    let mut trees = vec![];
+
+    let file = root.join(TREE_FILE);
+    if file.is_file() {
+        trees.push(file);
+    }
+
+    let dir = root.join(TREE_DIR);
+    if dir.is_dir() {
+        let mut entries = vec![];
+        for entry in fs::read_dir(&dir).with_context(|| format!("reading `{}`", dir.display()))? {
+            let path = entry?.path();
+            if path.is_file() && path.extension() == Some("toml".as_ref()) {
+                entries.push(path);
+            }
+        }
+        entries.sort();
+        trees.extend(entries);
+    }
+
+    trees
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,33 @@
+use super::discover;
+use std::fs;
+
+#[test]
+fn discovers_file_and_directory() {
+    let root = std::env::temp_dir().join(format!("skill-tree-discover-{}", std::process::id()));
+    fs::create_dir_all(root.join(".skill-tree")).unwrap();
+    fs::write(root.join("skill-tree.toml"), "").unwrap();
+    fs::write(root.join(".skill-tree/b.toml"), "").unwrap();
+    fs::write(root.join(".skill-tree/a.toml"), "").unwrap();
+    fs::write(root.join(".skill-tree/notes.md"), "").unwrap();
+
+    let trees = discover(&root).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(
+        trees,
+        vec![
+            root.join("skill-tree.toml"),
+            root.join(".skill-tree/a.toml"),
+            root.join(".skill-tree/b.toml"),
+        ]
+    );
+}
+
+#[test]
+fn discovers_nothing() {
+    let root = std::env::temp_dir().join(format!("skill-tree-empty-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    let trees = discover(&root).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+    assert!(trees.is_empty());
+}
@@ -0,0 +1,351 @@
+//! `skill-tree check`: loads and validates trees and reports every problem
+//! found, for CI.
+
+use crate::cache;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::json;
+use skill_tree_core::diagnostic::{Diagnostic, Location, Severity};
+use skill_tree_core::lint::{self, Level};
+use skill_tree_core::source::Source;
+use skill_tree_core::SkillTree;
+use std::path::{Path, PathBuf};
+
+/// Everything wrong with the tree at one path.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    /// The tree couldn't be loaded at all (unreadable file, bad TOML or an
+    /// include that failed).
+    pub load_error: Option<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+
+    /// Every problem above as a JSON message (see `json_message`).
+    pub messages: Vec<serde_json::Value>,
+
+    /// The files the tree was loaded from.
+    pub files: Vec<PathBuf>,
+}
+
+/// How `run` prints problems.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// `error: file: message`, then a summary.
+    Human,
+
+    /// One JSON object per line, like cargo's `--message-format json`:
+    /// `{"code", "level", "message", "file", "span": {"line"}}`. There is
+    /// no summary; the exit code tells how it went.
+    Json,
+
+    /// GitHub Actions workflow commands (`::error file=...,line=...::...`),
+    /// which show up as annotations on the pull request's diff.
+    Github,
+
+    /// A SARIF 2.1.0 log with every problem, e.g. for GitHub code
+    /// scanning.
+    Sarif,
+}
+
+/// How `check` exits, so CI can tell the cases apart.
+pub mod exit_code {
+    /// No errors, and no more warnings than allowed.
+    pub const OK: i32 = 0;
+    /// Validation errors, or more warnings than `--max-warnings`.
+    pub const FAILED: i32 = 1;
+    /// A tree couldn't be loaded.
+    pub const LOAD_ERROR: i32 = 2;
+}
+
+/// Loads and validates the tree at `path`, with the `lints` levels
+/// overriding those in the file.
+pub fn check(path: &Path, lints: &[(&str, Level)]) -> Report {
+    match SkillTree::load(path) {
+        Ok(mut tree) => {
+            for &(name, level) in lints {
+                // `main` has already checked the names.
+                tree.set_lint_level(name, level).unwrap();
+            }
+            Report {
+                files: tree.files().to_vec(),
+                ..check_tree(&tree, Some(path))
+            }
+        }
+        Err(error) => {
+            let message = format!("{:#}", error);
+            Report {
+                messages: vec![json_message(None, "error", &message, Some(path), None)],
+                load_error: Some(message),
+                ..Report::default()
+            }
+        }
+    }
+}
+
+/// Like `check`, but reuses the report cached in `cache` if none of the
+/// tree's files changed, and caches the new report otherwise. Trees that
+/// fail to load aren't cached: the file that is missing or broken may not
+/// be one we know to watch.
+pub fn check_cached(path: &Path, lints: &[(&str, Level)], cache: Option<&Path>) -> Report {
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return check(path, lints),
+    };
+    if let Some(report) = cache::get(cache, path, lints) {
+        return report;
+    }
+    let report = check(path, lints);
+    if report.load_error.is_none() {
+        cache::put(cache, path, lints, &report);
+    }
+    report
+}
+
+/// Validates a loaded tree, prefixing each problem with the `path` it was
+/// loaded from unless the problem already names a line of some file.
+pub fn check_tree(tree: &SkillTree, path: Option<&Path>) -> Report {
+    let mut report = Report::default();
+    for diagnostic in tree.diagnostics() {
+        let message = match (&diagnostic.location, path) {
+            (Some(Location::Line { .. }), _) | (_, None) => diagnostic.to_string(),
+            (_, Some(path)) => format!("{}: {}", path.display(), diagnostic),
+        };
+        let source = diagnostic
+            .location
+            .as_ref()
+            .and_then(|location| source(tree, location));
+        let level = match diagnostic.level {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        report.messages.push(json_message(
+            Some(&diagnostic),
+            level,
+            &diagnostic.message,
+            path,
+            source.as_ref(),
+        ));
+        match diagnostic.level {
+            Severity::Error => report.errors.push(message),
+            Severity::Warning => report.warnings.push(message),
+        }
+    }
+    report
+}
+
+/// The line a diagnostic is about, if we know where that was loaded from.
+fn source(tree: &SkillTree, location: &Location) -> Option<Source> {
+    match location {
+        Location::Line { path, line } => Some(Source {
+            path: path.clone(),
+            line: *line,
+        }),
+        Location::Group(name) | Location::Item { group: name, .. } => {
+            tree.group_named(name)?.source().cloned()
+        }
+        Location::Cluster(name) => tree.cluster_named(name)?.source().cloned(),
+    }
+}
+
+/// A problem as printed with `--message-format json`. The file is where
+/// the problem is if known, else the tree that was checked.
+fn json_message(
+    diagnostic: Option<&Diagnostic>,
+    level: &str,
+    message: &str,
+    path: Option<&Path>,
+    source: Option<&Source>,
+) -> serde_json::Value {
+    let file = source.map(|source| source.path.as_path()).or(path);
+    json!({
+        "code": diagnostic.and_then(|diagnostic| diagnostic.code),
+        "level": level,
+        "message": message,
+        "file": file.map(|file| file.display().to_string()),
+        "span": source.map(|source| json!({ "line": source.line })),
+    })
+}
+
+/// Checks the trees at `paths` (with the reports cached in `cache`, if
+/// given) and prints the problems in the chosen format. Returns the exit
+/// code.
+pub fn run(
+    paths: &[&Path],
+    lints: &[(&str, Level)],
+    max_warnings: Option<usize>,
+    format: MessageFormat,
+    cache: Option<&Path>,
+) -> i32 {
+    let reports: Vec<Report> = paths
+        .iter()
+        .map(|path| check_cached(path, lints, cache))
+        .collect();
+    match format {
+        MessageFormat::Human => {
+            for report in &reports {
+                if let Some(error) = &report.load_error {
+                    println!("error: {}", error);
+                }
+                for error in &report.errors {
+                    println!("error: {}", error);
+                }
+                for warning in &report.warnings {
+                    println!("warning: {}", warning);
+                }
+            }
+        }
+        MessageFormat::Json => {
+            for message in reports.iter().flat_map(|report| &report.messages) {
+                println!("{}", message);
+            }
+        }
+        MessageFormat::Github => {
+            for message in reports.iter().flat_map(|report| &report.messages) {
+                println!("{}", github_annotation(message));
+            }
+        }
+        MessageFormat::Sarif => println!("{:#}", sarif(&reports)),
+    }
+
+    let load_errors = reports.iter().filter(|r| r.load_error.is_some()).count();
+    let errors: usize = reports.iter().map(|r| r.errors.len()).sum::<usize>() + load_errors;
+    let warnings: usize = reports.iter().map(|r| r.warnings.len()).sum();
+    if let MessageFormat::Human | MessageFormat::Github = format {
+        println!(
+            "checked {} file(s): {} error(s), {} warning(s)",
+            paths.len(),
+            errors,
+            warnings
+        );
+    }
+
+    let code = exit_code_for(load_errors, errors, warnings, max_warnings);
+    if code == exit_code::FAILED && errors == 0 {
+        let message = format!(
+            "`--max-warnings` allows {}, but there are {}",
+            max_warnings.unwrap_or_default(),
+            warnings
+        );
+        match format {
+            MessageFormat::Human => println!("error: {}", message),
+            MessageFormat::Json => {
+                println!("{}", json_message(None, "error", &message, None, None))
+            }
+            MessageFormat::Github => println!("::error::{}", escape_data(&message)),
+            // Not a finding in any file; the exit code says it.
+            MessageFormat::Sarif => {}
+        }
+    }
+    code
+}
+
+/// A JSON message (see `json_message`) as a GitHub Actions workflow
+/// command.
+pub fn github_annotation(message: &serde_json::Value) -> String {
+    let mut properties = vec![];
+    if let Some(file) = message["file"].as_str() {
+        properties.push(format!("file={}", escape_property(file)));
+    }
+    if let Some(line) = message["span"]["line"].as_u64() {
+        properties.push(format!("line={}", line));
+    }
+    if let Some(code) = message["code"].as_str() {
+        properties.push(format!("title={}", escape_property(code)));
+    }
+    let level = message["level"].as_str().unwrap_or("error");
+    let text = escape_data(message["message"].as_str().unwrap_or_default());
+    if properties.is_empty() {
+        format!("::{}::{}", level, text)
+    } else {
+        format!("::{} {}::{}", level, properties.join(","), text)
+    }
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// A SARIF log of the problems in `reports`, with a rule for each lint.
+/// Problems that aren't lints (validation and load errors) have the rule
+/// `error`.
+pub fn sarif(reports: &[Report]) -> serde_json::Value {
+    let rules: Vec<serde_json::Value> = lint::LINTS
+        .iter()
+        .map(|lint| {
+            json!({
+                "id": lint.name,
+                "shortDescription": { "text": lint.description },
+            })
+        })
+        .chain(std::iter::once(json!({
+            "id": "error",
+            "shortDescription": { "text": "a skill tree that can't be loaded or is invalid" },
+        })))
+        .collect();
+    let results: Vec<serde_json::Value> = reports
+        .iter()
+        .flat_map(|report| &report.messages)
+        .map(|message| {
+            let locations: Vec<serde_json::Value> = message["file"]
+                .as_str()
+                .map(|file| {
+                    let mut location = json!({
+                        "physicalLocation": { "artifactLocation": { "uri": uri(file) } },
+                    });
+                    if let Some(line) = message["span"]["line"].as_u64() {
+                        location["physicalLocation"]["region"] = json!({ "startLine": line });
+                    }
+                    location
+                })
+                .into_iter()
+                .collect();
+            json!({
+                "ruleId": message["code"].as_str().unwrap_or("error"),
+                "level": message["level"],
+                "message": { "text": message["message"] },
+                "locations": locations,
+            })
+        })
+        .collect();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "skill-tree",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// A path as a relative URI, which SARIF viewers resolve against the
+/// repository root.
+fn uri(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+fn exit_code_for(load_errors: usize, errors: usize, warnings: usize, max: Option<usize>) -> i32 {
+    if load_errors > 0 {
+        exit_code::LOAD_ERROR
+    } else if errors > 0 || max.is_some_and(|max| warnings > max) {
+        exit_code::FAILED
+    } else {
+        exit_code::OK
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,141 @@
+use super::{check, check_tree, exit_code as code, exit_code_for, github_annotation, sarif};
+use skill_tree_core::lint::Level;
+use skill_tree_core::SkillTree;
+use std::path::Path;
+
+#[test]
+fn clean_tree() {
+    let report = check(Path::new("../skill-tree-core/test-data/locale.toml"), &[]);
+    assert!(report.load_error.is_none());
+    assert!(report.errors.is_empty());
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn reports_errors_and_warnings() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+locale = "tlh"
+
+[[group]]
+name = "a"
+requires = ["missing"]
+items = []
+"#,
+    )
+    .unwrap();
+    let report = check_tree(&tree, None);
+    assert_eq!(
+        report.errors,
+        ["the group `a` has a dependency on a group `missing` that does not exist"]
+    );
+    assert_eq!(report.warnings.len(), 1);
+    assert!(
+        report.warnings[0].contains("`tlh`"),
+        "{:?}",
+        report.warnings
+    );
+}
+
+#[test]
+fn reports_load_errors() {
+    let report = check(Path::new("does-not-exist.toml"), &[]);
+    let error = report.load_error.unwrap();
+    assert!(
+        error.starts_with("loading skill tree from `does-not-exist.toml`"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn exit_codes() {
+    assert_eq!(exit_code_for(0, 0, 3, None), code::OK);
+    assert_eq!(exit_code_for(0, 0, 3, Some(3)), code::OK);
+    assert_eq!(exit_code_for(0, 0, 3, Some(2)), code::FAILED);
+    assert_eq!(exit_code_for(0, 1, 0, None), code::FAILED);
+    assert_eq!(exit_code_for(1, 1, 0, None), code::LOAD_ERROR);
+}
+
+#[test]
+fn overrides_lint_levels() {
+    let path = Path::new("../skill-tree-core/test-data/locale.toml");
+    let report = check(path, &[("missing-href", Level::Deny)]);
+    assert_eq!(
+        report.errors,
+        [
+            "../skill-tree-core/test-data/locale.toml: the group `parser` has no `href` [missing-href]",
+            "../skill-tree-core/test-data/locale.toml: the group `codegen` has no `href` [missing-href]",
+        ]
+    );
+}
+
+#[test]
+fn json_messages() {
+    let path = Path::new("../skill-tree-core/test-data/provenance.toml");
+    let report = check(path, &[]);
+    assert_eq!(
+        report.messages,
+        [serde_json::json!({
+            "code": null,
+            "level": "error",
+            "message": "the group `parser` has a dependency on a group `lexer` that does not exist",
+            "file": "../skill-tree-core/test-data/provenance_team.toml",
+            "span": { "line": 5 },
+        })]
+    );
+
+    let report = check(Path::new("does-not-exist.toml"), &[]);
+    assert_eq!(report.messages[0]["file"], "does-not-exist.toml");
+    assert_eq!(report.messages[0]["span"], serde_json::Value::Null);
+}
+
+#[test]
+fn sarif_log() {
+    let report = check(
+        Path::new("../skill-tree-core/test-data/provenance.toml"),
+        &[],
+    );
+    let log = sarif(&[report]);
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert!(run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|rule| rule["id"] == "orphan-group"));
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "error");
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "../skill-tree-core/test-data/provenance_team.toml"
+    );
+    assert_eq!(location["region"]["startLine"], 5);
+}
+
+#[test]
+fn github_annotations() {
+    let message = serde_json::json!({
+        "code": "missing-emoji",
+        "level": "warning",
+        "message": "50% done,\nnearly",
+        "file": "trees/a,b.toml",
+        "span": { "line": 3 },
+    });
+    assert_eq!(
+        github_annotation(&message),
+        "::warning file=trees/a%2Cb.toml,line=3,title=missing-emoji::50%25 done,%0Anearly"
+    );
+
+    let message = serde_json::json!({
+        "code": null,
+        "level": "error",
+        "message": "no such file",
+        "file": null,
+        "span": null,
+    });
+    assert_eq!(github_annotation(&message), "::error::no such file");
+}
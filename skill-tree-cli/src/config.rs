@@ -0,0 +1,237 @@
+//! The user's configuration file, `~/.config/skill-tree/config.toml`,
+//! which supplies defaults for options that would otherwise be repeated
+//! on every invocation. Flags and the tree's own settings always win.
+
+use anyhow::Context;
+use fehler::throws;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde_derive::Deserialize;
+use skill_tree_core::{Graphviz, SkillTree};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that overrides where we look for the
+/// configuration file.
+pub const CONFIG_ENV_VAR: &str = "SKILL_TREE_CONFIG";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Default for `render --emit`.
+    pub emit: Option<String>,
+
+    /// Path of the graphviz `dot` executable, used unless
+    /// `SKILL_TREE_DOT` is set.
+    pub dot: Option<PathBuf>,
+
+    /// Name of the environment variable holding the GitHub token for
+    /// `import github-project` (defaults to `GITHUB_TOKEN`). The token
+    /// itself is never stored here.
+    pub github_token_env: Option<String>,
+
+    /// `[graphviz]` settings for every tree, used where the tree doesn't
+    /// set them itself.
+    #[serde(default, deserialize_with = "deserialize_theme")]
+    pub theme: Option<Graphviz>,
+}
+
+impl Config {
+    /// The environment variable to read the GitHub token from.
+    pub fn github_token_env(&self) -> &str {
+        self.github_token_env.as_deref().unwrap_or("GITHUB_TOKEN")
+    }
+
+    /// Fills in the `[graphviz]` settings that `tree` leaves unset from
+    /// the theme.
+    pub fn apply_theme(&self, tree: &mut SkillTree) {
+        let theme = match &self.theme {
+            Some(theme) => theme,
+            None => return,
+        };
+        let graphviz = tree.graphviz.get_or_insert_with(Default::default);
+        if graphviz.rankdir.is_none() {
+            graphviz.rankdir = theme.rankdir;
+        }
+        if graphviz.fontsize.is_none() {
+            graphviz.fontsize = theme.fontsize;
+        }
+        if graphviz.node_fontsize.is_none() {
+            graphviz.node_fontsize = theme.node_fontsize;
+        }
+        if graphviz.margin.is_none() {
+            graphviz.margin = theme.margin;
+        }
+        if graphviz.nodesep.is_none() {
+            graphviz.nodesep = theme.nodesep;
+        }
+        if graphviz.ranksep.is_none() {
+            graphviz.ranksep = theme.ranksep;
+        }
+        if graphviz.concentrate.is_none() {
+            graphviz.concentrate = theme.concentrate;
+        }
+        if graphviz.splines.is_none() {
+            graphviz.splines = theme.splines;
+        }
+        if graphviz.overlap.is_none() {
+            graphviz.overlap = theme.overlap;
+        }
+        if graphviz.cluster_colors.is_none() {
+            graphviz.cluster_colors = theme.cluster_colors;
+        }
+        if graphviz.tint_headers.is_none() {
+            graphviz.tint_headers = theme.tint_headers;
+        }
+        for (attrs, defaults) in [
+            (&mut graphviz.graph_attrs, &theme.graph_attrs),
+            (&mut graphviz.node_attrs, &theme.node_attrs),
+            (&mut graphviz.edge_attrs, &theme.edge_attrs),
+        ] {
+            // The tree's own attributes win over the theme's, one by one.
+            if let Some(defaults) = defaults {
+                let attrs = attrs.get_or_insert_with(Default::default);
+                for (name, value) in defaults {
+                    attrs.entry(name.clone()).or_insert_with(|| value.clone());
+                }
+            }
+        }
+        for (value, default) in [
+            (&mut graphviz.fontname, &theme.fontname),
+            (&mut graphviz.bgcolor, &theme.bgcolor),
+            (&mut graphviz.size, &theme.size),
+            (&mut graphviz.ratio, &theme.ratio),
+            (&mut graphviz.page, &theme.page),
+        ] {
+            if value.is_none() {
+                value.clone_from(default);
+            }
+        }
+    }
+}
+
+/// Where the configuration file lives: `$SKILL_TREE_CONFIG`, or
+/// `skill-tree/config.toml` under `$XDG_CONFIG_HOME` (defaulting to
+/// `~/.config`), or under `%APPDATA%` on Windows.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => PathBuf::from(env::var_os("APPDATA")?),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("skill-tree").join("config.toml"))
+}
+
+/// Reads `[theme]` like a tree's `[graphviz]`, except that unknown keys
+/// are errors: a tree can be checked with `skill-tree check`, but a typo
+/// here would otherwise go unnoticed.
+fn deserialize_theme<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Graphviz>, D::Error> {
+    let table = toml::value::Table::deserialize(deserializer)?;
+    let known = field_names::<Graphviz>();
+    if let Some(key) = table.keys().find(|key| !known.contains(&&key[..])) {
+        return Err(de::Error::unknown_field(key, known));
+    }
+    toml::Value::Table(table)
+        .try_into()
+        .map(Some)
+        .map_err(de::Error::custom)
+}
+
+/// The names of the fields of the struct `T`, which serde hands to the
+/// deserializer when it asks for a struct.
+fn field_names<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    struct FieldNames(&'static [&'static str]);
+
+    impl<'de> Deserializer<'de> for &mut FieldNames {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            fields: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0 = fields;
+            Err(de::Error::custom("only the field names were wanted"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut names = FieldNames(&[]);
+    let _ = T::deserialize(&mut names);
+    names.0
+}
+
+/// Formats that `emit` may name; the same as `render --emit`.
+pub fn emit_formats() -> &'static [&'static str] {
+    static FORMATS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    FORMATS.get_or_init(|| {
+        let mut formats = crate::GRAPHVIZ_FORMATS.to_vec();
+        for name in crate::renderers().names() {
+            if !formats.contains(&name) {
+                formats.push(Box::leak(name.to_string().into_boxed_str()));
+            }
+        }
+        formats
+    })
+}
+
+/// Parses the configuration in `text`.
+#[throws(anyhow::Error)]
+pub fn parse(text: &str) -> Config {
+    let config: Config = toml::from_str(text)?;
+    if let Some(emit) = &config.emit {
+        if !emit_formats().contains(&&emit[..]) {
+            anyhow::bail!(
+                "unknown `emit = \"{}\"`, expected one of {}",
+                emit,
+                emit_formats().join(", ")
+            );
+        }
+    }
+    config
+}
+
+/// Reads the configuration file at `path`; a missing file is the same as
+/// an empty one.
+#[throws(anyhow::Error)]
+pub fn load(path: &Path) -> Config {
+    match std::fs::read_to_string(path) {
+        Ok(text) => parse(&text).with_context(|| format!("loading `{}`", path.display()))?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Config::default(),
+        Err(error) => Err(error).with_context(|| format!("reading `{}`", path.display()))?,
+    }
+}
+
+/// The user's configuration, loaded from `path` the first time it is
+/// needed, so commands that don't use it work with a broken one.
+#[throws(anyhow::Error)]
+pub fn get() -> &'static Config {
+    if let Some(config) = CONFIG.get() {
+        return config;
+    }
+    let config = match path() {
+        Some(path) => load(&path)?,
+        None => Config::default(),
+    };
+    CONFIG.get_or_init(|| config)
+}
+
+#[cfg(test)]
+mod test;
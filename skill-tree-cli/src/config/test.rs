@@ -0,0 +1,102 @@
+use super::{emit_formats, load, parse};
+use skill_tree_core::{Rankdir, SkillTree};
+use std::path::Path;
+
+const CONFIG: &str = r#"
+emit = "svg"
+dot = "/opt/graphviz/bin/dot"
+github_token_env = "SKILL_TREE_GITHUB_TOKEN"
+
+[theme]
+rankdir = "top-to-bottom"
+fontname = "Inter"
+bgcolor = "transparent"
+"#;
+
+#[test]
+fn reads_settings() {
+    let config = parse(CONFIG).unwrap();
+    assert_eq!(config.emit.as_deref(), Some("svg"));
+    assert_eq!(
+        config.dot.as_deref(),
+        Some(Path::new("/opt/graphviz/bin/dot"))
+    );
+    assert_eq!(config.github_token_env(), "SKILL_TREE_GITHUB_TOKEN");
+}
+
+#[test]
+fn defaults_when_empty() {
+    let config = parse("").unwrap();
+    assert_eq!(config.emit, None);
+    assert_eq!(config.github_token_env(), "GITHUB_TOKEN");
+}
+
+#[test]
+fn missing_file_is_empty() {
+    let config = load(Path::new("does/not/exist/config.toml")).unwrap();
+    assert!(config.theme.is_none());
+}
+
+#[test]
+fn rejects_unknown_emit() {
+    let error = parse("emit = \"jpeg\"").unwrap_err();
+    assert!(
+        error.to_string().contains("unknown `emit = \"jpeg\"`"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    assert!(parse("emitt = \"svg\"").is_err());
+}
+
+#[test]
+fn rejects_unknown_theme_keys() {
+    let error = parse("[theme]\nrankdri = \"LR\"\n").unwrap_err();
+    assert!(
+        error.to_string().contains("unknown field `rankdri`"),
+        "{}",
+        error
+    );
+    assert!(parse("[theme]\nrankdir = \"sideways\"\n").is_err());
+}
+
+#[test]
+fn emits_graphviz_formats_and_renderers() {
+    for format in [
+        "svg",
+        "pdf",
+        "preview-json",
+        "dot",
+        "mermaid",
+        "timeline",
+        "atom",
+    ] {
+        assert!(emit_formats().contains(&format), "{}", format);
+    }
+    assert!(parse("emit = \"adjacency\"").is_ok());
+}
+
+#[test]
+fn theme_is_under_the_tree() {
+    let config = parse(CONFIG).unwrap();
+    let mut tree = SkillTree::parse("[graphviz]\nbgcolor = \"white\"\n").unwrap();
+    config.apply_theme(&mut tree);
+    let graphviz = tree.graphviz.unwrap();
+    assert_eq!(graphviz.bgcolor.as_deref(), Some("white"));
+    assert_eq!(graphviz.fontname.as_deref(), Some("Inter"));
+    assert_eq!(graphviz.rankdir, Some(Rankdir::TopToBottom));
+    assert_eq!(graphviz.size, None);
+}
+
+#[test]
+fn theme_attrs_merge_with_the_tree() {
+    let config = parse("[theme.node_attrs]\npenwidth = \"2\"\nstyle = \"filled\"\n").unwrap();
+    let mut tree = SkillTree::parse("[graphviz.node_attrs]\nstyle = \"dashed\"\n").unwrap();
+    config.apply_theme(&mut tree);
+    let attrs = tree.graphviz.unwrap().node_attrs.unwrap();
+    assert_eq!(attrs["penwidth"], "2");
+    assert_eq!(attrs["style"], "dashed");
+}
@@ -0,0 +1,264 @@
+//! `skill-tree daemon`: keeps trees loaded and answers JSON-RPC 2.0
+//! requests about them, so editor plugins and dashboards don't pay for
+//! parsing a large tree (and its includes) on every question.
+//!
+//! Requests and responses are one JSON object per line, over stdio or,
+//! with `--socket`, over a unix socket that any number of clients can
+//! connect to. Every method takes the `path` of a tree, which is loaded
+//! the first time it is asked about and again whenever one of its files
+//! changes. The methods are:
+//!
+//! * `validate {path}`: the tree's `errors` and `warnings`, and the same
+//!   as `messages` in the format of `check --message-format json`;
+//! * `render {path, emit}`: the `output` of one of the renderers `render
+//!   --emit` knows without graphviz (`dot` by default);
+//! * `query {path, filter}`: the groups passing a `--select` expression,
+//!   as `{name, label, cluster, status}`;
+//! * `diff {path, baseline}`: what changed since the tree at `baseline`,
+//!   as `notify` summarizes it.
+//!
+//! The method `exit` ends the session (for a socket, just that client's).
+
+use crate::check;
+use crate::jsonrpc::{METHOD_NOT_FOUND, PARSE_ERROR};
+use crate::notify;
+use anyhow::Context;
+use fehler::throws;
+use serde_json::{json, Value};
+use skill_tree_core::filter::Filter;
+use skill_tree_core::SkillTree;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// The code for a request that failed, e.g. because the tree couldn't be
+/// loaded.
+const REQUEST_FAILED: i64 = -32000;
+
+/// The trees loaded so far, by canonical path.
+#[derive(Default)]
+pub struct Daemon {
+    trees: HashMap<PathBuf, Loaded>,
+}
+
+struct Loaded {
+    tree: Arc<SkillTree>,
+
+    /// Each file the tree was loaded from, with what it looked like then.
+    files: Vec<(PathBuf, Option<Stat>)>,
+}
+
+/// Enough about a file to tell that it changed.
+type Stat = (SystemTime, u64);
+
+fn stat(path: &Path) -> Option<Stat> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl Daemon {
+    /// Handles a request, returning `None` if the method is unsupported.
+    pub fn request(&mut self, method: &str, params: &Value) -> Option<anyhow::Result<Value>> {
+        let result = match method {
+            "validate" => self.validate(params),
+            "render" => self.render(params),
+            "query" => self.query(params),
+            "diff" => self.diff(params),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// The tree at `path`, loaded the first time it is asked for and
+    /// again if any of its files changed since.
+    #[throws(anyhow::Error)]
+    pub fn tree(&mut self, path: &Path) -> Arc<SkillTree> {
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("loading skill tree from `{}`", path.display()))?;
+        let fresh = match self.trees.get(&path) {
+            Some(loaded) => loaded.files.iter().all(|(file, then)| stat(file) == *then),
+            None => false,
+        };
+        if !fresh {
+            let tree = SkillTree::load(&path)
+                .with_context(|| format!("loading skill tree from `{}`", path.display()))?;
+            let files = tree
+                .files()
+                .iter()
+                .map(|file| (file.clone(), stat(file)))
+                .collect();
+            let tree = Arc::new(tree);
+            self.trees.insert(path.clone(), Loaded { tree, files });
+        }
+        self.trees[&path].tree.clone()
+    }
+
+    #[throws(anyhow::Error)]
+    fn validate(&mut self, params: &Value) -> Value {
+        let path = param(params, "path")?;
+        let tree = self.tree(Path::new(path))?;
+        let report = check::check_tree(&tree, Some(Path::new(path)));
+        json!({
+            "errors": report.errors,
+            "warnings": report.warnings,
+            "messages": report.messages,
+        })
+    }
+
+    #[throws(anyhow::Error)]
+    fn render(&mut self, params: &Value) -> Value {
+        let path = param(params, "path")?;
+        let emit = params["emit"].as_str().unwrap_or("dot");
+        let tree = self.tree(Path::new(path))?;
+        tree.validate()?;
+        json!({ "output": crate::renderers().render_to_string(emit, &tree)? })
+    }
+
+    #[throws(anyhow::Error)]
+    fn query(&mut self, params: &Value) -> Value {
+        let path = param(params, "path")?;
+        let filter = Filter::parse(param(params, "filter")?)?;
+        let tree = self.tree(Path::new(path))?;
+        let groups: Vec<Value> = tree
+            .groups_matching(&filter)
+            .map(|group| {
+                json!({
+                    "name": group.name,
+                    "label": group.label.as_ref().unwrap_or(&group.name),
+                    "cluster": group.cluster,
+                    "status": group.status.as_ref().map(|status| tree.status_name(status)),
+                })
+            })
+            .collect();
+        json!(groups)
+    }
+
+    #[throws(anyhow::Error)]
+    fn diff(&mut self, params: &Value) -> Value {
+        let new = self.tree(Path::new(param(params, "path")?))?;
+        let old = self.tree(Path::new(param(params, "baseline")?))?;
+        let summary = notify::summarize(&old, &new);
+        json!({
+            "completed": summary.completed,
+            "blocked": summary.blocked,
+            "added": summary.added,
+            "removed": summary.removed,
+            "before": { "done": summary.before.0, "total": summary.before.1 },
+            "after": { "done": summary.after.0, "total": summary.after.1 },
+        })
+    }
+}
+
+/// The string parameter `name`.
+#[throws(anyhow::Error)]
+fn param<'p>(params: &'p Value, name: &str) -> &'p str {
+    match params[name].as_str() {
+        Some(value) => value,
+        None => anyhow::bail!("missing the string parameter `{}`", name),
+    }
+}
+
+/// Answers the requests read from `input` on `output`, until `exit` or the
+/// end of the input.
+#[throws(anyhow::Error)]
+pub fn serve(daemon: &Mutex<Daemon>, input: impl BufRead, mut output: impl Write) {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(error) => {
+                let response = failure(&Value::Null, PARSE_ERROR, error.to_string());
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+                continue;
+            }
+        };
+        let method = message["method"].as_str().unwrap_or("");
+        if method == "exit" {
+            break;
+        }
+
+        // A poisoned lock only means another client's request panicked;
+        // the trees themselves are still fine.
+        let result = daemon
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .request(method, &message["params"]);
+        let id = match message.get("id") {
+            Some(id) => id,
+            None => continue,
+        };
+        let response = match result {
+            Some(Ok(result)) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Some(Err(error)) => failure(id, REQUEST_FAILED, format!("{:#}", error)),
+            None => failure(
+                id,
+                METHOD_NOT_FOUND,
+                format!("unsupported method `{}`", method),
+            ),
+        };
+        writeln!(output, "{}", response)?;
+        output.flush()?;
+    }
+}
+
+fn failure(id: &Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Serves stdin and stdout.
+#[throws(anyhow::Error)]
+pub fn run_stdio() {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    serve(&Mutex::new(Daemon::default()), stdin.lock(), stdout.lock())?;
+}
+
+/// Serves every client connecting to a unix socket at `path`, each on its
+/// own thread, sharing the loaded trees. A socket left over from an
+/// earlier run is replaced.
+#[cfg(unix)]
+#[throws(anyhow::Error)]
+pub fn run_socket(path: &Path) {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            anyhow::bail!("`{}` exists and isn't a socket", path.display());
+        }
+        std::fs::remove_file(path)?;
+    }
+    let listener =
+        UnixListener::bind(path).with_context(|| format!("listening on `{}`", path.display()))?;
+    let daemon = Arc::new(Mutex::new(Daemon::default()));
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let daemon = daemon.clone();
+        std::thread::spawn(move || {
+            let input = std::io::BufReader::new(&stream);
+            if let Err(error) = serve(&daemon, input, &stream) {
+                eprintln!("skill-tree daemon: {:#}", error);
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+#[throws(anyhow::Error)]
+pub fn run_socket(_path: &Path) {
+    anyhow::bail!("`--socket` is only supported on unix systems");
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,130 @@
+use super::{serve, Daemon};
+use crate::test::scratch;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+const TREE: &str = r#"[[group]]
+name = "lexer"
+label = "Lexer"
+status = "Complete"
+items = [{ label = "Tokens", status = "Complete" }]
+
+[[group]]
+name = "parser"
+requires = ["lexer"]
+status = "Blocked"
+items = [{ label = "Expressions" }]
+"#;
+
+fn request(daemon: &mut Daemon, method: &str, params: Value) -> Value {
+    daemon.request(method, &params).unwrap().unwrap()
+}
+
+#[test]
+fn answers_about_a_tree() {
+    let dir = scratch("daemon");
+    let path = dir.join("tree.toml");
+    std::fs::write(&path, TREE).unwrap();
+    let path = path.to_str().unwrap();
+    let mut daemon = Daemon::default();
+
+    let report = request(&mut daemon, "validate", json!({ "path": path }));
+    assert_eq!(report["errors"], json!([]));
+
+    let groups = request(
+        &mut daemon,
+        "query",
+        json!({ "path": path, "filter": "status=blocked" }),
+    );
+    assert_eq!(
+        groups,
+        json!([{ "name": "parser", "label": "parser", "cluster": null, "status": "Blocked" }])
+    );
+
+    let rendered = request(
+        &mut daemon,
+        "render",
+        json!({ "path": path, "emit": "edges" }),
+    );
+    assert_eq!(
+        rendered["output"],
+        "from,to,port,kind\nlexer,parser,,requires\n"
+    );
+
+    let error = daemon
+        .request("render", &json!({ "path": path, "emit": "edgez" }))
+        .unwrap()
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("did you mean `edges`"),
+        "{}",
+        error
+    );
+    assert!(daemon.request("compile", &json!({})).is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reloads_changed_trees() {
+    let dir = scratch("daemon-reload");
+    let path = dir.join("tree.toml");
+    std::fs::write(&path, TREE).unwrap();
+    let baseline = dir.join("baseline.toml");
+    std::fs::write(&baseline, TREE).unwrap();
+    let params = json!({
+        "path": path.to_str().unwrap(),
+        "baseline": baseline.to_str().unwrap(),
+    });
+    let mut daemon = Daemon::default();
+
+    let diff = request(&mut daemon, "diff", params.clone());
+    assert_eq!(diff["completed"], json!([]));
+
+    std::fs::write(
+        &path,
+        TREE.replace("status = \"Blocked\"", "status = \"Complete\"")
+            + "\n[[group]]\nname = \"checker\"\nitems = []\n",
+    )
+    .unwrap();
+    let diff = request(&mut daemon, "diff", params);
+    assert_eq!(diff["completed"], json!(["parser"]));
+    assert_eq!(diff["added"], json!(["checker"]));
+    assert_eq!(diff["after"], json!({ "done": 2, "total": 2 }));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn one_response_per_line() {
+    let input = [
+        r#"{"jsonrpc": "2.0", "id": 1, "method": "validate", "params": {}}"#,
+        r#"{"jsonrpc": "2.0", "method": "validate", "params": {}}"#,
+        "not json",
+        r#"{"jsonrpc": "2.0", "id": 2, "method": "compile"}"#,
+        r#"{"jsonrpc": "2.0", "method": "exit"}"#,
+        r#"{"jsonrpc": "2.0", "id": 3, "method": "compile"}"#,
+    ]
+    .join("\n");
+    let mut output = vec![];
+    serve(
+        &Mutex::new(Daemon::default()),
+        input.as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    let responses: Vec<Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(responses.len(), 3);
+    assert_eq!(
+        responses[0]["error"]["message"],
+        "missing the string parameter `path`"
+    );
+    assert_eq!(responses[1]["id"], Value::Null);
+    assert_eq!(responses[1]["error"]["code"], -32700);
+    assert_eq!(responses[2]["id"], 2);
+    assert_eq!(responses[2]["error"]["code"], -32601);
+}
@@ -0,0 +1,93 @@
+//! `skill-tree doctor`: checks that the user configuration can be read
+//! and that graphviz is installed and usable.
+
+use crate::config;
+use crate::graphviz::{self, Graphviz};
+use fehler::throws;
+
+/// A tiny graph using an emoji in an HTML-like label, like the ones we
+/// generate.
+const PROBE: &str =
+    r#"digraph g { a [ label = <<table><tr><td>🙋</td></tr></table>> shape = "none" ] }"#;
+
+#[throws(anyhow::Error)]
+pub fn run() {
+    let mut problems = 0;
+
+    // A broken configuration is reported, and graphviz looked for as if
+    // there were none.
+    let mut config_dot = None;
+    if let Some(path) = config::path().filter(|path| path.is_file()) {
+        match config::get() {
+            Ok(config) => {
+                println!("ok: read the configuration from {}", path.display());
+                config_dot = config.dot.clone();
+            }
+            Err(error) => {
+                println!("error: {:#}", error);
+                problems += 1;
+            }
+        }
+    }
+
+    let graphviz = match graphviz::detect_with(config_dot) {
+        Ok(graphviz) => graphviz,
+        Err(error) => {
+            println!("error: {}", error);
+            anyhow::bail!("graphviz is not usable");
+        }
+    };
+    println!("ok: found `dot` at {}", graphviz.path.display());
+
+    problems += check_version(&graphviz);
+
+    match graphviz::run(&graphviz.path, PROBE, "svg") {
+        Ok(_) => println!("ok: `dot` can render HTML-like labels to SVG"),
+        Err(error) => {
+            println!("error: rendering a test graph failed: {:#}", error);
+            problems += 1;
+        }
+    }
+
+    match graphviz::has_emoji_font() {
+        Some(true) => println!("ok: an emoji font is installed"),
+        Some(false) => println!(
+            "warning: no emoji font found; status emoji will render as boxes \
+             (install e.g. Noto Color Emoji)"
+        ),
+        None => println!("note: could not check for emoji fonts (`fc-list` not available)"),
+    }
+
+    if problems > 0 {
+        anyhow::bail!(
+            "found {} problem(s) with the configuration or the graphviz installation",
+            problems
+        );
+    }
+}
+
+fn check_version(graphviz: &Graphviz) -> usize {
+    let (major, minor) = graphviz::MIN_VERSION;
+    match graphviz.version {
+        Some((a, b, c)) if (a, b) >= (major, minor) => {
+            println!("ok: graphviz version {}.{}.{}", a, b, c);
+            0
+        }
+        Some((a, b, c)) => {
+            println!(
+                "error: graphviz version {}.{}.{} is older than {}.{}; {}",
+                a,
+                b,
+                c,
+                major,
+                minor,
+                graphviz::install_hint()
+            );
+            1
+        }
+        None => {
+            println!("warning: could not determine the graphviz version from `dot -V`");
+            0
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! `skill-tree embed`: keeps a picture of a tree in a Markdown file
+//! (usually a README) up to date. The picture goes between
+//! `<!-- skill-tree:begin -->` and `<!-- skill-tree:end -->`, replacing
+//! whatever is there, so rerunning it (say, in CI) whenever the tree
+//! changes keeps the docs from drifting.
+//!
+//! The begin marker can hold options, as `key=value` words:
+//!
+//! * `tree=roadmap.toml`: the tree to embed, relative to the Markdown
+//!   file (otherwise `--tree`);
+//! * `image=docs/roadmap.svg`: link to this image of the tree instead of
+//!   drawing it as a Mermaid flowchart.
+//!
+//! Either way, a table of how far along each cluster is follows.
+
+use fehler::throws;
+use skill_tree_core::render::Renderers;
+use skill_tree_core::SkillTree;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+pub const BEGIN: &str = "<!-- skill-tree:begin";
+pub const END: &str = "<!-- skill-tree:end -->";
+
+/// The options a begin marker can have.
+const OPTIONS: &[&str] = &["tree", "image"];
+
+/// A generated part of a Markdown file.
+#[derive(Debug)]
+pub struct Region<'m> {
+    /// The options from its begin marker.
+    pub options: BTreeMap<&'m str, &'m str>,
+
+    /// What is between its markers.
+    content: Range<usize>,
+}
+
+/// The regions of `markdown`, in order.
+#[throws(anyhow::Error)]
+pub fn regions(markdown: &str) -> Vec<Region<'_>> {
+    let line = |offset: usize| markdown[..offset].lines().count() + 1;
+    let mut regions = vec![];
+    let mut from = 0;
+    while let Some(begin) = markdown[from..].find(BEGIN).map(|at| from + at) {
+        let options_start = begin + BEGIN.len();
+        let close = match markdown[options_start..].find("-->") {
+            Some(close) => options_start + close,
+            None => anyhow::bail!(
+                "the marker on line {} is never closed with `-->`",
+                line(begin)
+            ),
+        };
+        let mut options = BTreeMap::new();
+        for option in markdown[options_start..close].split_whitespace() {
+            match option.split_once('=') {
+                Some((key, value)) if OPTIONS.contains(&key) => {
+                    options.insert(key, value);
+                }
+                _ => anyhow::bail!(
+                    "unknown option `{}` in the marker on line {}, expected `tree=...` or `image=...`",
+                    option,
+                    line(begin)
+                ),
+            }
+        }
+        let start = close + "-->".len();
+        let end = match markdown[start..].find(END) {
+            Some(end) => start + end,
+            None => anyhow::bail!(
+                "the marker on line {} has no `{}` after it",
+                line(begin),
+                END
+            ),
+        };
+        if let Some(nested) = markdown[start..end].find(BEGIN) {
+            anyhow::bail!(
+                "the marker on line {} comes before the `{}` of the one on line {}",
+                line(start + nested),
+                END,
+                line(begin)
+            );
+        }
+        regions.push(Region {
+            options,
+            content: start..end,
+        });
+        from = end + END.len();
+    }
+    regions
+}
+
+/// `markdown` with what is in each region replaced by what `generate`
+/// returns for it.
+#[throws(anyhow::Error)]
+pub fn update<'m>(
+    markdown: &'m str,
+    mut generate: impl FnMut(&Region<'m>) -> anyhow::Result<String>,
+) -> String {
+    let mut output = String::new();
+    let mut copied = 0;
+    for region in regions(markdown)? {
+        output.push_str(&markdown[copied..region.content.start]);
+        output.push('\n');
+        output.push_str(&generate(&region)?);
+        copied = region.content.end;
+    }
+    output.push_str(&markdown[copied..]);
+    output
+}
+
+/// What goes in a region for `tree`: a Mermaid flowchart of it, or a
+/// link to `image`, and a table of its progress.
+#[throws(anyhow::Error)]
+pub fn section(tree: &SkillTree, image: Option<&str>) -> String {
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+    let picture = match image {
+        Some(image) => format!("![{}]({})\n", title.replace(['[', ']'], ""), image),
+        None => format!(
+            "```mermaid\n{}```\n",
+            Renderers::default().render_to_string("mermaid", tree)?
+        ),
+    };
+    format!("{}\n{}", picture, progress_table(tree))
+}
+
+/// A Markdown table with a row for each cluster (when there are any) and
+/// one for the whole tree, counting groups and items done.
+pub fn progress_table(tree: &SkillTree) -> String {
+    let mut table = format!(
+        "| {} | {} | {} |\n| --- | ---: | ---: |\n",
+        tree.translate("Cluster"),
+        tree.translate("Groups"),
+        tree.translate("Items done")
+    );
+    let row = |label: &str, groups: Vec<_>| {
+        let (done, total) = tree.progress(groups.iter().copied());
+        let progress = match total {
+            0 => "0/0".to_string(),
+            _ => format!("{}/{} ({}%)", done, total, done * 100 / total),
+        };
+        format!(
+            "| {} | {} | {} |\n",
+            label.replace('|', "\\|"),
+            groups.len(),
+            progress
+        )
+    };
+    let pages = tree.pages();
+    if pages != [None] {
+        for cluster in pages {
+            let label = match cluster {
+                Some(name) => tree.cluster_named(name).map_or(name, |c| &c.label[..]),
+                None => tree.translate("Other groups"),
+            };
+            table.push_str(&row(label, tree.groups_in(cluster).collect()));
+        }
+    }
+    let total = format!("**{}**", tree.translate("Total"));
+    table.push_str(&row(&total, tree.groups().collect()));
+    table
+}
+
+#[cfg(test)]
+mod test;
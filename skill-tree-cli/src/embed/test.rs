@@ -0,0 +1,92 @@
+use super::{regions, section, update};
+use skill_tree_core::SkillTree;
+
+const TREE: &str = r#"
+[doc]
+title = "Compiler"
+
+[[cluster]]
+name = "frontend"
+label = "Front end"
+
+[[group]]
+name = "lexer"
+cluster = "frontend"
+items = [{ label = "tokens", status = "Complete" }]
+
+[[group]]
+name = "types"
+requires = ["lexer"]
+items = [{ label = "check" }, { label = "infer" }, { label = "unify" }]
+"#;
+
+#[test]
+fn replaces_each_region() {
+    let markdown = "# Roadmap\n\
+                    <!-- skill-tree:begin tree=roadmap.toml -->\nold\n<!-- skill-tree:end -->\n\
+                    Between\n\
+                    <!-- skill-tree:begin image=tree.svg -->\n<!-- skill-tree:end -->\n";
+    let mut seen = vec![];
+    let updated = update(markdown, |region| {
+        seen.push(region.options.clone());
+        Ok(format!("new {}\n", seen.len()))
+    })
+    .unwrap();
+    assert_eq!(
+        updated,
+        "# Roadmap\n\
+         <!-- skill-tree:begin tree=roadmap.toml -->\nnew 1\n<!-- skill-tree:end -->\n\
+         Between\n\
+         <!-- skill-tree:begin image=tree.svg -->\nnew 2\n<!-- skill-tree:end -->\n"
+    );
+    assert_eq!(seen[0]["tree"], "roadmap.toml");
+    assert_eq!(seen[1]["image"], "tree.svg");
+
+    // Running it again changes nothing.
+    let mut count = 0;
+    let again = update(&updated, |_| {
+        count += 1;
+        Ok(format!("new {}\n", count))
+    })
+    .unwrap();
+    assert_eq!(again, updated);
+}
+
+#[test]
+fn bad_markers() {
+    let error = |markdown: &str| regions(markdown).unwrap_err().to_string();
+    assert_eq!(
+        error("a\n<!-- skill-tree:begin -->\n"),
+        "the marker on line 2 has no `<!-- skill-tree:end -->` after it"
+    );
+    assert_eq!(
+        error("<!-- skill-tree:begin tre=x -->\n<!-- skill-tree:end -->"),
+        "unknown option `tre=x` in the marker on line 1, expected `tree=...` or `image=...`"
+    );
+    assert_eq!(
+        error("<!-- skill-tree:begin -->\n<!-- skill-tree:begin -->\n<!-- skill-tree:end -->"),
+        "the marker on line 2 comes before the `<!-- skill-tree:end -->` of the one on line 1"
+    );
+}
+
+#[test]
+fn mermaid_and_table() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let text = section(&tree, None).unwrap();
+    assert!(text.starts_with("```mermaid\nflowchart LR\n"));
+    assert!(text.ends_with(
+        "```\n\n\
+         | Cluster | Groups | Items done |\n\
+         | --- | ---: | ---: |\n\
+         | Front end | 1 | 1/1 (100%) |\n\
+         | Other groups | 1 | 0/3 (0%) |\n\
+         | **Total** | 2 | 1/4 (25%) |\n"
+    ));
+}
+
+#[test]
+fn image_link() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let text = section(&tree, Some("docs/roadmap.svg")).unwrap();
+    assert!(text.starts_with("![Compiler](docs/roadmap.svg)\n\n| Cluster |"));
+}
@@ -0,0 +1,119 @@
+//! `render --emit atom`: an Atom feed of the tree's progress, with an
+//! entry for each change in a group's `status_history`, so people can
+//! follow a roadmap from their feed reader instead of watching the repo.
+
+use crate::sync::percent_encode;
+use skill_tree_core::date::{self, Date};
+use skill_tree_core::render::Renderer;
+use skill_tree_core::{Group, SkillTree, Status};
+use std::io::Write;
+
+pub struct Atom;
+
+/// A status change, as an entry of the feed.
+#[derive(Debug)]
+pub struct Entry<'t> {
+    pub group: &'t Group,
+    pub date: Date,
+    pub status: &'t Status,
+
+    /// The status the group had before, if it had one.
+    pub previous: Option<&'t Status>,
+}
+
+/// Every status change in `tree`, newest first. Changes on the same date
+/// keep the order of the groups.
+pub fn entries(tree: &SkillTree) -> Vec<Entry<'_>> {
+    let mut entries = vec![];
+    for group in tree.groups() {
+        let mut previous = None;
+        for change in group.status_history.iter().flatten() {
+            entries.push(Entry {
+                group,
+                date: change.date,
+                status: &change.status,
+                previous,
+            });
+            previous = Some(&change.status);
+        }
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.date));
+    entries
+}
+
+impl Renderer for Atom {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        let entries = entries(tree);
+        // A tree without any `status_history` yet makes an empty feed.
+        let updated = match entries.first() {
+            Some(entry) => timestamp(entry.date),
+            None => date::now(),
+        };
+        let title = tree
+            .title()
+            .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+        let base_url = tree.doc.as_ref().and_then(|doc| doc.base_url.as_deref());
+        let id = match base_url {
+            Some(base_url) => base_url.to_string(),
+            None => format!("urn:skill-tree:{}", percent_encode(&title)),
+        };
+
+        writeln!(output, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(output, r#"<feed xmlns="http://www.w3.org/2005/Atom">"#)?;
+        writeln!(output, "  <id>{}</id>", escape(&id))?;
+        writeln!(output, "  <title>{}</title>", escape(&title))?;
+        writeln!(output, "  <updated>{}</updated>", updated)?;
+        writeln!(output, "  <author><name>skill-tree</name></author>")?;
+        if let Some(base_url) = base_url {
+            writeln!(output, r#"  <link href="{}"/>"#, escape(base_url))?;
+        }
+        for entry in &entries {
+            let group = entry.group;
+            let label = group.label.as_ref().unwrap_or(&group.name);
+            let status = tree.status_name(entry.status);
+            let summary = match entry.previous {
+                Some(previous) => format!(
+                    "{} went from {} to {}.",
+                    label,
+                    tree.status_name(previous),
+                    status
+                ),
+                None => format!("{} started out as {}.", label, status),
+            };
+            writeln!(output, "  <entry>")?;
+            writeln!(
+                output,
+                "    <id>{}#{}/{}/{}</id>",
+                escape(&id),
+                percent_encode(&group.name),
+                entry.date,
+                percent_encode(entry.status.name())
+            )?;
+            writeln!(
+                output,
+                "    <title>{}</title>",
+                escape(&format!("{}: {}", label, status))
+            )?;
+            writeln!(output, "    <updated>{}</updated>", timestamp(entry.date))?;
+            if let Some(href) = &group.href {
+                writeln!(output, r#"    <link href="{}"/>"#, escape(&tree.link(href)))?;
+            }
+            writeln!(output, "    <summary>{}</summary>", escape(&summary))?;
+            writeln!(output, "  </entry>")?;
+        }
+        writeln!(output, "</feed>")?;
+        Ok(())
+    }
+}
+
+/// Atom wants full timestamps; status changes only have dates.
+fn timestamp(date: Date) -> String {
+    format!("{}T00:00:00Z", date)
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
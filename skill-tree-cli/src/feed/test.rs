@@ -0,0 +1,81 @@
+use super::Atom;
+use skill_tree_core::render::Renderer;
+use skill_tree_core::SkillTree;
+
+fn render(text: &str) -> anyhow::Result<String> {
+    let tree = SkillTree::parse(text).unwrap();
+    let mut output = vec![];
+    Atom.render(&tree, &mut output)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn entry_per_status_change() {
+    let feed = render(
+        r#"
+[doc]
+title = "Compiler & co"
+base_url = "https://example.org/roadmap/"
+
+[[group]]
+name = "lexer"
+label = "Lexer"
+href = "lexer.html"
+status_history = [
+    { status = "Assigned", date = "2024-01-01" },
+    { status = "Complete", date = "2024-03-01" },
+]
+items = []
+
+[[group]]
+name = "type checker"
+status_history = [{ status = "Blocked", date = "2024-02-01" }]
+items = []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        feed,
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>https://example.org/roadmap/</id>
+  <title>Compiler &amp; co</title>
+  <updated>2024-03-01T00:00:00Z</updated>
+  <author><name>skill-tree</name></author>
+  <link href="https://example.org/roadmap/"/>
+  <entry>
+    <id>https://example.org/roadmap/#lexer/2024-03-01/Complete</id>
+    <title>Lexer: Complete</title>
+    <updated>2024-03-01T00:00:00Z</updated>
+    <link href="lexer.html"/>
+    <summary>Lexer went from Assigned to Complete.</summary>
+  </entry>
+  <entry>
+    <id>https://example.org/roadmap/#type%20checker/2024-02-01/Blocked</id>
+    <title>type checker: Blocked</title>
+    <updated>2024-02-01T00:00:00Z</updated>
+    <summary>type checker started out as Blocked.</summary>
+  </entry>
+  <entry>
+    <id>https://example.org/roadmap/#lexer/2024-01-01/Assigned</id>
+    <title>Lexer: Assigned</title>
+    <updated>2024-01-01T00:00:00Z</updated>
+    <link href="lexer.html"/>
+    <summary>Lexer started out as Assigned.</summary>
+  </entry>
+</feed>
+"#
+    );
+}
+
+#[test]
+fn empty_without_a_history() {
+    let feed =
+        render("[doc]\ntitle = \"Compiler\"\n\n[[group]]\nname = \"lexer\"\nitems = []\n").unwrap();
+    let lines: Vec<&str> = feed.lines().collect();
+    assert_eq!(lines[3], "  <title>Compiler</title>");
+    assert!(lines[4].starts_with("  <updated>"), "{}", lines[4]);
+    assert!(lines[4].ends_with("Z</updated>"), "{}", lines[4]);
+    assert!(!feed.contains("<entry>"));
+    assert!(feed.ends_with("</feed>\n"));
+}
@@ -0,0 +1,348 @@
+//! `POST /graphql` on `skill-tree serve`: a GraphQL schema over the
+//! tree, for dashboards that would rather ask for exactly the fields they
+//! show than put together the REST endpoints. The schema is:
+//!
+//! ```graphql
+//! type Query {
+//!   title: String
+//!   groups(filter: String): [Group!]!
+//!   group(name: String!): Group
+//!   clusters: [Cluster!]!
+//!   edges(kind: String): [Edge!]!
+//! }
+//! type Group {
+//!   name: String!  label: String!  cluster: Cluster  status: String
+//!   href: String  description: [String!]!  goal: Boolean!
+//!   items: [Item!]!  progress: Progress!
+//!   requires: [Group!]!  suggests: [Group!]!  requiredBy: [Group!]!
+//! }
+//! type Item { label: String  href: String  status: String  column(name: String!): String! }
+//! type Cluster { name: String!  label: String!  groups(filter: String): [Group!]! }
+//! type Edge { from: Group!  to: Group!  port: String  kind: String! }
+//! type Progress { done: Int!  total: Int! }
+//! ```
+//!
+//! `filter` takes the same expressions as `render --select`. Dependencies
+//! are groups themselves, so `requires { requires { name } }` walks as
+//! deep as a query likes.
+
+use juniper::{graphql_object, EmptyMutation, EmptySubscription, FieldResult, RootNode};
+use skill_tree_core::filter::Filter;
+use skill_tree_core::{EdgeKind, Group, Item, ItemExt, Requirement, SkillTree};
+use std::sync::Arc;
+
+pub struct Context {
+    pub tree: Arc<SkillTree>,
+}
+
+impl juniper::Context for Context {}
+
+pub type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+pub fn schema() -> Schema {
+    Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+/// Runs the GraphQL request in `body` (`{"query", "variables",
+/// "operationName"}`) against `tree`, returning the JSON response and
+/// whether it succeeded.
+pub fn execute(schema: &Schema, tree: Arc<SkillTree>, body: &[u8]) -> (bool, serde_json::Value) {
+    let request: juniper::http::GraphQLRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(error) => {
+            let message = format!("not a GraphQL request: {}", error);
+            return (
+                false,
+                serde_json::json!({ "errors": [{ "message": message }] }),
+            );
+        }
+    };
+    let response = request.execute_sync(schema, &Context { tree });
+    let json = serde_json::to_value(&response).unwrap_or_default();
+    (response.is_ok(), json)
+}
+
+pub struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    /// `doc.title`, if the tree has one.
+    fn title(context: &Context) -> Option<String> {
+        context.tree.title()
+    }
+
+    /// Every group, or those passing `filter`.
+    fn groups(context: &Context, filter: Option<String>) -> FieldResult<Vec<GroupNode>> {
+        groups(&context.tree, None, filter)
+    }
+
+    /// The group called `name`, if there is one.
+    fn group(context: &Context, name: String) -> Option<GroupNode> {
+        context.tree.group_named(&name).map(GroupNode::new)
+    }
+
+    fn clusters(context: &Context) -> Vec<ClusterNode> {
+        context
+            .tree
+            .cluster
+            .iter()
+            .flatten()
+            .map(|cluster| ClusterNode {
+                name: cluster.name.clone(),
+                label: cluster.label.clone(),
+            })
+            .collect()
+    }
+
+    /// Every dependency between groups, or only those of `kind`
+    /// (`requires` or `suggests`).
+    fn edges(context: &Context, kind: Option<String>) -> Vec<EdgeNode> {
+        let tree = &context.tree;
+        let name = |index| tree.group_at(index).unwrap().name.clone();
+        tree.edges()
+            .into_iter()
+            .map(|edge| EdgeNode {
+                from: name(edge.to),
+                to: name(edge.from),
+                port: edge.to_port,
+                kind: kind_name(edge.kind),
+            })
+            .filter(|edge| kind.as_deref().is_none_or(|kind| edge.kind == kind))
+            .collect()
+    }
+}
+
+/// The groups of `tree` passing `filter`, in `cluster` if that is given.
+fn groups(
+    tree: &SkillTree,
+    cluster: Option<&str>,
+    filter: Option<String>,
+) -> FieldResult<Vec<GroupNode>> {
+    let filter = filter.as_deref().map(Filter::parse).transpose()?;
+    Ok(tree
+        .groups()
+        .filter(|group| cluster.is_none_or(|cluster| group.cluster.as_deref() == Some(cluster)))
+        .filter(|group| {
+            filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(tree, group))
+        })
+        .map(GroupNode::new)
+        .collect())
+}
+
+fn kind_name(kind: EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::Hard => "requires",
+        EdgeKind::Soft => "suggests",
+    }
+}
+
+/// A group, looked up by name as its fields are asked for.
+pub struct GroupNode {
+    name: String,
+}
+
+impl GroupNode {
+    fn new(group: &Group) -> GroupNode {
+        GroupNode {
+            name: group.name.clone(),
+        }
+    }
+
+    fn group<'t>(&self, tree: &'t SkillTree) -> &'t Group {
+        // Nodes are only made for groups that exist, in the same tree.
+        tree.group_named(&self.name).unwrap()
+    }
+
+    /// The groups named by `requirements` that exist in `tree`.
+    fn dependencies(tree: &SkillTree, requirements: &Option<Vec<Requirement>>) -> Vec<GroupNode> {
+        requirements
+            .iter()
+            .flatten()
+            .filter(|requirement| requirement.tree().is_none())
+            .filter_map(|requirement| tree.group_named(requirement.group()))
+            .map(GroupNode::new)
+            .collect()
+    }
+}
+
+#[graphql_object(context = Context, name = "Group")]
+impl GroupNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The label, or the name if the group has none.
+    fn label(&self, context: &Context) -> String {
+        let group = self.group(&context.tree);
+        group.label.clone().unwrap_or_else(|| group.name.clone())
+    }
+
+    fn cluster(&self, context: &Context) -> Option<ClusterNode> {
+        let name = self.group(&context.tree).cluster.as_ref()?;
+        let label = match context.tree.cluster_named(name) {
+            Some(cluster) => cluster.label.clone(),
+            None => name.clone(),
+        };
+        Some(ClusterNode {
+            name: name.clone(),
+            label,
+        })
+    }
+
+    fn status(&self, context: &Context) -> Option<String> {
+        let tree = &context.tree;
+        let status = self.group(tree).status.as_ref()?;
+        Some(tree.status_name(status).to_string())
+    }
+
+    fn href(&self, context: &Context) -> Option<String> {
+        self.group(&context.tree).href.clone()
+    }
+
+    fn description(&self, context: &Context) -> Vec<String> {
+        self.group(&context.tree)
+            .description
+            .clone()
+            .unwrap_or_default()
+    }
+
+    fn goal(&self, context: &Context) -> bool {
+        self.group(&context.tree).goal == Some(true)
+    }
+
+    fn items(&self, context: &Context) -> Vec<ItemNode> {
+        self.group(&context.tree)
+            .items()
+            .map(|item| ItemNode {
+                group: self.name.clone(),
+                item: item.clone(),
+            })
+            .collect()
+    }
+
+    /// How many of the group's items are done.
+    fn progress(&self, context: &Context) -> Progress {
+        let (done, total) = self.group(&context.tree).progress(&context.tree);
+        Progress {
+            done: done as i32,
+            total: total as i32,
+        }
+    }
+
+    fn requires(&self, context: &Context) -> Vec<GroupNode> {
+        GroupNode::dependencies(&context.tree, &self.group(&context.tree).requires)
+    }
+
+    fn suggests(&self, context: &Context) -> Vec<GroupNode> {
+        GroupNode::dependencies(&context.tree, &self.group(&context.tree).suggests)
+    }
+
+    /// The groups that require this one.
+    fn required_by(&self, context: &Context) -> Vec<GroupNode> {
+        context
+            .tree
+            .groups()
+            .filter(|group| {
+                group.requires.iter().flatten().any(|requirement| {
+                    requirement.tree().is_none() && requirement.group() == self.name
+                })
+            })
+            .map(GroupNode::new)
+            .collect()
+    }
+}
+
+pub struct ItemNode {
+    /// The name of the group the item is in, for the defaults of its
+    /// columns.
+    group: String,
+    item: Item,
+}
+
+#[graphql_object(context = Context, name = "Item")]
+impl ItemNode {
+    fn label(&self) -> Option<&str> {
+        self.item.label().map(String::as_str)
+    }
+
+    fn href(&self) -> Option<&str> {
+        self.item.href().map(String::as_str)
+    }
+
+    fn status(&self) -> Option<&str> {
+        self.item.get("status").map(String::as_str)
+    }
+
+    /// The item's value for the column `name`, with `doc.defaults`
+    /// applied (`""` if it has none).
+    fn column(&self, context: &Context, name: String) -> String {
+        let tree = &context.tree;
+        match tree.group_named(&self.group) {
+            Some(group) => self.item.column_value(tree, group, &name).to_string(),
+            None => self.item.get(&name).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+pub struct ClusterNode {
+    name: String,
+    label: String,
+}
+
+#[graphql_object(context = Context, name = "Cluster")]
+impl ClusterNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The groups in the cluster, or those passing `filter`.
+    fn groups(&self, context: &Context, filter: Option<String>) -> FieldResult<Vec<GroupNode>> {
+        groups(&context.tree, Some(&self.name), filter)
+    }
+}
+
+pub struct EdgeNode {
+    from: String,
+    to: String,
+    port: Option<String>,
+    kind: &'static str,
+}
+
+#[graphql_object(context = Context, name = "Edge")]
+impl EdgeNode {
+    /// The group depended on.
+    fn from(&self, context: &Context) -> GroupNode {
+        GroupNode::new(context.tree.group_named(&self.from).unwrap())
+    }
+
+    /// The group with the dependency.
+    fn to(&self, context: &Context) -> GroupNode {
+        GroupNode::new(context.tree.group_named(&self.to).unwrap())
+    }
+
+    /// The item of `from` that is depended on, if the dependency names
+    /// one.
+    fn port(&self) -> Option<&str> {
+        self.port.as_deref()
+    }
+
+    /// `requires` or `suggests`.
+    fn kind(&self) -> &str {
+        self.kind
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+pub struct Progress {
+    done: i32,
+    total: i32,
+}
+
+#[cfg(test)]
+mod test;
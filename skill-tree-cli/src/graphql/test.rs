@@ -0,0 +1,116 @@
+use super::{execute, schema};
+use serde_json::{json, Value};
+use skill_tree_core::SkillTree;
+use std::sync::Arc;
+
+const TREE: &str = r#"[[cluster]]
+name = "front"
+label = "Front end"
+
+[[group]]
+name = "lexer"
+label = "Lexer"
+cluster = "front"
+status = "Complete"
+items = [{ label = "Tokens", status = "Complete", port = "tokens" }]
+
+[[group]]
+name = "parser"
+cluster = "front"
+requires = ["lexer:tokens"]
+items = [{ label = "Expressions" }]
+
+[[group]]
+name = "type checker"
+requires = ["parser"]
+suggests = ["lexer"]
+status = "Blocked"
+items = []
+"#;
+
+fn query(query: &str) -> (bool, Value) {
+    let tree = Arc::new(SkillTree::parse(TREE).unwrap());
+    let body = json!({ "query": query }).to_string();
+    execute(&schema(), tree, body.as_bytes())
+}
+
+#[test]
+fn walks_dependencies() {
+    let (ok, response) = query(
+        r#"{ group(name: "type checker") {
+            label
+            requires { name requires { label progress { done total } } }
+            suggests { cluster { label } }
+        } }"#,
+    );
+    assert!(ok, "{}", response);
+    assert_eq!(
+        response["data"]["group"],
+        json!({
+            "label": "type checker",
+            "requires": [{
+                "name": "parser",
+                "requires": [{ "label": "Lexer", "progress": { "done": 1, "total": 1 } }],
+            }],
+            "suggests": [{ "cluster": { "label": "Front end" } }],
+        })
+    );
+
+    let (_, response) = query(r#"{ group(name: "lexer") { requiredBy { name } } }"#);
+    assert_eq!(
+        response["data"]["group"]["requiredBy"],
+        json!([{ "name": "parser" }])
+    );
+}
+
+#[test]
+fn filters_groups() {
+    let (ok, response) = query(
+        r#"{
+            blocked: groups(filter: "status=blocked") { name }
+            clusters { name groups(filter: "not status=complete") { name } }
+        }"#,
+    );
+    assert!(ok, "{}", response);
+    assert_eq!(
+        response["data"]["blocked"],
+        json!([{ "name": "type checker" }])
+    );
+    assert_eq!(
+        response["data"]["clusters"],
+        json!([{ "name": "front", "groups": [{ "name": "parser" }] }])
+    );
+
+    let (ok, response) = query(r#"{ groups(filter: "status=") { name } }"#);
+    assert!(ok, "field errors are part of the response: {}", response);
+    assert_eq!(response["data"], Value::Null);
+    assert_eq!(response["errors"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn lists_edges() {
+    let (ok, response) =
+        query(r#"{ edges(kind: "requires") { from { name } to { name } port kind } }"#);
+    assert!(ok, "{}", response);
+    assert_eq!(
+        response["data"]["edges"],
+        json!([
+            { "from": { "name": "lexer" }, "to": { "name": "parser" }, "port": "tokens", "kind": "requires" },
+            { "from": { "name": "parser" }, "to": { "name": "type checker" }, "port": null, "kind": "requires" },
+        ])
+    );
+}
+
+#[test]
+fn rejects_bad_requests() {
+    let (ok, _) = query("{ groups { nmae } }");
+    assert!(!ok);
+
+    let tree = Arc::new(SkillTree::parse(TREE).unwrap());
+    let (ok, response) = execute(&schema(), tree, b"not json");
+    assert!(!ok);
+    assert!(response["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("not a GraphQL request"));
+}
@@ -0,0 +1,237 @@
+//! Finding and running the graphviz `dot` executable.
+
+use crate::config;
+use anyhow::Context;
+use fehler::throws;
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Environment variable that overrides where we look for `dot`.
+pub const DOT_ENV_VAR: &str = "SKILL_TREE_DOT";
+
+/// The oldest graphviz release we support.
+pub const MIN_VERSION: (u32, u32) = (2, 38);
+
+/// A `dot` executable that we found and were able to run.
+#[derive(Debug)]
+pub struct Graphviz {
+    pub path: PathBuf,
+
+    /// The `major.minor.patch` version reported by `dot -V`, if we could
+    /// make sense of it.
+    pub version: Option<(u32, u32, u32)>,
+}
+
+/// Finds a working `dot` executable. We check `$SKILL_TREE_DOT`, then
+/// `dot` in the user configuration, then `PATH`, then the places the
+/// graphviz installers put it on Windows and macOS (which are often
+/// missing from `PATH`).
+#[throws(anyhow::Error)]
+pub fn detect() -> Graphviz {
+    detect_with(config::get()?.dot.clone())?
+}
+
+/// Like `detect`, but with `config_dot` in place of `dot` in the user
+/// configuration.
+#[throws(anyhow::Error)]
+pub fn detect_with(config_dot: Option<PathBuf>) -> Graphviz {
+    let configured = env::var_os(DOT_ENV_VAR).map(PathBuf::from).or(config_dot);
+    let path = match configured {
+        Some(path) => path,
+        None => match candidates().into_iter().find(|p| p.is_file()) {
+            Some(path) => path,
+            None => anyhow::bail!(
+                "could not find the graphviz `dot` executable; {}",
+                install_hint()
+            ),
+        },
+    };
+
+    let output = Command::new(&path)
+        .arg("-V")
+        .output()
+        .with_context(|| format!("running `{} -V`; {}", path.display(), install_hint()))?;
+
+    // `dot -V` prints something like `dot - graphviz version 2.43.0 (0)`,
+    // on stderr.
+    let banner = String::from_utf8_lossy(&output.stderr);
+    Graphviz {
+        version: parse_version(&banner),
+        path,
+    }
+}
+
+fn candidates() -> Vec<PathBuf> {
+    let exe = if cfg!(windows) { "dot.exe" } else { "dot" };
+    let mut dirs: Vec<PathBuf> = env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect())
+        .unwrap_or_default();
+    if cfg!(windows) {
+        dirs.push(PathBuf::from(r"C:\Program Files\Graphviz\bin"));
+        dirs.push(PathBuf::from(r"C:\Program Files (x86)\Graphviz\bin"));
+    } else {
+        dirs.push(PathBuf::from("/opt/homebrew/bin"));
+        dirs.push(PathBuf::from("/usr/local/bin"));
+        dirs.push(PathBuf::from("/opt/local/bin"));
+    }
+    dirs.into_iter().map(|dir| dir.join(exe)).collect()
+}
+
+/// How to install graphviz on the platform we were built for.
+pub fn install_hint() -> &'static str {
+    if cfg!(windows) {
+        "install graphviz with `winget install graphviz` or from https://graphviz.org/download/, \
+         or set SKILL_TREE_DOT to the path of `dot.exe`"
+    } else if cfg!(target_os = "macos") {
+        "install graphviz with `brew install graphviz`, or set SKILL_TREE_DOT to the path of `dot`"
+    } else {
+        "install graphviz with your package manager (e.g., `apt install graphviz`), \
+         or set SKILL_TREE_DOT to the path of `dot`"
+    }
+}
+
+fn parse_version(banner: &str) -> Option<(u32, u32, u32)> {
+    let version = banner.split("version").nth(1)?.split_whitespace().next()?;
+    let mut parts = version.split('.').map(|p| p.parse::<u32>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+/// Asks fontconfig whether some installed font has emoji glyphs (we use
+/// U+1F64B, the raised-hand emoji). Returns `None` if we can't tell, e.g.
+/// because `fc-list` isn't installed.
+pub fn has_emoji_font() -> Option<bool> {
+    let output = Command::new("fc-list")
+        .args([":charset=1f64b", "family"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(!output.stdout.iter().all(u8::is_ascii_whitespace))
+}
+
+/// Builds an HTML snippet showing the PNG at `src` with the clickable
+/// regions from `cmapx`, the output of `dot -Tcmapx`. `alt` defaults to
+/// "skill tree".
+pub fn image_map_snippet(src: &str, alt: Option<&str>, cmapx: &str) -> String {
+    // graphviz names the map after the graph, which we always call `g`.
+    format!(
+        "<img src=\"{}\" usemap=\"#g\" alt=\"{}\">\n{}",
+        htmlescape::encode_minimal(src),
+        htmlescape::encode_minimal(alt.unwrap_or("skill tree")),
+        cmapx.trim_end(),
+    ) + "\n"
+}
+
+/// Replaces the `<title>` graphviz gives the graph in `svg` (the graph's
+/// name, `g`) with `title`.
+pub fn svg_with_title(svg: &str, title: &str) -> String {
+    svg.replacen(
+        "<title>g</title>",
+        &format!("<title>{}</title>", htmlescape::encode_minimal(title)),
+        1,
+    )
+}
+
+/// Gives every link in `svg` both an `href` and an `xlink:href`: graphviz
+/// writes only the latter, which SVG 2 deprecates and some viewers and
+/// sanitizers drop, while older viewers only know it.
+pub fn svg_with_links(svg: &str) -> String {
+    let mut output = String::with_capacity(svg.len());
+    let mut rest = svg;
+    while let Some(start) = rest.find("<a ") {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        output.push_str(&rest[..start + "<a".len()]);
+        let attributes = &rest[start + "<a".len()..end];
+        let value = |name: &str| {
+            let needle = format!(" {}=\"", name);
+            let from = attributes.find(&needle)? + needle.len();
+            let to = from + attributes[from..].find('"')?;
+            Some(&attributes[from..to])
+        };
+        match (value("href"), value("xlink:href")) {
+            (None, Some(href)) => output.push_str(&format!(" href=\"{}\"", href)),
+            (Some(href), None) => output.push_str(&format!(" xlink:href=\"{}\"", href)),
+            _ => {}
+        }
+        output.push_str(attributes);
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+
+    // The `xlink` prefix needs declaring; graphviz does, but be sure.
+    match output.find("<svg") {
+        Some(start) if output.contains("xlink:href") => {
+            let end = start + output[start..].find('>').unwrap_or(0);
+            if !output[start..end].contains("xmlns:xlink") {
+                output.insert_str(
+                    start + "<svg".len(),
+                    " xmlns:xlink=\"http://www.w3.org/1999/xlink\"",
+                );
+            }
+            output
+        }
+        _ => output,
+    }
+}
+
+/// Renders `dot_text` to SVG, with `title` (normally `doc.title`) as its
+/// `<title>`, and links that work in any viewer (see `svg_with_links`).
+#[throws(anyhow::Error)]
+pub fn render_svg(dot_text: &str, title: Option<&str>) -> String {
+    let svg = svg_with_links(&String::from_utf8(run_dot(dot_text, "svg")?)?);
+    match title {
+        Some(title) => svg_with_title(&svg, title),
+        None => svg,
+    }
+}
+
+/// Finds `dot`, then runs `dot -T<format>` over `dot_text` and returns
+/// what it printed.
+#[throws(anyhow::Error)]
+pub fn run_dot(dot_text: &str, format: &str) -> Vec<u8> {
+    let graphviz = detect()?;
+    run(&graphviz.path, dot_text, format)?
+}
+
+/// Runs the `dot` at `dot` with `-T<format>` over `dot_text`.
+#[throws(anyhow::Error)]
+pub fn run(dot: &Path, dot_text: &str, format: &str) -> Vec<u8> {
+    let mut child = Command::new(dot)
+        .arg(format!("-T{}", format))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("running `{}`", dot.display()))?;
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(dot_text.as_bytes())
+        .context("writing to `dot`")?;
+
+    let output = child.wait_with_output().context("waiting for `dot`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`dot -T{}` failed ({}): {}",
+            format,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim(),
+        );
+    }
+    output.stdout
+}
+
+#[cfg(test)]
+mod test;
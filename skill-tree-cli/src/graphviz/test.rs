@@ -0,0 +1,66 @@
+use super::{image_map_snippet, parse_version, svg_with_links, svg_with_title};
+
+#[test]
+fn parses_dot_version_banner() {
+    assert_eq!(
+        parse_version("dot - graphviz version 2.43.0 (0)\n"),
+        Some((2, 43, 0))
+    );
+    assert_eq!(
+        parse_version("dot - graphviz version 9.0.0 (20230911.1827)\n"),
+        Some((9, 0, 0))
+    );
+    assert_eq!(
+        parse_version("dot - graphviz version 2.38"),
+        Some((2, 38, 0))
+    );
+    assert_eq!(parse_version("something else"), None);
+}
+
+#[test]
+fn builds_image_map_snippet() {
+    let cmapx = "<map id=\"g\" name=\"g\">\n<area shape=\"rect\" href=\"http://example.org\" coords=\"1,2,3,4\"/>\n</map>\n";
+    assert_eq!(
+        image_map_snippet("roadmap.png", None, cmapx),
+        "<img src=\"roadmap.png\" usemap=\"#g\" alt=\"skill tree\">\n\
+         <map id=\"g\" name=\"g\">\n\
+         <area shape=\"rect\" href=\"http://example.org\" coords=\"1,2,3,4\"/>\n\
+         </map>\n"
+    );
+}
+
+#[test]
+fn image_map_snippet_uses_title() {
+    let snippet = image_map_snippet("roadmap.png", Some("Q3 <plan>"), "<map/>\n");
+    assert_eq!(
+        snippet,
+        "<img src=\"roadmap.png\" usemap=\"#g\" alt=\"Q3 &lt;plan&gt;\">\n<map/>\n"
+    );
+}
+
+#[test]
+fn replaces_svg_title() {
+    let svg = "<svg>\n<g id=\"graph0\">\n<title>g</title>\n<g id=\"node1\"><title>g</title></g>\n</g>\n</svg>";
+    assert_eq!(
+        svg_with_title(svg, "Roadmap & plans"),
+        "<svg>\n<g id=\"graph0\">\n<title>Roadmap &amp; plans</title>\n<g id=\"node1\"><title>g</title></g>\n</g>\n</svg>"
+    );
+}
+
+#[test]
+fn links_get_href_and_xlink_href() {
+    let svg = "<svg width=\"8pt\">\n<g><a xlink:href=\"https://example.org/a\" xlink:title=\"A\">\n<text>A</text></a></g>\n</svg>";
+    assert_eq!(
+        svg_with_links(svg),
+        "<svg xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"8pt\">\n<g><a href=\"https://example.org/a\" xlink:href=\"https://example.org/a\" xlink:title=\"A\">\n<text>A</text></a></g>\n</svg>"
+    );
+
+    let svg = "<svg><a href=\"b.html\">B</a></svg>";
+    assert_eq!(
+        svg_with_links(svg),
+        "<svg xmlns:xlink=\"http://www.w3.org/1999/xlink\"><a xlink:href=\"b.html\" href=\"b.html\">B</a></svg>"
+    );
+
+    let svg = "<svg><text>no links</text></svg>";
+    assert_eq!(svg_with_links(svg), svg);
+}
@@ -0,0 +1,219 @@
+//! `render --emit html`: a standalone page showing the rendered SVG, with
+//! pan and zoom, a search box that highlights the groups and items
+//! matching a label or column value, and a sidebar listing the clusters and
+//! their groups, each linking to its node. Everything is inline, so the page
+//! works offline. `doc.css` and `doc.style` are added after our own styles,
+//! so they can restyle the page.
+
+use fehler::throws;
+use serde_json::{json, Map, Value};
+use skill_tree_core::anchor::Anchors;
+use skill_tree_core::color::Rgb;
+use skill_tree_core::{Group, ItemExt, SkillTree};
+
+const SEARCH_JS: &str = include_str!("html/search.js");
+
+const PAN_ZOOM_JS: &str = include_str!("html/panzoom.js");
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 0; }
+header { position: sticky; top: 0; background: white; padding: 0.5em 1em; border-bottom: 1px solid #ccc; }
+#skill-tree-search { width: 20em; }
+#skill-tree-search-status { margin-left: 1em; color: #666; }
+#skill-tree-page { display: flex; }
+#skill-tree-contents { flex: 0 0 16em; height: calc(100vh - 3em); overflow-y: auto; border-right: 1px solid #ccc; font-size: 0.9em; }
+#skill-tree-contents ul { list-style: none; margin: 0; padding-left: 1em; }
+#skill-tree-contents li { margin: 0.25em 0; }
+#skill-tree-contents a { color: inherit; text-decoration: none; }
+#skill-tree-contents a:hover { text-decoration: underline; }
+.skill-tree-dot { display: inline-block; width: 0.7em; height: 0.7em; margin-right: 0.4em; border-radius: 50%; border: 1px solid #666; }
+main { flex: 1; min-width: 0; }
+main svg { display: block; width: 100%; height: calc(100vh - 3em); cursor: grab; }
+.skill-tree-dim { opacity: 0.25; }
+.skill-tree-match > polygon, .skill-tree-match > path { stroke: #d33; stroke-width: 3; }
+.skill-tree-hit { fill: #d33; font-weight: bold; }
+";
+
+/// Builds the page around `svg`, the output of `dot -Tsvg` for `tree`.
+#[throws(anyhow::Error)]
+pub fn page(tree: &SkillTree, svg: &str) -> String {
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+
+    // Drop the XML prolog and doctype, which don't belong inside HTML.
+    let svg = match svg.find("<svg") {
+        Some(start) => &svg[start..],
+        None => svg,
+    };
+
+    // `</` would end the <style> early; `<\/` means the same in CSS.
+    let custom_style = match tree.custom_css()? {
+        Some(css) => format!(
+            "<style>\n{}\n</style>\n",
+            css.trim_end().replace("</", "<\\/")
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n\
+         <style>\n{style}</style>\n\
+         {custom_style}\
+         </head>\n\
+         <body>\n\
+         <header>\n\
+         <input id=\"skill-tree-search\" type=\"search\" placeholder=\"{placeholder}\">\
+         <span id=\"skill-tree-search-status\"></span>\n\
+         </header>\n\
+         <div id=\"skill-tree-page\">\n\
+         {contents}\
+         <main>\n{svg}\n</main>\n\
+         </div>\n\
+         <script>\n{pan_zoom}</script>\n\
+         <script>\nvar SKILL_TREE_INDEX = {index};\nvar SKILL_TREE_STRINGS = {strings};\n{script}</script>\n\
+         </body>\n\
+         </html>\n",
+        title = htmlescape::encode_minimal(&title),
+        style = STYLE,
+        custom_style = custom_style,
+        contents = contents(tree),
+        svg = svg.trim_end(),
+        // `</` can't appear inside a <script>, so escape the slash.
+        index = search_index(tree).to_string().replace("</", "<\\/"),
+        script = SEARCH_JS,
+        placeholder = htmlescape::encode_minimal(tree.translate("Search groups and items")),
+        strings = json!({
+            "match": tree.translate("{count} match"),
+            "matches": tree.translate("{count} matches"),
+        })
+        .to_string()
+        .replace("</", "<\\/"),
+        pan_zoom = PAN_ZOOM_JS,
+    )
+}
+
+/// The sidebar: each cluster's groups under its label, then the groups
+/// outside any cluster, each with a dot in its header color and linking
+/// to its node.
+pub fn contents(tree: &SkillTree) -> String {
+    let anchors = tree.anchors();
+    let contents = tree.translate("Contents");
+    let mut html = format!(
+        "<nav id=\"skill-tree-contents\" aria-label=\"{}\">\n<ul>\n",
+        escape(contents)
+    );
+    let clusters = tree.cluster.iter().flatten();
+    let mut sections: Vec<(&str, Vec<&Group>)> = clusters
+        .map(|cluster| {
+            let groups = tree.groups_in(Some(&cluster.name)).collect();
+            (&cluster.label[..], groups)
+        })
+        .collect();
+    let others: Vec<&Group> = tree
+        .groups()
+        .filter(|group| {
+            group
+                .cluster
+                .as_deref()
+                .is_none_or(|name| tree.cluster_named(name).is_none())
+        })
+        .collect();
+    if sections.is_empty() {
+        for group in others {
+            html.push_str(&contents_entry(tree, &anchors, group));
+        }
+    } else {
+        sections.push((tree.translate("Other groups"), others));
+        for (label, groups) in sections {
+            if groups.is_empty() {
+                continue;
+            }
+            html.push_str(&format!("<li>{}\n<ul>\n", escape(label)));
+            for group in groups {
+                html.push_str(&contents_entry(tree, &anchors, group));
+            }
+            html.push_str("</ul>\n</li>\n");
+        }
+    }
+    html.push_str("</ul>\n</nav>\n");
+    html
+}
+
+fn contents_entry(tree: &SkillTree, anchors: &Anchors<'_>, group: &Group) -> String {
+    let label = group.label.as_deref().unwrap_or(&group.name);
+    let status = match &group.status {
+        Some(status) => tree.status_name(status),
+        None => tree.translate("No status"),
+    };
+    format!(
+        "<li><a href=\"#{}\"><span class=\"skill-tree-dot\" style=\"background: {}\" title=\"{}\"></span>{}</a></li>\n",
+        escape(&anchors.group_id(group)),
+        escape(&dot_color(tree, group)),
+        escape(status),
+        escape(label),
+    )
+}
+
+/// The color of `group`'s dot in the sidebar: the color of its header in
+/// the graph (which shows its status's `color`, if it has one), as
+/// `#rrggbb`, since CSS doesn't know graphviz's color names. Colors that
+/// can't be read that way, such as ones from other color schemes, are
+/// shown as the default header color.
+fn dot_color(tree: &SkillTree, group: &Group) -> String {
+    Rgb::parse(tree.header_color(group))
+        .or_else(|| Rgb::parse("darkgoldenrod"))
+        .map_or_else(String::new, Rgb::hex)
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+/// Embeds the pan and zoom script in `svg`, for viewing it directly in a
+/// browser (scripts don't run when an SVG is shown through `<img>`).
+pub fn pan_zoom_svg(svg: &str) -> String {
+    match svg.rfind("</svg>") {
+        Some(end) => format!(
+            "{}<script><![CDATA[\n{}]]></script>\n{}",
+            &svg[..end],
+            PAN_ZOOM_JS,
+            &svg[end..]
+        ),
+        None => svg.to_string(),
+    }
+}
+
+/// Maps each group's name to the lowercased text the search box matches
+/// against: its name, label, description, item labels and column values
+/// (both as written and as shown through the emoji tables).
+pub fn search_index(tree: &SkillTree) -> Value {
+    let lookup = tree.cell_lookup();
+    let mut index = Map::new();
+    for group in tree.groups() {
+        let mut words: Vec<&str> = vec![&group.name];
+        words.extend(group.label.as_deref());
+        words.extend(group.description.iter().flatten().map(String::as_str));
+        for item in group.items() {
+            words.extend(item.label().map(String::as_str));
+            for column in tree.columns() {
+                let value = lookup.value(group, item, column.name());
+                words.push(value);
+                words.push(lookup.emoji(group, column.name(), value));
+            }
+        }
+        words.retain(|word| !word.is_empty());
+        index.insert(
+            group.name.clone(),
+            Value::String(words.join(" ").to_lowercase()),
+        );
+    }
+    Value::Object(index)
+}
+
+#[cfg(test)]
+mod test;
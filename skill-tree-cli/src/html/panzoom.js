@@ -0,0 +1,114 @@
+// Pan and zoom for skill-tree SVGs, by rewriting the viewBox: drag to pan,
+// use the wheel (or `+`/`-`) to zoom, double-click (or `0`) to reset. Used
+// by `--emit html` pages and embedded in SVGs rendered with `--pan-zoom`.
+// `skillTreeFocus(element)` zooms to an element, e.g. a search match.
+(function () {
+  var svg = document.querySelector("svg");
+  if (!svg || !svg.viewBox.baseVal) {
+    return;
+  }
+  if (svg === document.documentElement) {
+    // Opened directly: fill the window, and let the viewBox do the rest.
+    svg.setAttribute("width", "100%");
+    svg.setAttribute("height", "100%");
+  }
+
+  var initial = svg.viewBox.baseVal;
+  var home = { x: initial.x, y: initial.y, width: initial.width, height: initial.height };
+  var box = { x: home.x, y: home.y, width: home.width, height: home.height };
+
+  function apply() {
+    svg.setAttribute("viewBox", [box.x, box.y, box.width, box.height].join(" "));
+  }
+
+  // Converts a position on screen into viewBox coordinates.
+  function toViewBox(clientX, clientY) {
+    var p = svg.createSVGPoint();
+    p.x = clientX;
+    p.y = clientY;
+    return p.matrixTransform(svg.getScreenCTM().inverse());
+  }
+
+  function zoom(factor, center) {
+    box.x = center.x - (center.x - box.x) * factor;
+    box.y = center.y - (center.y - box.y) * factor;
+    box.width *= factor;
+    box.height *= factor;
+    apply();
+  }
+
+  function zoomAtCenter(factor) {
+    zoom(factor, { x: box.x + box.width / 2, y: box.y + box.height / 2 });
+  }
+
+  function reset() {
+    box = { x: home.x, y: home.y, width: home.width, height: home.height };
+    apply();
+  }
+
+  svg.addEventListener("wheel", function (event) {
+    event.preventDefault();
+    zoom(event.deltaY > 0 ? 1.2 : 1 / 1.2, toViewBox(event.clientX, event.clientY));
+  }, { passive: false });
+
+  var dragStart = null;
+  var dragged = false;
+  svg.addEventListener("mousedown", function (event) {
+    if (event.button === 0) {
+      dragStart = toViewBox(event.clientX, event.clientY);
+      dragged = false;
+    }
+  });
+  window.addEventListener("mousemove", function (event) {
+    if (!dragStart) {
+      return;
+    }
+    var p = toViewBox(event.clientX, event.clientY);
+    if (p.x !== dragStart.x || p.y !== dragStart.y) {
+      dragged = true;
+    }
+    box.x -= p.x - dragStart.x;
+    box.y -= p.y - dragStart.y;
+    apply();
+  });
+  window.addEventListener("mouseup", function () {
+    dragStart = null;
+  });
+  // Don't follow a link at the end of a drag.
+  svg.addEventListener("click", function (event) {
+    if (dragged) {
+      event.preventDefault();
+      dragged = false;
+    }
+  }, true);
+  svg.addEventListener("dblclick", reset);
+
+  window.addEventListener("keydown", function (event) {
+    if (event.target.tagName === "INPUT") {
+      return;
+    }
+    if (event.key === "+" || event.key === "=") {
+      zoomAtCenter(1 / 1.2);
+    } else if (event.key === "-") {
+      zoomAtCenter(1.2);
+    } else if (event.key === "0") {
+      reset();
+    }
+  });
+
+  window.skillTreeFocus = function (element) {
+    var rect = element.getBoundingClientRect();
+    var topLeft = toViewBox(rect.left, rect.top);
+    var bottomRight = toViewBox(rect.right, rect.bottom);
+    // Leave room around the element to see its neighbours.
+    var width = (bottomRight.x - topLeft.x) * 3;
+    var height = (bottomRight.y - topLeft.y) * 3;
+    box = {
+      x: (topLeft.x + bottomRight.x - width) / 2,
+      y: (topLeft.y + bottomRight.y - height) / 2,
+      width: width,
+      height: height,
+    };
+    apply();
+  };
+})();
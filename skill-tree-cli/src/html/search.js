@@ -0,0 +1,94 @@
+// Search box for `skill-tree render --emit html`. `SKILL_TREE_INDEX` maps
+// each group name (the `data-group` of its node in the SVG) to the lowercased
+// text of its label, description, items and column values.
+// `SKILL_TREE_STRINGS` holds the translated status messages.
+// A `#group-...` or `#item-...` fragment in the URL (the ids graphviz gives
+// nodes and item cells) marks that node and zooms to it, since scrolling
+// can't reach it once the SVG pans and zooms. The sidebar's links are such
+// fragments; following one again zooms back to its node.
+(function () {
+  var input = document.getElementById("skill-tree-search");
+  var status = document.getElementById("skill-tree-search-status");
+  var nodes = Array.prototype.slice.call(document.querySelectorAll("svg g.node"));
+
+  function name(node) {
+    return node.getAttribute("data-group") || "";
+  }
+
+  function clear() {
+    nodes.forEach(function (node) {
+      node.classList.remove("skill-tree-match", "skill-tree-dim");
+      node.querySelectorAll("text").forEach(function (text) {
+        text.classList.remove("skill-tree-hit");
+      });
+    });
+  }
+
+  function search() {
+    clear();
+    var query = input.value.trim().toLowerCase();
+    if (!query) {
+      status.textContent = "";
+      return;
+    }
+
+    var matches = nodes.filter(function (node) {
+      var text = SKILL_TREE_INDEX[name(node)];
+      return text !== undefined && text.indexOf(query) !== -1;
+    });
+    nodes.forEach(function (node) {
+      node.classList.add(matches.indexOf(node) === -1 ? "skill-tree-dim" : "skill-tree-match");
+    });
+    matches.forEach(function (node) {
+      node.querySelectorAll("text").forEach(function (text) {
+        if (text.textContent.toLowerCase().indexOf(query) !== -1) {
+          text.classList.add("skill-tree-hit");
+        }
+      });
+    });
+
+    var message = matches.length === 1 ? SKILL_TREE_STRINGS.match : SKILL_TREE_STRINGS.matches;
+    status.textContent = message.replace("{count}", matches.length);
+    if (matches.length > 0 && window.skillTreeFocus) {
+      window.skillTreeFocus(matches[0]);
+    } else if (matches.length > 0) {
+      matches[0].scrollIntoView({ block: "center", inline: "center", behavior: "smooth" });
+    }
+  }
+
+  function showTarget() {
+    var id = decodeURIComponent(window.location.hash.slice(1));
+    var target = id && document.getElementById(id);
+    if (!target || !target.closest("svg")) {
+      return;
+    }
+    var node = target.closest("g.node") || target;
+    node.classList.add("skill-tree-match");
+    if (window.skillTreeFocus) {
+      window.skillTreeFocus(node);
+    } else {
+      node.scrollIntoView({ block: "center", inline: "center" });
+    }
+  }
+
+  input.addEventListener("input", search);
+  document.querySelectorAll("#skill-tree-contents a").forEach(function (link) {
+    link.addEventListener("click", function () {
+      if (link.hash === window.location.hash) {
+        clear();
+        showTarget();
+      }
+    });
+  });
+  window.addEventListener("hashchange", function () {
+    clear();
+    showTarget();
+  });
+  showTarget();
+  input.addEventListener("keydown", function (event) {
+    if (event.key === "Escape") {
+      input.value = "";
+      search();
+    }
+  });
+})();
@@ -0,0 +1,116 @@
+use super::{contents, page, pan_zoom_svg, search_index};
+use serde_json::json;
+use skill_tree_core::SkillTree;
+
+const TREE: &str = r#"
+[doc]
+title = "Parser </script> plans"
+columns = ["team"]
+
+[doc.emoji.team]
+"lang" = "T-lang"
+
+[[group]]
+name = "parser"
+label = "Parser"
+description = ["Turns tokens into trees"]
+items = [
+    { label = "Expressions", team = "lang" },
+    { label = "Items" },
+]
+"#;
+
+#[test]
+fn indexes_labels_and_columns() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert_eq!(
+        search_index(&tree),
+        json!({
+            "parser": "parser parser turns tokens into trees expressions lang t-lang items",
+        })
+    );
+}
+
+#[test]
+fn embeds_svg_and_index() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let svg = "<?xml version=\"1.0\"?>\n<!DOCTYPE svg>\n<svg><g class=\"node\"><title>parser</title></g></svg>\n";
+    let html = page(&tree, svg).unwrap();
+    assert!(html.contains("<title>Parser &lt;/script&gt; plans</title>"));
+    assert!(
+        html.contains("<main>\n<svg><g class=\"node\"><title>parser</title></g></svg>\n</main>")
+    );
+    assert!(!html.contains("<?xml"));
+    assert!(html.contains("var SKILL_TREE_INDEX = {\"parser\":"));
+    assert_eq!(html.matches("</script>").count(), 2);
+    assert!(html.contains("window.skillTreeFocus = function"));
+}
+
+#[test]
+fn embeds_pan_zoom_in_svg() {
+    let svg = pan_zoom_svg("<svg viewBox=\"0 0 10 10\">\n<g/>\n</svg>\n");
+    assert!(
+        svg.starts_with("<svg viewBox=\"0 0 10 10\">\n<g/>\n<script><![CDATA[\n// Pan and zoom")
+    );
+    assert!(svg.ends_with("]]></script>\n</svg>\n"));
+}
+
+#[test]
+fn adds_inline_style_after_ours() {
+    let tree = SkillTree::parse("[doc]\nstyle = \"body { font-family: serif; } /* </style> */\"\n")
+        .unwrap();
+    let html = page(&tree, "<svg></svg>").unwrap();
+    let ours = html.find("#skill-tree-search {").unwrap();
+    let custom = html.find("body { font-family: serif; }").unwrap();
+    assert!(ours < custom);
+    assert!(html.contains("/* <\\/style> */"));
+    assert_eq!(html.matches("</style>").count(), 2);
+}
+
+#[test]
+fn lists_groups_by_cluster() {
+    let tree = SkillTree::parse(
+        r#"
+[doc.statuses]
+Review = { color = "lightblue" }
+
+[[cluster]]
+name = "front"
+label = "Front <end>"
+
+[[cluster]]
+name = "empty"
+label = "Empty"
+
+[[group]]
+name = "type checker"
+header_color = "/accent3/1"
+items = []
+
+[[group]]
+name = "lexer"
+label = "Lexer"
+cluster = "front"
+status = "Review"
+items = []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        contents(&tree),
+        "<nav id=\"skill-tree-contents\" aria-label=\"Contents\">\n<ul>\n\
+         <li>Front &lt;end&gt;\n<ul>\n\
+         <li><a href=\"#group-lexer\"><span class=\"skill-tree-dot\" style=\"background: #add8e6\" title=\"Review\"></span>Lexer</a></li>\n\
+         </ul>\n</li>\n\
+         <li>Other groups\n<ul>\n\
+         <li><a href=\"#group-type-checker\"><span class=\"skill-tree-dot\" style=\"background: #b8860b\" title=\"No status\"></span>type checker</a></li>\n\
+         </ul>\n</li>\n\
+         </ul>\n</nav>\n"
+    );
+
+    let html = page(&SkillTree::parse(TREE).unwrap(), "<svg></svg>").unwrap();
+    assert!(html.contains(
+        "<div id=\"skill-tree-page\">\n<nav id=\"skill-tree-contents\" aria-label=\"Contents\">\n<ul>\n\
+         <li><a href=\"#group-parser\">"
+    ));
+}
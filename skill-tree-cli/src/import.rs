@@ -0,0 +1,70 @@
+//! Importers that turn planning data from elsewhere into a starting
+//! skill tree.
+//!
+//! Each importer produces a list of `ImportedGroup`s, which `to_toml`
+//! writes out as a skill tree file for people to edit from there.
+
+pub use skill_tree_core::anchor::slug;
+use skill_tree_core::Status;
+
+pub mod cargo;
+pub mod github_project;
+pub mod markdown;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportedGroup {
+    pub name: String,
+    pub label: String,
+    pub href: Option<String>,
+    pub status: Option<Status>,
+    pub requires: Vec<String>,
+    pub items: Vec<ImportedItem>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportedItem {
+    pub label: String,
+    pub href: Option<String>,
+    pub status: Option<Status>,
+}
+
+/// Writes `groups` as the text of a skill tree file.
+pub fn to_toml(groups: &[ImportedGroup]) -> String {
+    let mut out = String::new();
+    for group in groups {
+        out.push_str("[[group]]\n");
+        out.push_str(&format!("name = {}\n", quote(&group.name)));
+        out.push_str(&format!("label = {}\n", quote(&group.label)));
+        if let Some(href) = &group.href {
+            out.push_str(&format!("href = {}\n", quote(href)));
+        }
+        if let Some(status) = &group.status {
+            out.push_str(&format!("status = {}\n", quote(status.name())));
+        }
+        if !group.requires.is_empty() {
+            let requires: Vec<String> = group.requires.iter().map(|r| quote(r)).collect();
+            out.push_str(&format!("requires = [{}]\n", requires.join(", ")));
+        }
+        out.push_str("items = [\n");
+        for item in &group.items {
+            let mut fields = vec![format!("label = {}", quote(&item.label))];
+            if let Some(href) = &item.href {
+                fields.push(format!("href = {}", quote(href)));
+            }
+            if let Some(status) = &item.status {
+                fields.push(format!("status = {}", quote(status.name())));
+            }
+            out.push_str(&format!("    {{ {} }},\n", fields.join(", ")));
+        }
+        out.push_str("]\n\n");
+    }
+    out
+}
+
+/// Quotes and escapes `s` as a TOML string.
+fn quote(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod test;
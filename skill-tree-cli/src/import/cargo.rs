@@ -0,0 +1,95 @@
+//! `skill-tree import cargo`: bootstraps a tree from a cargo workspace,
+//! as a map of its architecture to annotate.
+//!
+//! Each member crate becomes a group, which requires the other members it
+//! depends on (dev-dependencies aside, as they often go both ways). Each
+//! feature of the crate becomes an item, except `default` and the ones
+//! cargo makes for optional dependencies.
+
+use super::{ImportedGroup, ImportedItem};
+use anyhow::Context;
+use fehler::throws;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Asks cargo about the workspace of `manifest_path` (or of the current
+/// directory) and converts it into groups.
+#[throws(anyhow::Error)]
+pub fn import(manifest_path: Option<&Path>) -> Vec<ImportedGroup> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.args(["metadata", "--format-version", "1", "--no-deps"]);
+    if let Some(path) = manifest_path {
+        command.arg("--manifest-path").arg(path);
+    }
+    let output = command.output().context("running `cargo metadata`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`cargo metadata` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let metadata: Value =
+        serde_json::from_slice(&output.stdout).context("reading `cargo metadata`'s output")?;
+    convert(&metadata)
+}
+
+/// Converts the output of `cargo metadata --no-deps`, whose packages are
+/// the workspace's members.
+pub fn convert(metadata: &Value) -> Vec<ImportedGroup> {
+    let packages = metadata["packages"].as_array().map_or(&[][..], |p| &p[..]);
+    let members: HashSet<&str> = packages
+        .iter()
+        .filter_map(|package| package["name"].as_str())
+        .collect();
+
+    packages
+        .iter()
+        .filter_map(|package| {
+            let name = package["name"].as_str()?;
+            let mut requires: Vec<String> = vec![];
+            for dependency in package["dependencies"].as_array().into_iter().flatten() {
+                let dependency_name = dependency["name"].as_str().unwrap_or_default();
+                if dependency["kind"] != "dev"
+                    && members.contains(dependency_name)
+                    && dependency_name != name
+                    && !requires.iter().any(|r| r == dependency_name)
+                {
+                    requires.push(dependency_name.to_string());
+                }
+            }
+            let items = package["features"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(feature, enables)| {
+                    let implicit = format!("dep:{}", feature);
+                    *feature != "default"
+                        && enables.as_array().map(|e| &e[..]) != Some(&[Value::from(implicit)])
+                })
+                .map(|(feature, _)| ImportedItem {
+                    label: feature.clone(),
+                    ..ImportedItem::default()
+                })
+                .collect();
+            let href = ["documentation", "homepage", "repository"]
+                .iter()
+                .find_map(|key| package[*key].as_str())
+                .map(str::to_string);
+            Some(ImportedGroup {
+                name: name.to_string(),
+                label: name.to_string(),
+                href,
+                requires,
+                items,
+                ..ImportedGroup::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,84 @@
+use super::convert;
+use crate::import::to_toml;
+use serde_json::json;
+use skill_tree_core::SkillTree;
+
+#[test]
+fn converts_workspace() {
+    let metadata = json!({
+        "packages": [
+            {
+                "name": "demo-core",
+                "documentation": "https://docs.rs/demo-core",
+                "repository": "https://github.com/demo/demo",
+                "dependencies": [
+                    { "name": "serde", "kind": null },
+                    { "name": "demo-test-utils", "kind": "dev" },
+                ],
+                "features": {
+                    "default": ["std"],
+                    "std": [],
+                    "serde": ["dep:serde"],
+                    "unstable-parser": ["std"],
+                },
+            },
+            {
+                "name": "demo-cli",
+                "repository": "https://github.com/demo/demo",
+                "dependencies": [
+                    { "name": "demo-core", "kind": null },
+                    { "name": "demo-build", "kind": "build" },
+                    { "name": "demo-core", "kind": null, "target": "cfg(unix)" },
+                ],
+                "features": {},
+            },
+            {
+                "name": "demo-build",
+                "dependencies": [],
+                "features": {},
+            },
+            {
+                "name": "demo-test-utils",
+                "dependencies": [{ "name": "demo-core", "kind": null }],
+                "features": {},
+            },
+        ],
+    });
+
+    let text = to_toml(&convert(&metadata));
+    assert_eq!(
+        text,
+        r#"[[group]]
+name = "demo-core"
+label = "demo-core"
+href = "https://docs.rs/demo-core"
+items = [
+    { label = "std" },
+    { label = "unstable-parser" },
+]
+
+[[group]]
+name = "demo-cli"
+label = "demo-cli"
+href = "https://github.com/demo/demo"
+requires = ["demo-core", "demo-build"]
+items = [
+]
+
+[[group]]
+name = "demo-build"
+label = "demo-build"
+items = [
+]
+
+[[group]]
+name = "demo-test-utils"
+label = "demo-test-utils"
+requires = ["demo-core"]
+items = [
+]
+
+"#
+    );
+    SkillTree::parse(&text).unwrap().validate().unwrap();
+}
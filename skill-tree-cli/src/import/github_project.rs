@@ -0,0 +1,243 @@
+//! `skill-tree import github-project <url>`: bootstraps a tree from a
+//! GitHub Projects board.
+//!
+//! Every card on the board becomes a group, whose status comes from the
+//! card's `Status` column. If the card's issue tracks other issues (a
+//! tracking issue), those become the group's items, and any of them that
+//! are also cards on the board become `requires` edges. Otherwise the card
+//! itself is the only item.
+
+use super::{slug, ImportedGroup, ImportedItem};
+use anyhow::Context;
+use fehler::throws;
+use serde_json::{json, Value};
+use skill_tree_core::Status;
+use std::collections::HashMap;
+
+const GRAPHQL_URL: &str = "https://api.github.com/graphql";
+
+const QUERY: &str = r#"
+query($owner: String!, $number: Int!, $after: String) {
+  OWNER(login: $owner) {
+    projectV2(number: $number) {
+      items(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          fieldValueByName(name: "Status") {
+            ... on ProjectV2ItemFieldSingleSelectValue { name }
+          }
+          content {
+            ... on DraftIssue { title }
+            ... on PullRequest { title url }
+            ... on Issue {
+              title
+              url
+              trackedIssues(first: 100) { nodes { title url state } }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// The board named by a project URL.
+#[derive(Debug, PartialEq)]
+pub struct ProjectRef {
+    /// `organization` or `user`, as used in the GraphQL schema.
+    pub owner_kind: &'static str,
+    pub owner: String,
+    pub number: u64,
+}
+
+/// A card on the board.
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub title: String,
+    pub url: Option<String>,
+    pub column: Option<String>,
+    pub tracked: Vec<TrackedIssue>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TrackedIssue {
+    pub title: String,
+    pub url: String,
+    pub closed: bool,
+}
+
+/// Parses `https://github.com/orgs/<org>/projects/<n>` or
+/// `https://github.com/users/<user>/projects/<n>`.
+#[throws(anyhow::Error)]
+pub fn parse_url(url: &str) -> ProjectRef {
+    let path = url
+        .trim_end_matches('/')
+        .strip_prefix("https://github.com/")
+        .with_context(|| format!("`{}` is not a github.com URL", url))?;
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts[..] {
+        [kind, owner, "projects", number, ..] if kind == "orgs" || kind == "users" => ProjectRef {
+            owner_kind: if kind == "orgs" {
+                "organization"
+            } else {
+                "user"
+            },
+            owner: owner.to_string(),
+            number: number
+                .parse()
+                .with_context(|| format!("`{}` is not a project number", number))?,
+        },
+        _ => anyhow::bail!(
+            "expected a URL like `https://github.com/orgs/<org>/projects/<number>`, found `{}`",
+            url
+        ),
+    }
+}
+
+/// Fetches the board at `url` and converts it into groups. Needs a token
+/// with read access to the project in `GITHUB_TOKEN` (or the variable
+/// named by `github_token_env` in the user configuration).
+#[throws(anyhow::Error)]
+pub fn import(url: &str) -> Vec<ImportedGroup> {
+    let project = parse_url(url)?;
+    let token_env = crate::config::get()?.github_token_env();
+    let token = std::env::var(token_env)
+        .with_context(|| format!("set {} to a token that can read the project", token_env))?;
+
+    let query = QUERY.replace("OWNER", project.owner_kind);
+    let mut cards = vec![];
+    let mut after = Value::Null;
+    loop {
+        let response: Value = ureq::post(GRAPHQL_URL)
+            .set("Authorization", &format!("bearer {}", token))
+            .set("User-Agent", "skill-tree")
+            .send_json(json!({
+                "query": query,
+                "variables": {
+                    "owner": project.owner,
+                    "number": project.number,
+                    "after": after,
+                },
+            }))
+            .context("querying the GitHub GraphQL API")?
+            .into_json()?;
+
+        let items = &response["data"][project.owner_kind]["projectV2"]["items"];
+        if items.is_null() {
+            anyhow::bail!("GitHub returned no project: {}", response["errors"]);
+        }
+        cards.extend(parse_cards(items));
+
+        if items["pageInfo"]["hasNextPage"].as_bool() != Some(true) {
+            break;
+        }
+        after = items["pageInfo"]["endCursor"].clone();
+    }
+
+    convert(&cards)
+}
+
+/// Extracts the cards from one page of `items` in the GraphQL response.
+pub fn parse_cards(items: &Value) -> Vec<Card> {
+    let str_of = |v: &Value| v.as_str().map(str::to_string);
+    items["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|node| {
+            let content = &node["content"];
+            Some(Card {
+                title: str_of(&content["title"])?,
+                url: str_of(&content["url"]),
+                column: str_of(&node["fieldValueByName"]["name"]),
+                tracked: content["trackedIssues"]["nodes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|issue| {
+                        Some(TrackedIssue {
+                            title: str_of(&issue["title"])?,
+                            url: str_of(&issue["url"])?,
+                            closed: issue["state"] == "CLOSED",
+                        })
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+pub fn convert(cards: &[Card]) -> Vec<ImportedGroup> {
+    // Name each card's group, so tracked issues that are also cards can
+    // be turned into `requires` edges.
+    let names: HashMap<&str, String> = cards
+        .iter()
+        .filter_map(|card| Some((card.url.as_deref()?, group_name(card))))
+        .collect();
+
+    cards
+        .iter()
+        .map(|card| {
+            let mut group = ImportedGroup {
+                name: group_name(card),
+                label: card.title.clone(),
+                href: card.url.clone(),
+                status: card.column.as_deref().and_then(column_status),
+                ..ImportedGroup::default()
+            };
+
+            for issue in &card.tracked {
+                if let Some(name) = names.get(&issue.url[..]) {
+                    group.requires.push(name.clone());
+                }
+                group.items.push(ImportedItem {
+                    label: issue.title.clone(),
+                    href: Some(issue.url.clone()),
+                    status: if issue.closed {
+                        Some(Status::Complete)
+                    } else {
+                        None
+                    },
+                });
+            }
+
+            if group.items.is_empty() {
+                group.items.push(ImportedItem {
+                    label: card.title.clone(),
+                    href: card.url.clone(),
+                    status: group.status.clone(),
+                });
+            }
+            group
+        })
+        .collect()
+}
+
+/// `https://github.com/o/repo/issues/12` becomes `repo-12`; draft cards
+/// are named after their title.
+fn group_name(card: &Card) -> String {
+    let url = match &card.url {
+        Some(url) => url,
+        None => return slug(&card.title),
+    };
+    let parts: Vec<&str> = url.trim_end_matches('/').rsplit('/').collect();
+    match parts[..] {
+        [number, _, repo, ..] => format!("{}-{}", slug(repo), number),
+        _ => slug(&card.title),
+    }
+}
+
+/// Maps common board column names onto our statuses.
+fn column_status(column: &str) -> Option<Status> {
+    match &column.to_lowercase()[..] {
+        "todo" | "to do" | "backlog" | "ready" | "new" => Some(Status::Unassigned),
+        "in progress" | "doing" | "in review" => Some(Status::Assigned),
+        "done" | "complete" | "completed" | "closed" => Some(Status::Complete),
+        "blocked" => Some(Status::Blocked),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,88 @@
+use super::{convert, parse_cards, parse_url, ProjectRef};
+use crate::import::to_toml;
+use serde_json::json;
+
+#[test]
+fn parses_project_urls() {
+    assert_eq!(
+        parse_url("https://github.com/orgs/rust-lang/projects/42/views/1").unwrap(),
+        ProjectRef {
+            owner_kind: "organization",
+            owner: "rust-lang".to_string(),
+            number: 42,
+        }
+    );
+    assert_eq!(
+        parse_url("https://github.com/users/someone/projects/3").unwrap(),
+        ProjectRef {
+            owner_kind: "user",
+            owner: "someone".to_string(),
+            number: 3,
+        }
+    );
+    assert!(parse_url("https://github.com/rust-lang/rust").is_err());
+}
+
+#[test]
+fn converts_board() {
+    let items = json!({
+        "nodes": [
+            {
+                "fieldValueByName": { "name": "In Progress" },
+                "content": {
+                    "title": "Async fn in traits",
+                    "url": "https://github.com/o/lang/issues/1",
+                    "trackedIssues": { "nodes": [
+                        { "title": "GATs", "url": "https://github.com/o/lang/issues/2", "state": "CLOSED" },
+                        { "title": "TAIT", "url": "https://github.com/o/lang/issues/3", "state": "OPEN" },
+                    ] },
+                },
+            },
+            {
+                "fieldValueByName": { "name": "Done" },
+                "content": {
+                    "title": "GATs",
+                    "url": "https://github.com/o/lang/issues/2",
+                    "trackedIssues": { "nodes": [] },
+                },
+            },
+            {
+                "fieldValueByName": null,
+                "content": { "title": "Write a blog post" },
+            },
+        ],
+    });
+
+    let groups = convert(&parse_cards(&items));
+    assert_eq!(
+        to_toml(&groups),
+        r#"[[group]]
+name = "lang-1"
+label = "Async fn in traits"
+href = "https://github.com/o/lang/issues/1"
+status = "Assigned"
+requires = ["lang-2"]
+items = [
+    { label = "GATs", href = "https://github.com/o/lang/issues/2", status = "Complete" },
+    { label = "TAIT", href = "https://github.com/o/lang/issues/3" },
+]
+
+[[group]]
+name = "lang-2"
+label = "GATs"
+href = "https://github.com/o/lang/issues/2"
+status = "Complete"
+items = [
+    { label = "GATs", href = "https://github.com/o/lang/issues/2", status = "Complete" },
+]
+
+[[group]]
+name = "write-a-blog-post"
+label = "Write a blog post"
+items = [
+    { label = "Write a blog post" },
+]
+
+"#
+    );
+}
@@ -0,0 +1,105 @@
+//! `skill-tree import markdown <file>`: bootstraps a tree from a tracking
+//! issue written as a Markdown checklist.
+//!
+//! Each heading starts a group and each `- [ ]` / `- [x]` line below it
+//! becomes an item; checked items are marked complete. A Markdown link in
+//! the item (or a bare URL) becomes its `href`, and with a repository
+//! given, so does a `#123` issue reference.
+
+use super::{slug, ImportedGroup, ImportedItem};
+use skill_tree_core::Status;
+
+/// Converts the Markdown `text` into groups. `repo` (`owner/name`) is
+/// used to turn `#123` references into issue links.
+pub fn import(text: &str, repo: Option<&str>) -> Vec<ImportedGroup> {
+    let mut groups: Vec<ImportedGroup> = vec![];
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            let title = trimmed.trim_start_matches('#').trim();
+            if !title.is_empty() {
+                groups.push(ImportedGroup {
+                    name: slug(title),
+                    label: title.to_string(),
+                    ..ImportedGroup::default()
+                });
+            }
+        } else if let Some((checked, rest)) = checkbox(trimmed) {
+            if groups.is_empty() {
+                groups.push(ImportedGroup {
+                    name: "checklist".to_string(),
+                    label: "Checklist".to_string(),
+                    ..ImportedGroup::default()
+                });
+            }
+            let (label, href) = link(rest, repo);
+            groups.last_mut().unwrap().items.push(ImportedItem {
+                label,
+                href,
+                status: if checked {
+                    Some(Status::Complete)
+                } else {
+                    None
+                },
+            });
+        }
+    }
+
+    // Headings used only for structure (with nothing under them) aren't
+    // useful as groups.
+    groups.retain(|group| !group.items.is_empty());
+    groups
+}
+
+/// Recognizes `- [ ] rest`, `* [x] rest` and so on.
+fn checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))?
+        .trim_start();
+    let checked = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((checked, rest[3..].trim()))
+}
+
+/// Pulls a link out of an item's text, returning the label to use and the
+/// link target.
+fn link(text: &str, repo: Option<&str>) -> (String, Option<String>) {
+    // `[label](url)`
+    if let Some(start) = text.find('[') {
+        if let Some(middle) = text[start..].find("](") {
+            let middle = start + middle;
+            if let Some(end) = text[middle..].find(')') {
+                let end = middle + end;
+                let label = format!(
+                    "{}{}{}",
+                    &text[..start],
+                    &text[start + 1..middle],
+                    &text[end + 1..]
+                );
+                let href = text[middle + 2..end].to_string();
+                return (label.trim().to_string(), Some(href));
+            }
+        }
+    }
+
+    for word in text.split_whitespace() {
+        if word.starts_with("https://") || word.starts_with("http://") {
+            return (text.to_string(), Some(word.to_string()));
+        }
+        if let (Some(repo), Some(number)) = (repo, word.strip_prefix('#')) {
+            if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                let href = format!("https://github.com/{}/issues/{}", repo, number);
+                return (text.to_string(), Some(href));
+            }
+        }
+    }
+
+    (text.to_string(), None)
+}
+
+#[cfg(test)]
+mod test;
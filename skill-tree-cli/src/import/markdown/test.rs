@@ -0,0 +1,48 @@
+use super::import;
+use crate::import::to_toml;
+
+#[test]
+fn converts_checklist() {
+    let text = r#"
+Intro text that is ignored.
+
+# Async fn in traits
+
+## Language
+
+- [x] [Generic associated types](https://github.com/rust-lang/rust/issues/44265)
+- [ ] Type alias impl Trait #63063
+- not a checkbox
+
+## Library
+* [ ] Async iterator trait https://example.com/iter
+"#;
+
+    assert_eq!(
+        to_toml(&import(text, Some("rust-lang/rust"))),
+        r#"[[group]]
+name = "language"
+label = "Language"
+items = [
+    { label = "Generic associated types", href = "https://github.com/rust-lang/rust/issues/44265", status = "Complete" },
+    { label = "Type alias impl Trait #63063", href = "https://github.com/rust-lang/rust/issues/63063" },
+]
+
+[[group]]
+name = "library"
+label = "Library"
+items = [
+    { label = "Async iterator trait https://example.com/iter", href = "https://example.com/iter" },
+]
+
+"#
+    );
+}
+
+#[test]
+fn collects_items_before_any_heading() {
+    let groups = import("- [ ] one\n- [X] two\n", None);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "checklist");
+    assert_eq!(groups[0].items.len(), 2);
+}
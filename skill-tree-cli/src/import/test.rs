@@ -0,0 +1,53 @@
+use super::{slug, to_toml, ImportedGroup, ImportedItem};
+use skill_tree_core::{SkillTree, Status};
+
+#[test]
+fn writes_loadable_toml() {
+    let groups = vec![
+        ImportedGroup {
+            name: "lexer".to_string(),
+            label: "The \"lexer\"".to_string(),
+            status: Some(Status::Complete),
+            items: vec![ImportedItem {
+                label: "Tokens".to_string(),
+                href: Some("https://example.com/1".to_string()),
+                ..ImportedItem::default()
+            }],
+            ..ImportedGroup::default()
+        },
+        ImportedGroup {
+            name: "parser".to_string(),
+            label: "Parser".to_string(),
+            requires: vec!["lexer".to_string()],
+            ..ImportedGroup::default()
+        },
+    ];
+
+    let text = to_toml(&groups);
+    assert_eq!(
+        text,
+        r#"[[group]]
+name = "lexer"
+label = "The \"lexer\""
+status = "Complete"
+items = [
+    { label = "Tokens", href = "https://example.com/1" },
+]
+
+[[group]]
+name = "parser"
+label = "Parser"
+requires = ["lexer"]
+items = [
+]
+
+"#
+    );
+    SkillTree::parse(&text).unwrap().validate().unwrap();
+}
+
+#[test]
+fn slugs() {
+    assert_eq!(slug("Async fn in traits!"), "async-fn-in-traits");
+    assert_eq!(slug("  -- GATs --"), "gats");
+}
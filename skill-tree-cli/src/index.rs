@@ -0,0 +1,107 @@
+//! The `index.html` written next to `render --split-by cluster` output,
+//! listing each page with its progress and a thumbnail.
+
+use fehler::throws;
+use skill_tree_core::SkillTree;
+
+/// The file the page for `cluster` is written to with `--emit
+/// <extension>`: named after the cluster, or `unclustered` for the groups
+/// outside any cluster.
+pub fn page_file(cluster: Option<&str>, extension: &str) -> String {
+    format!("{}.{}", cluster.unwrap_or("unclustered"), extension)
+}
+
+/// Fails if a page of `tree` would be written over another page or over
+/// `index.html`, as a cluster named `index` would be with `--emit html`.
+#[throws(anyhow::Error)]
+pub fn check_page_files(tree: &SkillTree, extension: &str) {
+    let mut taken = vec![("index.html".to_string(), "the index".to_string())];
+    for cluster in tree.pages() {
+        let file = page_file(cluster, extension);
+        let page = match cluster {
+            Some(name) => format!("the cluster `{}`", name),
+            None => "the groups outside any cluster".to_string(),
+        };
+        if let Some((_, other)) = taken.iter().find(|(taken, _)| *taken == file) {
+            anyhow::bail!(
+                "{} would be written to `{}`, over {}; rename the cluster",
+                page,
+                file,
+                other
+            );
+        }
+        taken.push((file, page));
+    }
+}
+
+/// Builds the index for the pages of `tree`, given as the cluster of each
+/// page (see `SkillTree::pages`) and the file it was written to.
+/// Thumbnails are only shown for `svg` and `png` pages.
+pub fn index_html(tree: &SkillTree, pages: &[(Option<&str>, String)]) -> String {
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(&title)));
+    html.push_str(
+        "<style>\n\
+         li { margin-bottom: 1em; }\n\
+         img { display: block; max-width: 320px; max-height: 200px; border: 1px solid #ccc; }\n\
+         </style>\n",
+    );
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&title)));
+    if let Some(caption) = tree.caption() {
+        html.push_str(&format!("<p>{}</p>\n", escape(&caption)));
+    }
+
+    html.push_str("<ul>\n");
+    for (cluster, file) in pages {
+        let label = match cluster {
+            Some(name) => tree.cluster_named(name).map_or(&name[..], |c| &c.label[..]),
+            None => tree.translate("Other groups"),
+        };
+        let groups = tree.groups_in(*cluster).count();
+        let (done, total) = tree.progress(tree.groups_in(*cluster));
+        let percent = (done * 100).checked_div(total).unwrap_or(0);
+        let href = escape(file);
+
+        let groups = match groups {
+            1 => tree.translate("1 group").to_string(),
+            n => tree
+                .translate("{count} groups")
+                .replace("{count}", &n.to_string()),
+        };
+        let progress = tree
+            .translate("{done}/{total} items done ({percent}%)")
+            .replace("{done}", &done.to_string())
+            .replace("{total}", &total.to_string())
+            .replace("{percent}", &percent.to_string());
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>: {}, {}",
+            href,
+            escape(label),
+            escape(&groups),
+            escape(&progress),
+        ));
+        if file.ends_with(".svg") || file.ends_with(".png") {
+            html.push_str(&format!(
+                "\n<a href=\"{}\"><img src=\"{}\" alt=\"{}\"></a>",
+                href,
+                href,
+                escape(label)
+            ));
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
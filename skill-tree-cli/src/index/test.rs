@@ -0,0 +1,81 @@
+use super::{check_page_files, index_html};
+use skill_tree_core::SkillTree;
+
+const TREE: &str = r#"
+[doc]
+title = "Compiler & tools"
+
+[[cluster]]
+name = "frontend"
+label = "Frontend"
+
+[[group]]
+name = "lexer"
+cluster = "frontend"
+status = "Complete"
+items = [{ label = "Tokens" }, { label = "Spans" }]
+
+[[group]]
+name = "parser"
+cluster = "frontend"
+items = [{ label = "Expressions", status = "Complete" }, { label = "Items" }]
+
+[[group]]
+name = "docs"
+items = [{ label = "Book" }]
+"#;
+
+#[test]
+fn lists_pages_with_progress() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let pages = vec![
+        (Some("frontend"), "frontend.svg".to_string()),
+        (None, "unclustered.svg".to_string()),
+    ];
+    let html = index_html(&tree, &pages);
+    assert!(html.contains("<title>Compiler &amp; tools</title>"));
+    assert!(html.contains(
+        "<li><a href=\"frontend.svg\">Frontend</a>: 2 groups, 3/4 items done (75%)\n\
+         <a href=\"frontend.svg\"><img src=\"frontend.svg\" alt=\"Frontend\"></a></li>"
+    ));
+    assert!(html.contains(
+        "<li><a href=\"unclustered.svg\">Other groups</a>: 1 group, 0/1 items done (0%)"
+    ));
+}
+
+#[test]
+fn no_thumbnails_for_dot() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let html = index_html(&tree, &[(None, "unclustered.dot".to_string())]);
+    assert!(!html.contains("<img"));
+}
+
+#[test]
+fn pages_keep_off_the_index() {
+    let tree = SkillTree::parse(
+        "[[cluster]]\nname = \"index\"\nlabel = \"Index\"\n\n\
+         [[group]]\nname = \"lexer\"\ncluster = \"index\"\nitems = []\n",
+    )
+    .unwrap();
+    check_page_files(&tree, "svg").unwrap();
+    let error = check_page_files(&tree, "html").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the cluster `index` would be written to `index.html`, over the index; rename the cluster"
+    );
+}
+
+#[test]
+fn pages_keep_off_each_other() {
+    let tree = SkillTree::parse(
+        "[[cluster]]\nname = \"unclustered\"\nlabel = \"Misc\"\n\n\
+         [[group]]\nname = \"lexer\"\ncluster = \"unclustered\"\nitems = []\n\n\
+         [[group]]\nname = \"docs\"\nitems = []\n",
+    )
+    .unwrap();
+    let error = check_page_files(&tree, "svg").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the groups outside any cluster would be written to `unclustered.svg`, over the cluster `unclustered`; rename the cluster"
+    );
+}
@@ -0,0 +1,8 @@
+//! Error codes from the JSON-RPC 2.0 spec, shared by the language server
+//! and the daemon.
+
+/// JSON-RPC "parse error" code.
+pub const PARSE_ERROR: i64 = -32700;
+
+/// JSON-RPC "method not found" error code.
+pub const METHOD_NOT_FOUND: i64 = -32601;
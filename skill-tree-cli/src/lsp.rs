@@ -0,0 +1,436 @@
+//! A small language server for skill-tree TOML files.
+//!
+//! It speaks JSON-RPC over stdio and supports diagnostics (from
+//! `SkillTree::validate_all`), completion of group names inside
+//! `requires` and `suggests`, go-to-definition from a `requires` entry to its group, and
+//! hover over a group name. Documents are synced in full on every change;
+//! the trees involved are small enough that incremental sync isn't worth
+//! the complexity.
+
+use crate::jsonrpc::METHOD_NOT_FOUND;
+use crate::uri::file_path;
+use anyhow::Context;
+use fehler::throws;
+use serde_json::{json, Value};
+use skill_tree_core::diagnostic::{Diagnostic, Location, Severity};
+use skill_tree_core::source::{cluster_definition_line, group_definition_line, is_quote};
+use skill_tree_core::{ItemExt, SkillTree};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use toml_edit::{ImDocument, Item, TableLike};
+
+/// LSP `CompletionItemKind::Reference`.
+const COMPLETION_KIND_REFERENCE: u32 = 18;
+
+/// LSP `DiagnosticSeverity::Error`.
+const SEVERITY_ERROR: u32 = 1;
+
+/// LSP `DiagnosticSeverity::Warning`.
+const SEVERITY_WARNING: u32 = 2;
+
+#[throws(anyhow::Error)]
+pub fn run() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut input = stdin.lock();
+    let mut output = stdout.lock();
+    let mut server = Server::default();
+
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or("");
+        if method == "exit" {
+            break;
+        }
+
+        let params = &message["params"];
+        match message.get("id") {
+            Some(id) => {
+                let response = match server.request(method, params) {
+                    Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    None => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("unsupported method `{}`", method),
+                        },
+                    }),
+                };
+                write_message(&mut output, &response)?;
+            }
+            None => {
+                for notification in server.notification(method, params) {
+                    write_message(&mut output, &notification)?;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Server {
+    /// Text of each open document, keyed by URI.
+    documents: HashMap<String, String>,
+}
+
+impl Server {
+    /// Handles a request, returning `None` if the method is unsupported.
+    fn request(&mut self, method: &str, params: &Value) -> Option<Value> {
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": ["\""] },
+                    "definitionProvider": true,
+                    "hoverProvider": true,
+                },
+                "serverInfo": { "name": "skill-tree" },
+            }),
+            "shutdown" => Value::Null,
+            "textDocument/completion" => self.completion(params),
+            "textDocument/definition" => self.definition(params),
+            "textDocument/hover" => self.hover(params),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// Handles a notification, returning any notifications to send back.
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or("");
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                // We ask for full sync, so the last change holds the whole text.
+                let changes = params["contentChanges"].as_array();
+                if let Some(change) = changes.and_then(|c| c.last()) {
+                    let text = change["text"].as_str().unwrap_or("");
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, vec![])];
+            }
+            _ => return vec![],
+        }
+
+        // A change to a document that was never opened has nothing to check.
+        match self.documents.get(&uri) {
+            Some(text) => vec![publish_diagnostics(&uri, diagnostics(&uri, text))],
+            None => vec![],
+        }
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let (text, line, character) = match self.position(params) {
+            Some(p) => p,
+            None => return Value::Null,
+        };
+        if !in_group_list(text, line, character) {
+            return json!([]);
+        }
+
+        let tree = match SkillTree::load_text(&uri_path(params), text) {
+            Ok(tree) => tree,
+            Err(_) => return json!([]),
+        };
+        let items: Vec<Value> = tree
+            .groups()
+            .map(|group| {
+                json!({
+                    "label": group.name,
+                    "kind": COMPLETION_KIND_REFERENCE,
+                    "detail": group.label.as_ref().unwrap_or(&group.name),
+                })
+            })
+            .collect();
+        json!(items)
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let (text, line, character) = match self.position(params) {
+            Some(p) => p,
+            None => return Value::Null,
+        };
+        let name = match group_name_at(text, line, character) {
+            Some(name) => name,
+            None => return Value::Null,
+        };
+        match group_definition_line(text, name) {
+            Some(line) => json!({
+                "uri": params["textDocument"]["uri"],
+                "range": line_range(text, line),
+            }),
+            None => Value::Null,
+        }
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let (text, line, character) = match self.position(params) {
+            Some(p) => p,
+            None => return Value::Null,
+        };
+        let name = match group_name_at(text, line, character) {
+            Some(name) => name,
+            None => return Value::Null,
+        };
+        let tree = match SkillTree::load_text(&uri_path(params), text) {
+            Ok(tree) => tree,
+            Err(_) => return Value::Null,
+        };
+        let group = match tree.group_named(name) {
+            Some(group) => group,
+            None => return Value::Null,
+        };
+
+        let mut contents = format!("**{}**", group.label.as_ref().unwrap_or(&group.name));
+        if let Some(status) = &group.status {
+            contents.push_str(&format!(" ({})", tree.status_name(status)));
+        }
+        contents.push_str("\n\n");
+        for item in group.items() {
+            if let Some(label) = item.label() {
+                contents.push_str(&format!("* {}\n", label));
+            }
+        }
+        json!({ "contents": { "kind": "markdown", "value": contents } })
+    }
+
+    /// Extracts the document text and cursor position from request params.
+    fn position<'a>(&'a self, params: &Value) -> Option<(&'a str, usize, usize)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let text = self.documents.get(uri)?;
+        let line = params["position"]["line"].as_u64()? as usize;
+        let character = params["position"]["character"].as_u64()? as usize;
+        Some((text, line, character))
+    }
+}
+
+/// Loads and validates `text`, converting any problems into LSP
+/// diagnostics.
+fn diagnostics(uri: &str, text: &str) -> Vec<Value> {
+    let path = file_path(uri);
+    let tree = match SkillTree::load_text(&path, text) {
+        Ok(tree) => tree,
+        Err(error) => {
+            return vec![json!({
+                "range": line_range(text, error_line(text, &error)),
+                "severity": SEVERITY_ERROR,
+                "source": "skill-tree",
+                "message": format!("{:#}", error),
+            })]
+        }
+    };
+
+    tree.diagnostics()
+        .iter()
+        .map(|diagnostic| {
+            let mut json = json!({
+                "range": line_range(text, diagnostic_line(&path, text, diagnostic)),
+                "severity": match diagnostic.level {
+                    Severity::Error => SEVERITY_ERROR,
+                    Severity::Warning => SEVERITY_WARNING,
+                },
+                "source": "skill-tree",
+                "message": diagnostic.message,
+            });
+            if let Some(code) = diagnostic.code {
+                json["code"] = json!(code);
+            }
+            json
+        })
+        .collect()
+}
+
+/// Picks the line to attach a diagnostic to, from its location if it is
+/// in `text` (which was read from `path`, rather than an include).
+fn diagnostic_line(path: &Path, text: &str, diagnostic: &Diagnostic) -> usize {
+    let line = match &diagnostic.location {
+        Some(Location::Line { path: p, line }) if p == path => Some(line.saturating_sub(1)),
+        Some(Location::Group(group)) | Some(Location::Item { group, .. }) => {
+            group_definition_line(text, group)
+        }
+        Some(Location::Cluster(cluster)) => cluster_definition_line(text, cluster),
+        _ => None,
+    };
+    line.unwrap_or_else(|| message_line(text, &diagnostic.message))
+}
+
+/// Picks the line to attach an error to. TOML syntax errors know their
+/// position; for anything else we point at the group the message names.
+fn error_line(text: &str, error: &anyhow::Error) -> usize {
+    for cause in error.chain() {
+        if let Some(toml_error) = cause.downcast_ref::<toml::de::Error>() {
+            if let Some((line, _)) = toml_error.line_col() {
+                return line;
+            }
+        }
+    }
+
+    message_line(text, &error.to_string())
+}
+
+/// Finds the line a diagnostic message refers to. Messages either start
+/// with a `file:line:` location or name the offending group in backticks
+/// first, in which case we find the line defining that group.
+fn message_line(text: &str, message: &str) -> usize {
+    let location = message.split(": ").next().unwrap_or("");
+    if let Some(line) = location.rsplit(':').next() {
+        if let Ok(line) = line.parse::<usize>() {
+            return line.saturating_sub(1);
+        }
+    }
+
+    message
+        .split('`')
+        .nth(1)
+        .and_then(|name| group_definition_line(text, name))
+        .unwrap_or(0)
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn line_range(text: &str, line: usize) -> Value {
+    let len = text
+        .lines()
+        .nth(line)
+        .map(|l| l.chars().count())
+        .unwrap_or(0);
+    json!({
+        "start": { "line": line, "character": 0 },
+        "end": { "line": line, "character": len },
+    })
+}
+
+fn uri_path(params: &Value) -> PathBuf {
+    file_path(params["textDocument"]["uri"].as_str().unwrap_or(""))
+}
+
+/// Returns the group name in the quoted string under the cursor, dropping
+/// any `:port` suffix.
+fn group_name_at(text: &str, line: usize, character: usize) -> Option<&str> {
+    let line = text.lines().nth(line)?;
+    let offset = line
+        .char_indices()
+        .nth(character)
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| line.len());
+    let start = line[..offset].rfind(is_quote)? + 1;
+    let end = offset + line[offset..].find(is_quote)?;
+    let word = &line[start..end];
+    Some(word.split(':').next().unwrap_or(word))
+}
+
+/// True if the cursor sits inside the brackets of a `requires = [...]` or
+/// `suggests = [...]` list, judged from the parsed document. While a
+/// name is being typed its string is usually unterminated, so if the
+/// text doesn't parse we try again with the string closed at the cursor.
+fn in_group_list(text: &str, line: usize, character: usize) -> bool {
+    let offset = match byte_offset(text, line, character) {
+        Some(offset) => offset,
+        None => return false,
+    };
+    let closed = format!("{}\"{}", &text[..offset], &text[offset..]);
+    let key = match ImDocument::parse(text).or_else(|_| ImDocument::parse(closed.as_str())) {
+        Ok(document) => list_key_at(document.as_table(), offset),
+        Err(_) => None,
+    };
+    matches!(key.as_deref(), Some("requires") | Some("suggests"))
+}
+
+/// Converts a (0-based) line and character into a byte offset in `text`.
+fn byte_offset(text: &str, line: usize, character: usize) -> Option<usize> {
+    let mut start = 0;
+    for (index, l) in text.split('\n').enumerate() {
+        if index == line {
+            let column = l
+                .char_indices()
+                .nth(character)
+                .map(|(i, _)| i)
+                .unwrap_or_else(|| l.len());
+            return Some(start + column);
+        }
+        start += l.len() + 1;
+    }
+    None
+}
+
+/// Finds the key of the innermost array whose brackets enclose `offset`.
+fn list_key_at(table: &dyn TableLike, offset: usize) -> Option<String> {
+    for (key, item) in table.iter() {
+        let found = match item {
+            Item::Table(table) => list_key_at(table, offset),
+            Item::ArrayOfTables(tables) => tables.iter().find_map(|t| list_key_at(t, offset)),
+            Item::Value(value) => value_list_key_at(key, value, offset),
+            Item::None => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+fn value_list_key_at(key: &str, value: &toml_edit::Value, offset: usize) -> Option<String> {
+    match value {
+        toml_edit::Value::Array(array) => {
+            let span = array.span()?;
+            if offset <= span.start || offset >= span.end {
+                return None;
+            }
+            let nested = array
+                .iter()
+                .find_map(|value| value_list_key_at(key, value, offset));
+            Some(nested.unwrap_or_else(|| key.to_string()))
+        }
+        toml_edit::Value::InlineTable(table) => list_key_at(table, offset),
+        _ => None,
+    }
+}
+
+#[throws(anyhow::Error)]
+fn read_message(input: &mut impl BufRead) -> Option<Value> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return None;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(length) = header.strip_prefix("Content-Length:") {
+            content_length = Some(length.trim().parse::<usize>()?);
+        }
+    }
+
+    let content_length = content_length.context("message without `Content-Length` header")?;
+    let mut body = vec![0; content_length];
+    input.read_exact(&mut body)?;
+    Some(serde_json::from_slice(&body)?)
+}
+
+#[throws(anyhow::Error)]
+fn write_message(output: &mut impl Write, message: &Value) {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,115 @@
+use super::{group_name_at, in_group_list, message_line, Server};
+use serde_json::json;
+
+const TREE: &str = r#"[[group]]
+name = "lexer"
+label = "Lexer"
+status = "Assigned"
+items = [
+    { label = "Tokens" },
+]
+
+[[group]]
+name = "parser"
+requires = [
+    "lexer",
+    "missing",
+]
+items = []
+"#;
+
+fn open(server: &mut Server) -> serde_json::Value {
+    let params = json!({
+        "textDocument": { "uri": "file:///tmp/tree.toml", "text": TREE },
+    });
+    server
+        .notification("textDocument/didOpen", &params)
+        .remove(0)
+}
+
+#[test]
+fn detects_group_list_context() {
+    assert!(in_group_list(TREE, 12, 5));
+    assert!(!in_group_list(TREE, 2, 5));
+    assert!(!in_group_list(TREE, 5, 4));
+    assert!(!in_group_list(TREE, 14, 9));
+    assert!(!in_group_list(TREE, 15, 0));
+}
+
+#[test]
+fn detects_suggests_and_unterminated_names() {
+    let text = "[[group]]\nname = \"a\"\nsuggests = [\"b\"]\nitems = []\n";
+    assert!(in_group_list(text, 2, 13));
+    assert!(!in_group_list(text, 3, 9));
+
+    let typing = "[[group]]\nname = \"a\"\nrequires = [\"le]\nitems = []\n";
+    assert!(in_group_list(typing, 2, 15));
+}
+
+#[test]
+fn ignores_changes_to_unopened_documents() {
+    let mut server = Server::default();
+    let params = json!({
+        "textDocument": { "uri": "file:///tmp/other.toml" },
+        "contentChanges": [],
+    });
+    assert!(server
+        .notification("textDocument/didChange", &params)
+        .is_empty());
+}
+
+#[test]
+fn reads_group_name_under_cursor() {
+    assert_eq!(group_name_at(TREE, 11, 7), Some("lexer"));
+    assert_eq!(group_name_at(r#"requires = ["a:port"]"#, 0, 14), Some("a"));
+}
+
+#[test]
+fn reports_missing_requires() {
+    let mut server = Server::default();
+    let notification = open(&mut server);
+    let diagnostics = &notification["params"]["diagnostics"];
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 9);
+}
+
+#[test]
+fn completes_and_resolves_requires() {
+    let mut server = Server::default();
+    open(&mut server);
+    let at = |line, character| {
+        json!({
+            "textDocument": { "uri": "file:///tmp/tree.toml" },
+            "position": { "line": line, "character": character },
+        })
+    };
+
+    let completion = server
+        .request("textDocument/completion", &at(12, 5))
+        .unwrap();
+    let labels: Vec<_> = completion
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(labels, vec!["lexer", "parser"]);
+
+    let definition = server
+        .request("textDocument/definition", &at(11, 7))
+        .unwrap();
+    assert_eq!(definition["range"]["start"]["line"], 1);
+
+    let hover = server.request("textDocument/hover", &at(11, 7)).unwrap();
+    assert_eq!(
+        hover["contents"]["value"],
+        "**Lexer** (Assigned)\n\n* Tokens\n"
+    );
+}
+
+#[test]
+fn locates_messages() {
+    assert_eq!(message_line(TREE, "tree.toml:12: `x` is deprecated"), 11);
+    assert_eq!(message_line(TREE, "the group `parser` is broken"), 9);
+    assert_eq!(message_line(TREE, "something else"), 0);
+}
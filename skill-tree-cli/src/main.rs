@@ -0,0 +1,1081 @@
+use anyhow::Context;
+use fehler::throws;
+use skill_tree_core::date::Date;
+use skill_tree_core::filter::Filter;
+use skill_tree_core::lint::{self, Level};
+use skill_tree_core::render::Renderers;
+use skill_tree_core::{Rankdir, SkillTree};
+use stamp::Stamp;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+mod accessibility;
+#[cfg(feature = "http-api")]
+mod api;
+mod badge;
+mod burndown;
+mod cache;
+mod cargo;
+mod check;
+mod config;
+mod daemon;
+mod doctor;
+mod embed;
+mod feed;
+#[cfg(feature = "http-api")]
+mod graphql;
+mod graphviz;
+mod html;
+mod import;
+mod index;
+mod jsonrpc;
+mod lsp;
+mod notify;
+mod overrides;
+mod paper;
+mod pdf;
+mod preview;
+mod pull_requests;
+mod stamp;
+mod sync;
+mod timeline;
+mod twemoji;
+mod uri;
+mod workspace;
+
+// Parsed once at startup, so the size of `RenderOpts` doesn't matter.
+#[allow(clippy::large_enum_variant)]
+#[derive(StructOpt, Debug)]
+#[structopt(name = "skill-tree")]
+enum Opts {
+    /// Render a skill tree to a graphviz `dot` file, or through graphviz
+    /// to an image.
+    Render(RenderOpts),
+
+    /// Run a language server for skill-tree TOML files over stdio.
+    Lsp,
+
+    /// Keep skill trees loaded and answer JSON-RPC requests about them
+    /// (`validate`, `render`, `query` and `diff`), one per line, over
+    /// stdio or a unix socket.
+    Daemon(DaemonOpts),
+
+    /// Serve a tree over HTTP as a read-only REST API (`/tree`,
+    /// `/groups/{name}`, `/render.svg` and `/stats`).
+    #[cfg(feature = "http-api")]
+    Serve(ServeOpts),
+
+    /// Check that graphviz is installed and able to render skill trees.
+    Doctor,
+
+    /// Load and validate skill trees, printing every error and warning.
+    /// Exits with 1 if there are errors (or more warnings than
+    /// `--max-warnings`) and 2 if a file can't be loaded.
+    Check(CheckOpts),
+
+    /// Rewrite skill tree files written for older schema versions.
+    Migrate(MigrateOpts),
+
+    /// Create a starting skill tree from planning data kept elsewhere.
+    Import(ImportOpts),
+
+    /// Chart how many groups of each cluster were open and complete over
+    /// time, from the groups' `status_history`.
+    Burndown(BurndownOpts),
+
+    /// Draw a badge showing how many items of a group, or of the whole
+    /// tree, are done.
+    Badge(BadgeOpts),
+
+    /// Rewrite the parts of Markdown files between `<!-- skill-tree:begin
+    /// -->` and `<!-- skill-tree:end -->` with a flowchart (or image link)
+    /// and a progress table of a tree.
+    Embed(EmbedOpts),
+
+    /// Check that the GitHub issues groups link to have the labels for the
+    /// groups' statuses (see `doc.issue_labels`), or with `--push`, fix
+    /// them.
+    Sync(SyncOpts),
+
+    /// Summarize what changed since an earlier revision of a tree
+    /// (groups completed, newly blocked, added and removed) and post it
+    /// to a Slack, Discord or Matrix webhook.
+    Notify(NotifyOpts),
+
+    /// Render every `*.skill-tree.toml` under a directory, one tree per
+    /// team say, into a page each and an `index.html` overview.
+    Workspace(WorkspaceOpts),
+
+    /// Render the `skill-tree.toml` (or `.skill-tree/*.toml`) at the root
+    /// of the current cargo workspace into `target/skill-tree/`. Also
+    /// available as `cargo skill-tree`.
+    Cargo(CargoOpts),
+}
+
+#[derive(StructOpt, Debug)]
+struct DaemonOpts {
+    /// Listen on a unix socket at this path instead of using stdio.
+    #[structopt(long, parse(from_os_str))]
+    socket: Option<PathBuf>,
+}
+
+#[cfg(feature = "http-api")]
+#[derive(StructOpt, Debug)]
+struct ServeOpts {
+    #[structopt(name = "skill_tree", parse(from_os_str))]
+    skill_tree: PathBuf,
+
+    /// The address to listen on.
+    #[structopt(long, default_value = "127.0.0.1:8080")]
+    address: String,
+}
+
+#[derive(StructOpt, Debug)]
+struct WorkspaceOpts {
+    /// The directory to look for trees under.
+    #[structopt(name = "dir", parse(from_os_str))]
+    dir: PathBuf,
+
+    /// The directory to write the pages to.
+    #[structopt(short, long, parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(StructOpt, Debug)]
+struct CargoOpts {
+    /// What to produce for each tree.
+    #[structopt(long, default_value = "svg", possible_values = &["dot", "svg", "png"])]
+    emit: String,
+
+    /// Stamp each output as `render --stamp` does.
+    #[structopt(long)]
+    stamp: bool,
+}
+
+#[derive(StructOpt, Debug)]
+struct BurndownOpts {
+    #[structopt(name = "skill_tree", parse(from_os_str))]
+    skill_tree: PathBuf,
+
+    /// `csv` (one row per cluster and date) or an `svg` line chart.
+    #[structopt(long, default_value = "csv", possible_values = &["csv", "svg"])]
+    emit: String,
+
+    /// Where to write the chart (defaults to stdout).
+    #[structopt(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+struct BadgeOpts {
+    #[structopt(name = "skill_tree", parse(from_os_str))]
+    skill_tree: PathBuf,
+
+    /// The group to count (defaults to every group).
+    #[structopt(long)]
+    group: Option<String>,
+
+    /// Where to write the SVG (defaults to stdout).
+    #[structopt(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+struct EmbedOpts {
+    /// Markdown files to update in place.
+    #[structopt(name = "markdown", parse(from_os_str), required = true)]
+    markdown: Vec<PathBuf>,
+
+    /// The tree to embed where a marker has no `tree=...`.
+    #[structopt(long, parse(from_os_str))]
+    tree: Option<PathBuf>,
+
+    /// Don't write anything; fail if any file is out of date.
+    #[structopt(long)]
+    check: bool,
+}
+
+#[derive(StructOpt, Debug)]
+struct SyncOpts {
+    #[structopt(name = "skill_tree", parse(from_os_str))]
+    skill_tree: PathBuf,
+
+    /// Update the issues' labels. Otherwise the issues that are out of
+    /// date are listed, failing if there are any.
+    #[structopt(long)]
+    push: bool,
+
+    /// With `--push`, list what would change without changing it.
+    #[structopt(long, requires = "push")]
+    dry_run: bool,
+}
+
+#[derive(StructOpt, Debug)]
+struct NotifyOpts {
+    #[structopt(name = "skill_tree", parse(from_os_str))]
+    skill_tree: PathBuf,
+
+    /// The earlier revision of the tree to compare against.
+    #[structopt(long, parse(from_os_str))]
+    baseline: PathBuf,
+
+    /// Where to post the summary (otherwise it is printed).
+    #[structopt(long)]
+    webhook: Option<String>,
+
+    /// What the webhook expects (guessed from its URL by default).
+    #[structopt(long, possible_values = notify::Service::NAMES)]
+    service: Option<String>,
+}
+
+#[derive(StructOpt, Debug)]
+struct CheckOpts {
+    #[structopt(name = "skill_tree", parse(from_os_str), required = true)]
+    skill_trees: Vec<PathBuf>,
+
+    /// Fail if there are more warnings than this (e.g. `0` to treat
+    /// warnings as errors). By default warnings never fail the check.
+    #[structopt(long)]
+    max_warnings: Option<usize>,
+
+    /// `json` prints each problem as a line of JSON, for editors and CI;
+    /// `github` as GitHub Actions annotations; `sarif` as a SARIF log, for
+    /// code scanning dashboards.
+    #[structopt(
+        long,
+        default_value = "human",
+        possible_values = &["human", "json", "github", "sarif"]
+    )]
+    message_format: String,
+
+    /// Reuse the result of the last check of each tree whose files haven't
+    /// changed since, e.g. in a pre-commit hook. Results are cached under
+    /// `~/.cache/skill-tree` (or wherever `SKILL_TREE_CACHE` points).
+    #[structopt(long)]
+    quick: bool,
+
+    #[structopt(flatten)]
+    lints: LintOpts,
+}
+
+/// Lint levels that override `doc.lints`, as in rustc.
+#[derive(StructOpt, Clone, Debug, Default)]
+struct LintOpts {
+    /// Don't report this lint. May be repeated.
+    #[structopt(short = "A", long = "allow", name = "allow-lint", number_of_values = 1)]
+    allow: Vec<String>,
+
+    /// Report this lint as a warning. May be repeated.
+    #[structopt(short = "W", long = "warn", name = "warn-lint", number_of_values = 1)]
+    warn: Vec<String>,
+
+    /// Report this lint as an error. May be repeated.
+    #[structopt(short = "D", long = "deny", name = "deny-lint", number_of_values = 1)]
+    deny: Vec<String>,
+}
+
+impl LintOpts {
+    /// The overridden levels, in order of increasing severity so that
+    /// `--deny` wins if a lint is named twice.
+    #[throws(anyhow::Error)]
+    fn levels(&self) -> Vec<(&str, Level)> {
+        let mut levels = vec![];
+        for (names, level) in [
+            (&self.allow, Level::Allow),
+            (&self.warn, Level::Warn),
+            (&self.deny, Level::Deny),
+        ] {
+            for name in names {
+                if lint::find(name).is_none() {
+                    anyhow::bail!(
+                        "unknown lint `{}`; the lints are {}",
+                        name,
+                        lint::LINTS
+                            .iter()
+                            .map(|lint| lint.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+                levels.push((&name[..], level));
+            }
+        }
+        levels
+    }
+}
+
+#[derive(StructOpt, Debug)]
+struct ImportOpts {
+    #[structopt(subcommand)]
+    source: ImportSource,
+
+    /// Where to write the new tree (defaults to stdout).
+    #[structopt(short, long, parse(from_os_str), global = true)]
+    output: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+enum ImportSource {
+    /// Import a GitHub Projects board (reads `GITHUB_TOKEN`).
+    GithubProject {
+        /// e.g. `https://github.com/orgs/<org>/projects/<number>`
+        url: String,
+    },
+
+    /// Import the member crates of a cargo workspace, with their features
+    /// as items.
+    Cargo {
+        /// The workspace's `Cargo.toml` (defaults to the one cargo finds
+        /// from the current directory).
+        #[structopt(long, parse(from_os_str))]
+        manifest_path: Option<PathBuf>,
+    },
+
+    /// Import a Markdown checklist, such as a tracking issue.
+    Markdown {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+
+        /// `owner/name` of the repository that `#123` references point to.
+        #[structopt(long)]
+        repo: Option<String>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+struct RenderOpts {
+    /// The skill tree to render (`-` reads it from stdin), then any more
+    /// trees to merge into it (as if it included them), which needs
+    /// `--output`. Without `--output`, a second path that isn't a `.toml`
+    /// file is where to write the output, as in `render in.toml out.svg`.
+    #[structopt(name = "skill_tree", parse(from_os_str), required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Where to write the output (`-` for stdout); `preview-json` defaults
+    /// to stdout. With `--separate` or `--split-by`, a directory.
+    #[structopt(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// Render each input on its own, into `<output>/<name>.<emit>`,
+    /// instead of merging them (needs `--output`).
+    #[structopt(long)]
+    separate: bool,
+
+    /// Directory that includes are resolved against when the tree is read
+    /// from stdin (defaults to the current directory).
+    #[structopt(long, parse(from_os_str))]
+    base_dir: Option<PathBuf>,
+
+    /// What to produce: a graphviz `dot` file, an `svg`, `png`, `pdf` or
+    /// `ps` image (a `png` also gets an HTML snippet with a clickable image
+    /// map next to it), an `html` page with the SVG and a search box, or
+    /// `preview-json` (rendered SVG plus a map from nodes to their source
+    /// lines, for editor plugins), a `timeline` (an SVG with the groups
+    /// laid out by their `start` and `due` dates), or the dependencies as
+    /// CSV: `edges` (`from,to,port,kind`) or an `adjacency` matrix, a
+    /// `mermaid` flowchart, or an `atom` feed of the groups' status
+    /// changes.
+    /// Defaults to `emit` in the user configuration, or `dot`.
+    #[structopt(long, possible_values = config::emit_formats())]
+    emit: Option<String>,
+
+    /// Record the skill-tree version, the source file's git blob id and
+    /// the render time in the output (a comment in `dot`, `<metadata>` in
+    /// SVG, a footer in the PNG's HTML snippet).
+    #[structopt(long)]
+    stamp: bool,
+
+    /// Write one file per cluster into the directory `output_path`
+    /// (groups outside any cluster go in `unclustered`), plus an
+    /// `index.html` linking to them. Dependencies between pages are drawn
+    /// as notes linking to the other page.
+    #[structopt(long, possible_values = &["cluster"])]
+    split_by: Option<String>,
+
+    /// Embed a script in `svg` output that pans (drag) and zooms (mouse
+    /// wheel) when the file is opened directly in a browser. `html`
+    /// output always has this.
+    #[structopt(long)]
+    pan_zoom: bool,
+
+    /// Draw the emoji in `svg` and `html` output as embedded Twemoji
+    /// graphics, so they look the same whatever fonts the viewer has.
+    #[structopt(long)]
+    twemoji: bool,
+
+    /// Mark items whose `href` is a GitHub pull request `Complete` once it
+    /// is merged. Reads `GITHUB_TOKEN`, if set, for private repositories
+    /// and higher rate limits.
+    #[structopt(long)]
+    pull_requests: bool,
+
+    /// With `--pull-requests`, don't ask GitHub; use what earlier runs
+    /// found out, as cached under `~/.cache/skill-tree`.
+    #[structopt(long, requires = "pull-requests")]
+    offline: bool,
+
+    /// Lay the drawing out for printing on this paper size, scaled to
+    /// fit one sheet.
+    #[structopt(long, possible_values = paper::PAPERS, case_insensitive = true)]
+    paper: Option<String>,
+
+    /// Orientation of the `--paper` (defaults to portrait).
+    #[structopt(long, possible_values = &["portrait", "landscape"], requires = "paper")]
+    orientation: Option<String>,
+
+    /// Instead of scaling the drawing to one sheet of `--paper`, print it
+    /// at full size across as many sheets as it needs (`--emit ps` only).
+    #[structopt(long, requires = "paper")]
+    tile: bool,
+
+    /// Override `rankdir` in `[graphviz]` (e.g. `LR` or `top-to-bottom`).
+    #[structopt(long, parse(try_from_str = overrides::parse_rankdir))]
+    rankdir: Option<Rankdir>,
+
+    /// Override a setting in the file for this render, e.g.
+    /// `--set doc.defaults.status=unassigned`. May be repeated.
+    #[structopt(long = "set", name = "key=value", number_of_values = 1)]
+    set: Vec<String>,
+
+    /// Show only these item columns, in this order (comma-separated).
+    #[structopt(long)]
+    columns: Option<String>,
+
+    /// Leave out groups that no goal (see `doc.goals`) depends on.
+    #[structopt(long)]
+    hide_unreachable: bool,
+
+    /// Show only the groups that match this query, e.g. `status=blocked
+    /// and cluster=frontend`. A group that required a hidden group is
+    /// shown requiring what that group required.
+    #[structopt(long, name = "query")]
+    select: Option<Filter>,
+
+    /// Show the tree as it stood on this date (`YYYY-MM-DD`), using each
+    /// group's `status_history`.
+    #[structopt(long, name = "date")]
+    as_of: Option<Date>,
+
+    #[structopt(flatten)]
+    lints: LintOpts,
+}
+
+/// One render that a `render` command asks for (see `RenderOpts::jobs`).
+#[derive(Debug)]
+struct RenderJob<'o> {
+    opts: &'o RenderOpts,
+
+    /// The root tree of this render.
+    skill_tree: PathBuf,
+
+    /// Trees to merge into `skill_tree`.
+    merge: Vec<PathBuf>,
+
+    /// Where to write this render.
+    output_path: Option<PathBuf>,
+}
+
+impl RenderOpts {
+    fn emit(&self) -> &str {
+        self.emit.as_deref().unwrap_or("dot")
+    }
+
+    /// Works out from `paths`, `--output` and `--separate` what to render
+    /// where.
+    #[throws(anyhow::Error)]
+    fn jobs(&self) -> Vec<RenderJob<'_>> {
+        let mut inputs = self.paths.clone();
+        let output = match &self.output {
+            Some(output) => Some(output.clone()),
+            None if inputs.len() > 2 && !self.separate => {
+                anyhow::bail!("`--output` is required to merge several trees")
+            }
+            None if inputs.len() == 2 && !self.separate => {
+                let output = inputs.pop().unwrap();
+                if output.extension().is_some_and(|ext| ext == "toml")
+                    || (output == inputs[0] && !is_stdio(&output))
+                {
+                    anyhow::bail!(
+                        "`{}` looks like a tree, not where to write the output; \
+                         pass `--output` to merge it",
+                        output.display()
+                    );
+                }
+                Some(output)
+            }
+            None => None,
+        };
+
+        if inputs.iter().skip(1).any(|input| is_stdio(input)) {
+            anyhow::bail!("only the first tree can be read from stdin (`-`)");
+        }
+        if self.base_dir.is_some() && !is_stdio(&inputs[0]) {
+            anyhow::bail!("`--base-dir` only applies when the tree is read from stdin (`-`)");
+        }
+
+        if !self.separate {
+            let (root, merge) = inputs.split_first().unwrap();
+            return vec![RenderJob {
+                opts: self,
+                skill_tree: root.clone(),
+                merge: merge.to_vec(),
+                output_path: output,
+            }];
+        }
+
+        let dir = match output {
+            Some(dir) if !is_stdio(&dir) => dir,
+            _ => anyhow::bail!("an output directory is required with `--separate`"),
+        };
+        if is_stdio(&inputs[0]) {
+            anyhow::bail!("`--separate` can't read a tree from stdin (`-`)");
+        }
+        fs::create_dir_all(&dir).with_context(|| format!("creating `{}`", dir.display()))?;
+        inputs
+            .iter()
+            .map(|input| {
+                let stem = input.file_stem().unwrap_or_default();
+                let output_path = match self.split_by {
+                    Some(_) => dir.join(stem),
+                    None => dir.join(stem).with_extension(self.emit()),
+                };
+                RenderJob {
+                    opts: self,
+                    skill_tree: input.clone(),
+                    merge: vec![],
+                    output_path: Some(output_path),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(StructOpt, Debug)]
+struct MigrateOpts {
+    /// Files to migrate in place (includes are not followed).
+    #[structopt(name = "skill_tree", parse(from_os_str), required = true)]
+    skill_trees: Vec<PathBuf>,
+
+    /// Don't write anything; fail if any file needs migrating.
+    #[structopt(long)]
+    check: bool,
+}
+
+#[throws(anyhow::Error)]
+fn main() {
+    let opts = Opts::from_args();
+    match opts {
+        Opts::Render(mut opts) => {
+            if opts.emit.is_none() {
+                opts.emit = config::get()?.emit.clone();
+            }
+            for job in opts.jobs()? {
+                render(&job)?;
+            }
+        }
+        Opts::Lsp => lsp::run()?,
+        Opts::Daemon(opts) => match &opts.socket {
+            Some(path) => daemon::run_socket(path)?,
+            None => daemon::run_stdio()?,
+        },
+        #[cfg(feature = "http-api")]
+        Opts::Serve(opts) => api::run(&api::Api::new(&opts.skill_tree), &opts.address)?,
+        Opts::Doctor => doctor::run()?,
+        Opts::Check(opts) => {
+            let paths: Vec<&Path> = opts.skill_trees.iter().map(|p| p.as_path()).collect();
+            let lints = opts.lints.levels()?;
+            let format = match &opts.message_format[..] {
+                "json" => check::MessageFormat::Json,
+                "github" => check::MessageFormat::Github,
+                "sarif" => check::MessageFormat::Sarif,
+                _ => check::MessageFormat::Human,
+            };
+            let cache = if opts.quick { cache::dir() } else { None };
+            std::process::exit(check::run(
+                &paths,
+                &lints,
+                opts.max_warnings,
+                format,
+                cache.as_deref(),
+            ));
+        }
+        Opts::Migrate(opts) => migrate(&opts)?,
+        Opts::Import(opts) => import(&opts)?,
+        Opts::Burndown(opts) => burndown(&opts)?,
+        Opts::Badge(opts) => badge(&opts)?,
+        Opts::Embed(opts) => embed(&opts)?,
+        Opts::Sync(opts) => sync(&opts)?,
+        Opts::Notify(opts) => notify(&opts)?,
+        Opts::Workspace(opts) => workspace::run(&opts.dir, &opts.output)?,
+        Opts::Cargo(opts) => cargo::run(&opts.emit, opts.stamp)?,
+    }
+}
+
+#[throws(anyhow::Error)]
+fn render(job: &RenderJob<'_>) {
+    let opts = job.opts;
+
+    // Load the skill tree. Includes are relative to its file or, for
+    // stdin, to `--base-dir`.
+    let text = read_input(&job.skill_tree)?;
+    let source = if is_stdio(&job.skill_tree) {
+        opts.base_dir.clone().unwrap_or_default().join("<stdin>")
+    } else {
+        job.skill_tree.clone()
+    };
+    let mut skill_tree = if opts.set.is_empty() {
+        SkillTree::load_text(&source, &text)
+    } else {
+        SkillTree::load_text(&source, &overrides::set(&text, &opts.set)?)
+    }
+    .with_context(|| format!("loading skill tree from `{}`", source.display()))?;
+    for path in &job.merge {
+        skill_tree.merge_file(path)?;
+    }
+    if let Some(date) = opts.as_of {
+        skill_tree.as_of(date);
+    }
+    if opts.pull_requests {
+        let mut pulls = pull_requests::PullRequests::new(cache::dir().as_deref(), opts.offline)?;
+        let result = skill_tree.apply_statuses(&mut pulls);
+        pulls.save();
+        result.context("finding out which pull requests are merged")?;
+        for failure in pulls.failures() {
+            eprintln!(
+                "warning: {:#}; leaving the status of its items as it is",
+                failure
+            );
+        }
+    }
+
+    if let Some(rankdir) = opts.rankdir {
+        skill_tree
+            .graphviz
+            .get_or_insert_with(Default::default)
+            .rankdir = Some(rankdir);
+    }
+    if let Some(columns) = &opts.columns {
+        overrides::select_columns(&mut skill_tree, columns)?;
+    }
+
+    config::get()?.apply_theme(&mut skill_tree);
+    for (name, level) in opts.lints.levels()? {
+        skill_tree.set_lint_level(name, level)?;
+    }
+
+    // Validate it for errors.
+    skill_tree.validate()?;
+    for warning in skill_tree.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+    if opts.hide_unreachable {
+        if skill_tree.reachable_from_goals().is_none() {
+            anyhow::bail!("`--hide-unreachable` needs goals (`doc.goals` or `goal = true`)");
+        }
+        skill_tree.remove_unreachable();
+    }
+    if let Some(filter) = &opts.select {
+        skill_tree.select_groups(filter);
+        if skill_tree.groups().next().is_none() {
+            anyhow::bail!("no group matches `--select`");
+        }
+    }
+
+    if let Some(paper) = &opts.paper {
+        if opts.tile && opts.emit() != "ps" {
+            anyhow::bail!("`--tile` needs `--emit ps`; graphviz only splits PostScript into pages");
+        }
+        let landscape = opts.orientation.as_deref() == Some("landscape");
+        paper::apply(&mut skill_tree, &paper.to_lowercase(), landscape, opts.tile);
+    }
+
+    let stamp = if opts.stamp {
+        Some(Stamp::new(&source, &text))
+    } else {
+        None
+    };
+
+    match (opts.emit(), &opts.split_by) {
+        (
+            emit @ ("preview-json" | "timeline" | "edges" | "adjacency" | "mermaid" | "atom"),
+            Some(_),
+        ) => {
+            anyhow::bail!("`--split-by` can't be used with `--emit {}`", emit)
+        }
+        (_, Some(_)) => write_pages(&skill_tree, job, stamp.as_ref())?,
+        ("preview-json", None) => write_preview_json(&skill_tree, job, &source, &text)?,
+        (emit, None) if renderers().get(emit).is_some() => {
+            write_rendered(&skill_tree, job, stamp.as_ref())?
+        }
+        (_, None) => write_image(&skill_tree, job, stamp.as_ref())?,
+    }
+}
+
+/// Writes one file per page (see `--split-by`) into the output directory.
+#[throws(anyhow::Error)]
+fn write_pages(skill_tree: &SkillTree, job: &RenderJob<'_>, stamp: Option<&Stamp>) {
+    let opts = job.opts;
+    let dir = match &job.output_path {
+        Some(path) if !is_stdio(path) => path,
+        _ => anyhow::bail!("an output directory is required with `--split-by`"),
+    };
+    fs::create_dir_all(dir).with_context(|| format!("creating `{}`", dir.display()))?;
+
+    index::check_page_files(skill_tree, opts.emit())?;
+    let file_name = |cluster: Option<&str>| index::page_file(cluster, opts.emit());
+    let mut pages = vec![];
+    for cluster in skill_tree.pages() {
+        let dot_text = skill_tree.page_to_graphviz(cluster, &file_name)?;
+        let path = dir.join(file_name(cluster));
+        match opts.emit() {
+            "dot" => write_dot_text(&path, &dot_text, stamp)?,
+            _ => write_image_file(skill_tree, &dot_text, &path, opts, stamp)?,
+        }
+        pages.push((cluster, file_name(cluster)));
+    }
+
+    let index_path = dir.join("index.html");
+    let index = index::index_html(skill_tree, &pages);
+    let index = match stamp {
+        Some(stamp) => stamp.html(&index),
+        None => index,
+    };
+    fs::write(&index_path, index)
+        .with_context(|| format!("writing to `{}`", index_path.display()))?;
+}
+
+#[throws(anyhow::Error)]
+fn write_image(skill_tree: &SkillTree, job: &RenderJob<'_>, stamp: Option<&Stamp>) {
+    let path = match &job.output_path {
+        Some(path) => path,
+        None => anyhow::bail!(
+            "an output path is required when emitting `{}`",
+            job.opts.emit()
+        ),
+    };
+    write_image_file(
+        skill_tree,
+        &skill_tree.to_graphviz()?,
+        path,
+        job.opts,
+        stamp,
+    )?;
+}
+
+/// Renders `dot_text`, which was generated from `skill_tree`, to an image
+/// (or an HTML page) at `path`.
+#[throws(anyhow::Error)]
+fn write_image_file(
+    skill_tree: &SkillTree,
+    dot_text: &str,
+    path: &Path,
+    opts: &RenderOpts,
+    stamp: Option<&Stamp>,
+) {
+    let format = opts.emit();
+    let title = skill_tree.title();
+    let image = match format {
+        "svg" | "html" => {
+            let mut svg = graphviz::render_svg(dot_text, title.as_deref())?;
+            svg = accessibility::accessible_svg(skill_tree, &svg);
+            if opts.twemoji {
+                svg = twemoji::inline_emoji(&svg);
+            }
+            if let Some(stamp) = stamp {
+                svg = stamp.svg(&svg);
+            }
+            match (format, stamp) {
+                ("html", Some(stamp)) => stamp.html(&html::page(skill_tree, &svg)?),
+                ("html", None) => html::page(skill_tree, &svg)?,
+                _ if opts.pan_zoom => html::pan_zoom_svg(&svg),
+                _ => svg,
+            }
+            .into_bytes()
+        }
+        "pdf" => {
+            let pdf = graphviz::run_dot(dot_text, "pdf")?;
+            let layout = graphviz::run_dot(dot_text, "json")?;
+            // The outline is a nicety; a PDF we can't add one to is still
+            // worth writing.
+            pdf::add_bookmarks(skill_tree, &pdf, &layout).unwrap_or_else(|error| {
+                eprintln!("warning: leaving the PDF without bookmarks: {:#}", error);
+                pdf
+            })
+        }
+        format => graphviz::run_dot(dot_text, format)?,
+    };
+    write_output(path, image)?;
+
+    // PNGs can't carry links, so write an image map for the hrefs too
+    // (unless the PNG went to stdout, where there's nowhere to put it).
+    if format == "png" && !is_stdio(path) {
+        let map = graphviz::run_dot(dot_text, "cmapx")?;
+        let html_path = path.with_extension("html");
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        let snippet =
+            graphviz::image_map_snippet(&file_name, title.as_deref(), &String::from_utf8(map)?);
+        let snippet = match stamp {
+            Some(stamp) => stamp.html(&snippet),
+            None => snippet,
+        };
+        fs::write(&html_path, snippet)
+            .with_context(|| format!("writing to `{}`", html_path.display()))?;
+    }
+}
+
+#[throws(anyhow::Error)]
+fn write_preview_json(skill_tree: &SkillTree, job: &RenderJob<'_>, source: &Path, text: &str) {
+    let preview = preview::preview_json(skill_tree, source, text)?;
+    match &job.output_path {
+        Some(path) if !is_stdio(path) => write_output(path, preview.to_string())?,
+        _ => writeln!(io::stdout(), "{}", preview)?,
+    }
+}
+
+/// The `--emit` formats that run graphviz, as opposed to those of
+/// `renderers`.
+const GRAPHVIZ_FORMATS: &[&str] = &["svg", "png", "pdf", "ps", "html", "preview-json"];
+
+/// The formats that come straight from a renderer, without running
+/// graphviz: the built-in `dot`, `edges`, `adjacency` and `mermaid`, and
+/// `timeline` and `atom`.
+fn renderers() -> Renderers {
+    let mut renderers = Renderers::default();
+    renderers.register("timeline", timeline::Timeline);
+    renderers.register("atom", feed::Atom);
+    renderers
+}
+
+/// Writes the output of the renderer named by `--emit`.
+#[throws(anyhow::Error)]
+fn write_rendered(skill_tree: &SkillTree, job: &RenderJob<'_>, stamp: Option<&Stamp>) {
+    let opts = job.opts;
+    let path = match &job.output_path {
+        Some(path) => path,
+        None => anyhow::bail!("an output path is required when emitting `{}`", opts.emit()),
+    };
+    let text = renderers().render_to_string(opts.emit(), skill_tree)?;
+    let text = match (stamp, opts.emit()) {
+        (Some(stamp), "dot") => stamp.dot(&text),
+        (Some(stamp), "timeline") => stamp.svg(&text),
+        _ => text,
+    };
+    write_output(path, text)?;
+}
+
+#[throws(anyhow::Error)]
+fn write_dot_text(path: &Path, dot_text: &str, stamp: Option<&Stamp>) {
+    let text = match stamp {
+        Some(stamp) => stamp.dot(dot_text),
+        None => dot_text.to_string(),
+    };
+    write_output(path, text)?;
+}
+
+/// Whether `path` is `-`, which means stdin as an input and stdout as an
+/// output.
+fn is_stdio(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Reads the file at `path`, or stdin for `-`.
+#[throws(anyhow::Error)]
+fn read_input(path: &Path) -> String {
+    if is_stdio(path) {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("reading the skill tree from stdin")?;
+        text
+    } else {
+        fs::read_to_string(path).with_context(|| format!("reading `{}`", path.display()))?
+    }
+}
+
+/// Writes `contents` to the file at `path`, or to stdout for `-`.
+#[throws(anyhow::Error)]
+fn write_output(path: &Path, contents: impl AsRef<[u8]>) {
+    if is_stdio(path) {
+        io::stdout().write_all(contents.as_ref())?;
+    } else {
+        fs::write(path, contents).with_context(|| format!("writing to `{}`", path.display()))?;
+    }
+}
+
+#[throws(anyhow::Error)]
+fn migrate(opts: &MigrateOpts) {
+    let mut outdated = 0;
+    for path in &opts.skill_trees {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading `{}`", path.display()))?;
+        let migrated = skill_tree_core::migrate(&text)
+            .with_context(|| format!("migrating `{}`", path.display()))?;
+        if migrated == text {
+            continue;
+        }
+
+        outdated += 1;
+        if opts.check {
+            eprintln!("`{}` needs migrating", path.display());
+        } else {
+            fs::write(path, migrated).with_context(|| format!("writing `{}`", path.display()))?;
+            eprintln!(
+                "migrated `{}` to schema version {}",
+                path.display(),
+                skill_tree_core::SCHEMA_VERSION
+            );
+        }
+    }
+
+    if opts.check && outdated > 0 {
+        anyhow::bail!("{} file(s) need migrating", outdated);
+    }
+}
+
+#[throws(anyhow::Error)]
+fn import(opts: &ImportOpts) {
+    let groups = match &opts.source {
+        ImportSource::GithubProject { url } => import::github_project::import(url)?,
+        ImportSource::Cargo { manifest_path } => import::cargo::import(manifest_path.as_deref())?,
+        ImportSource::Markdown { file, repo } => {
+            let text = fs::read_to_string(file)
+                .with_context(|| format!("reading `{}`", file.display()))?;
+            import::markdown::import(&text, repo.as_deref())
+        }
+    };
+    let text = import::to_toml(&groups);
+    match &opts.output {
+        Some(path) => {
+            fs::write(path, text).with_context(|| format!("writing `{}`", path.display()))?
+        }
+        None => write!(io::stdout(), "{}", text)?,
+    }
+}
+
+#[throws(anyhow::Error)]
+fn burndown(opts: &BurndownOpts) {
+    let path = &opts.skill_tree;
+    let tree = SkillTree::load(path)
+        .with_context(|| format!("loading skill tree from `{}`", path.display()))?;
+    tree.validate()?;
+    let series = burndown::burndown(&tree)?;
+    let chart = match &opts.emit[..] {
+        "svg" => burndown::svg(&tree, &series),
+        _ => burndown::csv(&series),
+    };
+    write_output(opts.output.as_deref().unwrap_or(Path::new("-")), chart)?;
+}
+
+#[throws(anyhow::Error)]
+fn badge(opts: &BadgeOpts) {
+    let path = &opts.skill_tree;
+    let tree = SkillTree::load(path)
+        .with_context(|| format!("loading skill tree from `{}`", path.display()))?;
+    tree.validate()?;
+    let svg = badge::progress_badge(&tree, opts.group.as_deref())?;
+    write_output(opts.output.as_deref().unwrap_or(Path::new("-")), svg)?;
+}
+
+#[throws(anyhow::Error)]
+fn embed(opts: &EmbedOpts) {
+    let mut outdated = 0;
+    for path in &opts.markdown {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading `{}`", path.display()))?;
+        if embed::regions(&text)
+            .with_context(|| format!("reading `{}`", path.display()))?
+            .is_empty()
+        {
+            anyhow::bail!("`{}` has no `{} -->` marker", path.display(), embed::BEGIN);
+        }
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let updated = embed::update(&text, |region| {
+            let tree_path = match (region.options.get("tree"), &opts.tree) {
+                (Some(tree), _) => dir.join(tree),
+                (None, Some(tree)) => tree.clone(),
+                (None, None) => {
+                    anyhow::bail!("a marker has no `tree=...`, and there is no `--tree`")
+                }
+            };
+            let tree = SkillTree::load(&tree_path)
+                .with_context(|| format!("loading skill tree from `{}`", tree_path.display()))?;
+            tree.validate()?;
+            embed::section(&tree, region.options.get("image").copied())
+        })
+        .with_context(|| format!("updating `{}`", path.display()))?;
+        if updated == text {
+            continue;
+        }
+
+        outdated += 1;
+        if opts.check {
+            eprintln!("`{}` is out of date", path.display());
+        } else {
+            fs::write(path, updated).with_context(|| format!("writing `{}`", path.display()))?;
+            eprintln!("updated `{}`", path.display());
+        }
+    }
+
+    if opts.check && outdated > 0 {
+        anyhow::bail!("{} file(s) are out of date", outdated);
+    }
+}
+
+#[throws(anyhow::Error)]
+fn sync(opts: &SyncOpts) {
+    let path = &opts.skill_tree;
+    let tree = SkillTree::load(path)
+        .with_context(|| format!("loading skill tree from `{}`", path.display()))?;
+    tree.validate()?;
+    let github = sync::GitHub::new()?;
+    if opts.push && !opts.dry_run && !github.has_token() {
+        anyhow::bail!(
+            "set {} to a token that can edit the issues",
+            config::get()?.github_token_env()
+        );
+    }
+    let changes = sync::plan(&tree, |issue| github.labels(issue))?;
+    for change in &changes {
+        match (opts.push, opts.dry_run) {
+            (false, _) => println!("{}", change),
+            (true, true) => println!("would update {}", change),
+            (true, false) => {
+                github.apply(change)?;
+                println!("updated {}", change);
+            }
+        }
+    }
+    if !opts.push && !changes.is_empty() {
+        anyhow::bail!(
+            "{} issue(s) are out of date; `--push` updates them",
+            changes.len()
+        );
+    }
+}
+
+#[throws(anyhow::Error)]
+fn notify(opts: &NotifyOpts) {
+    let load = |path: &Path| {
+        SkillTree::load(path)
+            .with_context(|| format!("loading skill tree from `{}`", path.display()))
+    };
+    let tree = load(&opts.skill_tree)?;
+    tree.validate()?;
+    let baseline = load(&opts.baseline)?;
+    let summary = notify::summarize(&baseline, &tree);
+    let service = match (&opts.service, &opts.webhook) {
+        (Some(name), _) => notify::Service::named(name).unwrap(),
+        (None, Some(url)) => notify::Service::guess(url),
+        (None, None) => notify::Service::Slack,
+    };
+    let text = notify::message(&tree, &summary, service);
+    match &opts.webhook {
+        Some(url) => notify::post(url, service, &text)?,
+        None => println!("{}", text),
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,185 @@
+//! `skill-tree notify`: what changed between two revisions of a tree
+//! (groups completed, groups newly blocked, groups added and removed, and
+//! the progress overall), as a message posted to a chat webhook, for
+//! progress updates from CI.
+
+use anyhow::Context;
+use fehler::throws;
+use serde_json::{json, Value};
+use skill_tree_core::{Group, SkillTree, Status};
+
+/// What changed from one revision of a tree to the next.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Summary<'t> {
+    /// Labels of the groups that are complete now but weren't before.
+    pub completed: Vec<&'t str>,
+
+    /// Labels of the groups that are `Blocked` now but weren't before.
+    pub blocked: Vec<&'t str>,
+
+    /// Labels of the groups that are new.
+    pub added: Vec<&'t str>,
+
+    /// Labels of the groups that are gone.
+    pub removed: Vec<&'t str>,
+
+    /// Items done, and items, before and after.
+    pub before: (usize, usize),
+    pub after: (usize, usize),
+}
+
+/// Compares `old` and `new`. Groups are matched by name, or by an alias
+/// in `new` for groups that were renamed.
+pub fn summarize<'t>(old: &'t SkillTree, new: &'t SkillTree) -> Summary<'t> {
+    let mut summary = Summary {
+        completed: vec![],
+        blocked: vec![],
+        added: vec![],
+        removed: vec![],
+        before: old.progress(old.groups()),
+        after: new.progress(new.groups()),
+    };
+    for group in new.groups() {
+        let label = label(group);
+        let before = std::iter::once(&group.name)
+            .chain(group.aliases())
+            .find_map(|name| old.group_named(name));
+        let before = match before {
+            Some(before) => before,
+            None => {
+                summary.added.push(label);
+                if is_complete(new, group) {
+                    summary.completed.push(label);
+                }
+                continue;
+            }
+        };
+        if is_complete(new, group) && !is_complete(old, before) {
+            summary.completed.push(label);
+        }
+        if is_blocked(group) && !is_blocked(before) {
+            summary.blocked.push(label);
+        }
+    }
+    for group in old.groups() {
+        if new.group_named(&group.name).is_none() {
+            summary.removed.push(label(group));
+        }
+    }
+    summary
+}
+
+fn label(group: &Group) -> &str {
+    group.label.as_ref().unwrap_or(&group.name)
+}
+
+/// A group is complete if its status is done, or if it has items and all
+/// of them are.
+fn is_complete(tree: &SkillTree, group: &Group) -> bool {
+    match &group.status {
+        Some(status) => tree.is_done(status.name()),
+        None => {
+            let (done, total) = group.progress(tree);
+            total > 0 && done == total
+        }
+    }
+}
+
+fn is_blocked(group: &Group) -> bool {
+    group.status == Some(Status::Blocked)
+}
+
+/// The chat services we know how to post to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Service {
+    Slack,
+    Discord,
+    Matrix,
+}
+
+impl Service {
+    pub const NAMES: &'static [&'static str] = &["slack", "discord", "matrix"];
+
+    pub fn named(name: &str) -> Option<Service> {
+        match name {
+            "slack" => Some(Service::Slack),
+            "discord" => Some(Service::Discord),
+            "matrix" => Some(Service::Matrix),
+            _ => None,
+        }
+    }
+
+    /// Guesses the service from a webhook URL: Discord's are on
+    /// `discord.com`, and Matrix bridges (like hookshot) usually have
+    /// `hookshot` or `matrix` in them. Anything else is treated like
+    /// Slack, whose payload most other services accept too.
+    pub fn guess(url: &str) -> Service {
+        if url.contains("discord.com/") || url.contains("discordapp.com/") {
+            Service::Discord
+        } else if url.contains("hookshot") || url.contains("matrix") {
+            Service::Matrix
+        } else {
+            Service::Slack
+        }
+    }
+
+    /// `text` in bold.
+    fn bold(self, text: &str) -> String {
+        match self {
+            Service::Slack => format!("*{}*", text),
+            Service::Discord | Service::Matrix => format!("**{}**", text),
+        }
+    }
+
+    /// The JSON to post to a webhook of this service.
+    pub fn payload(self, text: &str) -> Value {
+        match self {
+            Service::Slack => json!({ "text": text }),
+            Service::Discord => json!({ "content": text }),
+            Service::Matrix => json!({ "text": text, "format": "markdown" }),
+        }
+    }
+}
+
+/// `summary` of `tree` as a chat message for `service`.
+pub fn message(tree: &SkillTree, summary: &Summary<'_>, service: Service) -> String {
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+    let progress = |(done, total): (usize, usize)| match total {
+        0 => "0/0".to_string(),
+        _ => format!("{}/{} ({}%)", done, total, done * 100 / total),
+    };
+    let mut lines = vec![format!(
+        "{}: {} items done, from {}",
+        service.bold(&title),
+        progress(summary.after),
+        progress(summary.before)
+    )];
+    for (heading, labels) in [
+        ("Completed", &summary.completed),
+        ("Newly blocked", &summary.blocked),
+        ("Added", &summary.added),
+        ("Removed", &summary.removed),
+    ] {
+        if !labels.is_empty() {
+            lines.push(format!("{}: {}", service.bold(heading), labels.join(", ")));
+        }
+    }
+    if lines.len() == 1 {
+        lines.push("No groups changed.".to_string());
+    }
+    lines.join("\n")
+}
+
+/// Posts `text` to the webhook at `url`.
+#[throws(anyhow::Error)]
+pub fn post(url: &str, service: Service, text: &str) {
+    ureq::post(url)
+        .set("User-Agent", "skill-tree")
+        .send_json(service.payload(text))
+        .context("posting to the webhook")?;
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,101 @@
+use super::{message, summarize, Service, Summary};
+use serde_json::json;
+use skill_tree_core::SkillTree;
+
+const OLD: &str = r#"
+[[group]]
+name = "lexer"
+items = [{ label = "tokens", status = "Complete" }, { label = "spans" }]
+
+[[group]]
+name = "parser"
+status = "Assigned"
+items = []
+
+[[group]]
+name = "typeck"
+items = []
+
+[[group]]
+name = "docs"
+items = []
+"#;
+
+const NEW: &str = r#"
+[doc]
+title = "Compiler"
+
+[[group]]
+name = "lexer"
+label = "Lexer"
+items = [{ label = "tokens", status = "Complete" }, { label = "spans", status = "Complete" }]
+
+[[group]]
+name = "parser"
+status = "Blocked"
+items = []
+
+[[group]]
+name = "types"
+aliases = ["typeck"]
+status = "Blocked"
+items = []
+
+[[group]]
+name = "codegen"
+items = []
+"#;
+
+#[test]
+fn summarizes_changes() {
+    let old = SkillTree::parse(OLD).unwrap();
+    let new = SkillTree::parse(NEW).unwrap();
+    let summary = summarize(&old, &new);
+    assert_eq!(
+        summary,
+        Summary {
+            completed: vec!["Lexer"],
+            blocked: vec!["parser", "types"],
+            added: vec!["codegen"],
+            removed: vec!["docs"],
+            before: (1, 2),
+            after: (2, 2),
+        }
+    );
+    assert_eq!(
+        message(&new, &summary, Service::Slack),
+        "*Compiler*: 2/2 (100%) items done, from 1/2 (50%)\n\
+         *Completed*: Lexer\n\
+         *Newly blocked*: parser, types\n\
+         *Added*: codegen\n\
+         *Removed*: docs"
+    );
+}
+
+#[test]
+fn unchanged() {
+    let old = SkillTree::parse(OLD).unwrap();
+    let summary = summarize(&old, &old);
+    assert_eq!(
+        message(&old, &summary, Service::Discord),
+        "**Skill tree**: 1/2 (50%) items done, from 1/2 (50%)\nNo groups changed."
+    );
+}
+
+#[test]
+fn services() {
+    assert_eq!(
+        Service::guess("https://discord.com/api/webhooks/1/abc"),
+        Service::Discord
+    );
+    assert_eq!(
+        Service::guess("https://matrix.example.org/hookshot/webhook/abc"),
+        Service::Matrix
+    );
+    assert_eq!(
+        Service::guess("https://hooks.slack.com/services/T/B/X"),
+        Service::Slack
+    );
+    assert_eq!(Service::Discord.payload("hi"), json!({ "content": "hi" }));
+    assert_eq!(Service::Slack.payload("hi"), json!({ "text": "hi" }));
+}
@@ -0,0 +1,118 @@
+//! `render --set`, `--rankdir` and `--columns`: changes to the tree's
+//! settings for one render, without editing the file.
+
+use fehler::throws;
+use skill_tree_core::{Rankdir, SkillTree};
+use std::convert::TryFrom;
+use toml::Value;
+
+/// Parses the argument of `--rankdir`, accepting the same values as
+/// `rankdir` in `[graphviz]`.
+pub fn parse_rankdir(value: &str) -> Result<Rankdir, String> {
+    Rankdir::try_from(value.to_string())
+}
+
+/// Applies `--set key=value` assignments to the TOML in `text` and
+/// returns the result. Keys are dotted paths through tables (such as
+/// `doc.defaults.status`); values are read as TOML if they parse as a
+/// value (`true`, `3.5`, `"text"`) and as a plain string otherwise.
+#[throws(anyhow::Error)]
+pub fn set(text: &str, assignments: &[String]) -> String {
+    let mut root: Value = toml::from_str(text)?;
+    for assignment in assignments {
+        let (key, value) = match assignment.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => anyhow::bail!("`--set {}` should have the form `key=value`", assignment),
+        };
+        let path: Vec<&str> = key.split('.').collect();
+        if path.iter().any(|part| part.is_empty()) {
+            anyhow::bail!("`--set {}` has an empty key", assignment);
+        }
+        let (last, parents) = path.split_last().unwrap();
+
+        let mut table = root.as_table_mut().unwrap();
+        for (depth, part) in parents.iter().enumerate() {
+            let entry = table
+                .entry(part.to_string())
+                .or_insert_with(|| Value::Table(Default::default()));
+            table = match entry.as_table_mut() {
+                Some(table) => table,
+                None => anyhow::bail!(
+                    "can't set `{}`: `{}` is not a table",
+                    key,
+                    path[..=depth].join(".")
+                ),
+            };
+        }
+        table.insert(last.to_string(), parse_value(value));
+    }
+    root.as_table()
+        .unwrap()
+        .iter()
+        .map(|(key, value)| format!("{} = {}\n", quote(key), inline(value)))
+        .collect::<String>()
+}
+
+/// Writes `value` as an inline TOML value. (`toml::to_string` can't
+/// write arrays that mix tables with other values, like `doc.columns`.)
+fn inline(value: &Value) -> String {
+    match value {
+        Value::String(s) => quote(s),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => format!("{:?}", f),
+        Value::Boolean(b) => b.to_string(),
+        Value::Datetime(d) => d.to_string(),
+        Value::Array(values) => format!(
+            "[{}]",
+            values.iter().map(inline).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Table(table) => format!(
+            "{{ {} }}",
+            table
+                .iter()
+                .map(|(key, value)| format!("{} = {}", quote(key), inline(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// JSON string escapes are a subset of TOML's.
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap()
+}
+
+fn parse_value(value: &str) -> Value {
+    match toml::from_str::<toml::value::Table>(&format!("value = {}", value)) {
+        Ok(mut table) => table.remove("value").unwrap(),
+        Err(_) => Value::String(value.to_string()),
+    }
+}
+
+/// Keeps only the columns named in `--columns`, in that order.
+#[throws(anyhow::Error)]
+pub fn select_columns(tree: &mut SkillTree, names: &str) {
+    let columns = tree.columns();
+    let mut selected = vec![];
+    for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match columns.iter().find(|column| column.name() == name) {
+            Some(column) => selected.push(column.clone()),
+            None if columns.is_empty() => {
+                anyhow::bail!("`--columns` names `{}`, but the tree has no columns", name)
+            }
+            None => anyhow::bail!(
+                "`--columns` names `{}`, but the columns are {}",
+                name,
+                columns
+                    .iter()
+                    .map(|column| format!("`{}`", column.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+    tree.doc.get_or_insert_with(Default::default).columns = Some(selected);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,89 @@
+use super::{parse_rankdir, select_columns, set};
+use skill_tree_core::{Rankdir, SkillTree};
+
+const TREE: &str = r#"
+[doc]
+columns = ["status", "assignee", "area"]
+
+[doc.defaults]
+status = "tbd"
+
+[[group]]
+name = "parser"
+items = [{ label = "Expressions", status = "done", assignee = "ana" }]
+"#;
+
+fn assignments(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn sets_nested_keys() {
+    let text = set(
+        TREE,
+        &assignments(&[
+            "doc.defaults.status=unassigned",
+            "doc.title = Roadmap 2025",
+            "graphviz.node_fontsize=12",
+        ]),
+    )
+    .unwrap();
+    let tree = SkillTree::parse(&text).unwrap();
+    let doc = tree.doc.as_ref().unwrap();
+    assert_eq!(
+        doc.defaults.as_ref().unwrap().columns["status"],
+        "unassigned"
+    );
+    assert_eq!(doc.title.as_deref(), Some("Roadmap 2025"));
+    assert_eq!(tree.graphviz.unwrap().node_fontsize, Some(12.0));
+}
+
+#[test]
+fn keeps_mixed_column_lists() {
+    let tree = r#"
+[doc]
+columns = [{ name = "state", type = "enum", values = ["a", "b"] }, "team"]
+"#;
+    let text = set(tree, &assignments(&["graphviz.rankdir=TB"])).unwrap();
+    let tree = SkillTree::parse(&text).unwrap();
+    let names: Vec<_> = tree.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["state", "team"]);
+    assert_eq!(tree.graphviz.unwrap().rankdir, Some(Rankdir::TopToBottom));
+}
+
+#[test]
+fn rejects_malformed_assignments() {
+    let error = set(TREE, &assignments(&["doc.title"])).unwrap_err();
+    assert!(error.to_string().contains("form `key=value`"), "{}", error);
+
+    let error = set(TREE, &assignments(&["doc.columns.status=x"])).unwrap_err();
+    assert!(
+        error.to_string().contains("`doc.columns` is not a table"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn selects_columns_in_order() {
+    let mut tree = SkillTree::parse(TREE).unwrap();
+    select_columns(&mut tree, "assignee,status").unwrap();
+    let names: Vec<_> = tree.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["assignee", "status"]);
+}
+
+#[test]
+fn rejects_unknown_columns() {
+    let mut tree = SkillTree::parse(TREE).unwrap();
+    let error = select_columns(&mut tree, "owner").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`--columns` names `owner`, but the columns are `status`, `assignee`, `area`"
+    );
+}
+
+#[test]
+fn parses_rankdir_aliases() {
+    assert_eq!(parse_rankdir("left-to-right"), Ok(Rankdir::LeftToRight));
+    assert!(parse_rankdir("diagonal").is_err());
+}
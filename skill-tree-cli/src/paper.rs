@@ -0,0 +1,54 @@
+//! `render --paper`: lays the drawing out for printing, either scaled to
+//! fit one sheet or, with `--tile`, at full size across several sheets.
+
+use skill_tree_core::{Margin, SkillTree};
+
+/// Paper sizes we know, for `--paper`.
+pub const PAPERS: &[&str] = &["a2", "a3", "a4", "a5", "letter", "legal", "tabloid"];
+
+/// Margin left around the drawing on each side, in inches.
+const MARGIN: f64 = 0.5;
+
+/// Width and height of `paper` in portrait orientation, in inches.
+fn dimensions(paper: &str) -> Option<(f64, f64)> {
+    Some(match paper {
+        "a2" => (16.54, 23.39),
+        "a3" => (11.69, 16.54),
+        "a4" => (8.27, 11.69),
+        "a5" => (5.83, 8.27),
+        "letter" => (8.5, 11.0),
+        "legal" => (8.5, 14.0),
+        "tabloid" => (11.0, 17.0),
+        _ => return None,
+    })
+}
+
+/// Sets the graphviz `size`, `page` and `margin` of `tree` for printing
+/// on `paper`. Without `tile` the drawing is scaled to fill one sheet;
+/// with it, the drawing keeps its size and graphviz splits it across
+/// sheets (which only PostScript output supports).
+pub fn apply(tree: &mut SkillTree, paper: &str, landscape: bool, tile: bool) {
+    let (mut width, mut height) = dimensions(paper).expect("unknown paper size");
+    if landscape {
+        std::mem::swap(&mut width, &mut height);
+    }
+
+    let graphviz = tree.graphviz.get_or_insert_with(Default::default);
+    graphviz.margin = Some(Margin::uniform(MARGIN));
+    if tile {
+        graphviz.page = Some(format!("{:.2},{:.2}", width, height));
+        graphviz.size = None;
+    } else {
+        // The trailing `!` scales small drawings up as well as large
+        // ones down.
+        graphviz.size = Some(format!(
+            "{:.2},{:.2}!",
+            width - 2.0 * MARGIN,
+            height - 2.0 * MARGIN
+        ));
+        graphviz.page = None;
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,33 @@
+use super::{apply, dimensions, PAPERS};
+use skill_tree_core::{Margin, Rankdir, SkillTree};
+
+fn tree() -> SkillTree {
+    SkillTree::parse("[graphviz]\nrankdir = \"TD\"\nsize = \"1,1\"\n").unwrap()
+}
+
+#[test]
+fn knows_every_paper() {
+    for paper in PAPERS {
+        assert!(dimensions(paper).is_some(), "{}", paper);
+    }
+}
+
+#[test]
+fn fits_on_one_sheet() {
+    let mut tree = tree();
+    apply(&mut tree, "a3", true, false);
+    let graphviz = tree.graphviz.unwrap();
+    assert_eq!(graphviz.size.as_deref(), Some("15.54,10.69!"));
+    assert_eq!(graphviz.page, None);
+    assert_eq!(graphviz.margin, Some(Margin::uniform(0.5)));
+    assert_eq!(graphviz.rankdir, Some(Rankdir::TopToBottom));
+}
+
+#[test]
+fn tiles_across_sheets() {
+    let mut tree = tree();
+    apply(&mut tree, "letter", false, true);
+    let graphviz = tree.graphviz.unwrap();
+    assert_eq!(graphviz.page.as_deref(), Some("8.50,11.00"));
+    assert_eq!(graphviz.size, None);
+}
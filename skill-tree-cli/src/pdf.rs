@@ -0,0 +1,389 @@
+//! Bookmarks for `render --emit pdf`.
+//!
+//! graphviz's PDFs have no outline, which makes a big printed tree hard to
+//! get around. We ask graphviz for the layout as JSON as well, find where
+//! each cluster and goal landed, and append an outline pointing there to
+//! the PDF as an incremental update, so what graphviz wrote is untouched.
+
+use anyhow::Context;
+use fehler::throws;
+use serde_json::Value;
+use skill_tree_core::SkillTree;
+
+/// graphviz pads the drawing by this many points on every side.
+const PAD: f64 = 4.0;
+
+/// A place to jump to, in graphviz's coordinates (points, origin at the
+/// bottom left): the top left corner of a cluster or goal, and its right
+/// edge, which becomes the top when the drawing is turned on its side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bookmark {
+    pub title: String,
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+}
+
+/// How graphviz laid the drawing out, as far as finding things on the
+/// PDF's pages goes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drawing {
+    /// The drawing's bounding box, in points.
+    pub bb: (f64, f64, f64, f64),
+    /// The graph's margins, in points.
+    pub margin: (f64, f64),
+    /// Whether the drawing was turned a quarter turn counterclockwise
+    /// (graphviz's `rotate = 90` or `landscape`).
+    pub rotated: bool,
+}
+
+/// Adds an outline with a bookmark for each cluster and each goal of
+/// `tree` to `pdf`, which graphviz rendered along with `layout` (its
+/// `-Tjson` output). Returns `pdf` unchanged if there is nothing to
+/// bookmark.
+#[throws(anyhow::Error)]
+pub fn add_bookmarks(tree: &SkillTree, pdf: &[u8], layout: &[u8]) -> Vec<u8> {
+    let layout: Value = serde_json::from_slice(layout).context("reading graphviz's layout")?;
+    let bookmarks = bookmarks(tree, &layout);
+    if bookmarks.is_empty() {
+        return pdf.to_vec();
+    }
+    let bb = layout["bb"]
+        .as_str()
+        .and_then(parse_box)
+        .context("graphviz's layout has no bounding box")?;
+    let margin = tree
+        .graphviz
+        .as_ref()
+        .and_then(|graphviz| graphviz.margin)
+        .map_or((0.0, 0.0), |margin| (margin.x * 72.0, margin.y * 72.0));
+    let attribute = |name: &str| layout[name].as_str().unwrap_or("").to_lowercase();
+    let rotated = attribute("rotate") == "90"
+        || matches!(&attribute("landscape")[..], "true" | "yes" | "1")
+        || attribute("orientation").starts_with('l');
+    let drawing = Drawing {
+        bb,
+        margin,
+        rotated,
+    };
+    add_outline(pdf, &drawing, &bookmarks)?
+}
+
+/// The clusters of `tree`, then its goals, where `layout` put them.
+pub fn bookmarks(tree: &SkillTree, layout: &Value) -> Vec<Bookmark> {
+    let objects = layout["objects"].as_array().map_or(&[][..], |o| &o[..]);
+    let named = |name: &str| objects.iter().find(|object| object["name"] == name);
+
+    let clusters = tree.cluster.iter().flatten().filter_map(|cluster| {
+        let object = named(&format!("cluster_{}", cluster.name))?;
+        let (left, _, right, top) = parse_box(object["bb"].as_str()?)?;
+        Some(Bookmark {
+            title: cluster.label.clone(),
+            left,
+            top,
+            right,
+        })
+    });
+    let goals = tree
+        .groups()
+        .filter(|group| tree.is_goal(group))
+        .filter_map(|group| {
+            let object = named(&group.name)?;
+            let (x, y) = parse_point(object["pos"].as_str()?)?;
+            let width: f64 = object["width"].as_str()?.parse().ok()?;
+            let height: f64 = object["height"].as_str()?.parse().ok()?;
+            Some(Bookmark {
+                title: group.label.clone().unwrap_or_else(|| group.name.clone()),
+                left: x - width * 36.0,
+                top: y + height * 36.0,
+                right: x + width * 36.0,
+            })
+        });
+    clusters.chain(goals).collect()
+}
+
+/// Appends an outline of `bookmarks` to `pdf`, which graphviz drew as
+/// `drawing` describes. A drawing on one page is scaled to fit it; one
+/// spread over several (with graphviz's `page`) keeps its size and is
+/// tiled across them from the bottom left, a row at a time, so each
+/// bookmark points at the page holding its corner.
+#[throws(anyhow::Error)]
+pub fn add_outline(pdf: &[u8], drawing: &Drawing, bookmarks: &[Bookmark]) -> Vec<u8> {
+    let startxref = rfind(pdf, b"startxref").context("the PDF has no `startxref`")?;
+    let prev: usize = text(&pdf[startxref + "startxref".len()..])
+        .split_whitespace()
+        .next()
+        .and_then(|offset| offset.parse().ok())
+        .context("the PDF's `startxref` has no offset")?;
+    let trailer = match rfind(&pdf[..startxref], b"trailer") {
+        Some(trailer) => text(&pdf[trailer..startxref]),
+        None => {
+            anyhow::bail!("the PDF has a cross-reference stream, which we can't add bookmarks to")
+        }
+    };
+    if bookmarks.is_empty() {
+        return pdf.to_vec();
+    }
+    let size = reference(&trailer, "/Size").context("the PDF's trailer has no `/Size`")?;
+    let root = reference(&trailer, "/Root").context("the PDF's trailer has no `/Root`")?;
+
+    let catalog = object(pdf, root).context("the PDF has no catalog")?;
+    if catalog.contains("/Outlines") {
+        anyhow::bail!("the PDF already has an outline");
+    }
+    let pages = reference(&catalog, "/Pages").context("the PDF's catalog has no `/Pages`")?;
+    let pages = leaf_pages(pdf, pages, None)?;
+    let media_box = match pages.first() {
+        Some(&(_, media_box)) => media_box,
+        None => anyhow::bail!("the PDF has no pages"),
+    };
+
+    // Work in the padded drawing, turned the way it sits on the page.
+    let (bx0, by0, bx1, by1) = drawing.bb;
+    let (mut width, mut height) = (bx1 - bx0 + 2.0 * PAD, by1 - by0 + 2.0 * PAD);
+    if drawing.rotated {
+        std::mem::swap(&mut width, &mut height);
+    }
+    let corner = |bookmark: &Bookmark| {
+        if drawing.rotated {
+            (by1 - bookmark.top + PAD, PAD + bookmark.right - bx0)
+        } else {
+            (PAD + bookmark.left - bx0, PAD + bookmark.top - by0)
+        }
+    };
+
+    // graphviz scales a drawing on one page to fit inside the margins,
+    // and cuts one on several into pieces the size of a page less its
+    // margins.
+    let (mx, my) = drawing.margin;
+    let (page_width, page_height) = (
+        media_box.2 - media_box.0 - 2.0 * mx,
+        media_box.3 - media_box.1 - 2.0 * my,
+    );
+    let scale = match width {
+        width if pages.len() == 1 && width > 0.0 => page_width / width,
+        _ => 1.0,
+    };
+    let columns = match pages.len() {
+        1 => 1,
+        _ if page_width > 0.0 && page_height > 0.0 => (width / page_width).ceil().max(1.0) as usize,
+        _ => anyhow::bail!("the PDF's pages are smaller than their margins"),
+    };
+    let to_page = |bookmark: &Bookmark| {
+        let (x, y) = corner(bookmark);
+        let (x, y) = (x * scale, y * scale);
+        let (column, row) = match pages.len() {
+            1 => (0, 0),
+            _ => (
+                ((x / page_width) as usize).min(columns - 1),
+                (y / page_height).max(0.0) as usize,
+            ),
+        };
+        let (page, media_box) = *pages
+            .get(row * columns + column)
+            .with_context(|| format!("the PDF has no page for `{}`", bookmark.title))?;
+        Ok::<_, anyhow::Error>((
+            page,
+            media_box.0 + mx + x - column as f64 * page_width,
+            media_box.1 + my + y - row as f64 * page_height,
+        ))
+    };
+
+    let outlines = size;
+    let first = outlines + 1;
+    let last = first + bookmarks.len() - 1;
+    let mut objects = vec![(
+        root,
+        format!(
+            "{} /Outlines {} 0 R /PageMode /UseOutlines >>",
+            catalog.trim_end().trim_end_matches(">>").trim_end(),
+            outlines
+        ),
+    )];
+    objects.push((
+        outlines,
+        format!(
+            "<< /Type /Outlines /First {} 0 R /Last {} 0 R /Count {} >>",
+            first,
+            last,
+            bookmarks.len()
+        ),
+    ));
+    for (index, bookmark) in bookmarks.iter().enumerate() {
+        let number = first + index;
+        let (page, left, top) = to_page(bookmark)?;
+        let mut entry = format!(
+            "<< /Title {} /Parent {} 0 R /Dest [{} 0 R /XYZ {:.2} {:.2} null]",
+            text_string(&bookmark.title),
+            outlines,
+            page,
+            left,
+            top
+        );
+        if number > first {
+            entry.push_str(&format!(" /Prev {} 0 R", number - 1));
+        }
+        if number < last {
+            entry.push_str(&format!(" /Next {} 0 R", number + 1));
+        }
+        entry.push_str(" >>");
+        objects.push((number, entry));
+    }
+
+    let mut output = pdf.to_vec();
+    if !output.ends_with(b"\n") {
+        output.push(b'\n');
+    }
+    let mut offsets = vec![];
+    for (number, body) in &objects {
+        offsets.push((*number, output.len()));
+        output.extend(format!("{} 0 obj\n{}\nendobj\n", number, body).into_bytes());
+    }
+
+    let xref = output.len();
+    let mut table = String::from("xref\n");
+    table.push_str(&format!("{} 1\n{:010} 00000 n \n", root, offsets[0].1));
+    table.push_str(&format!("{} {}\n", outlines, offsets.len() - 1));
+    for (_, offset) in &offsets[1..] {
+        table.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    let info = reference(&trailer, "/Info")
+        .map(|info| format!(" /Info {} 0 R", info))
+        .unwrap_or_default();
+    table.push_str(&format!(
+        "trailer\n<< /Size {} /Root {} 0 R{} /Prev {} >>\nstartxref\n{}\n%%EOF\n",
+        last + 1,
+        root,
+        info,
+        prev,
+        xref
+    ));
+    output.extend(table.into_bytes());
+    output
+}
+
+/// The pages under the page tree node `number` of `pdf`, in order, with
+/// their media boxes (which pages may inherit from the node above).
+#[throws(anyhow::Error)]
+fn leaf_pages(
+    pdf: &[u8],
+    number: usize,
+    inherited: Option<(f64, f64, f64, f64)>,
+) -> Vec<(usize, (f64, f64, f64, f64))> {
+    let node = object(pdf, number).with_context(|| format!("the PDF has no object {}", number))?;
+    let media_box = media_box(&node).or(inherited);
+    match node.find("/Kids") {
+        Some(start) => {
+            let kids = &node[start + "/Kids".len()..];
+            let kids = kids.split(']').next().unwrap_or("");
+            let mut pages = vec![];
+            for kid in references(kids) {
+                pages.extend(leaf_pages(pdf, kid, media_box)?);
+            }
+            pages
+        }
+        None => vec![(
+            number,
+            media_box.context("the PDF's page has no `/MediaBox`")?,
+        )],
+    }
+}
+
+/// The `/MediaBox` of a page or page tree node.
+fn media_box(dict: &str) -> Option<(f64, f64, f64, f64)> {
+    let start = dict.find("/MediaBox")? + "/MediaBox".len();
+    let numbers: Vec<f64> = dict[start..]
+        .trim_start()
+        .strip_prefix('[')?
+        .split(']')
+        .next()?
+        .split_whitespace()
+        .filter_map(|n| n.parse().ok())
+        .collect();
+    match numbers[..] {
+        [x0, y0, x1, y1] => Some((x0, y0, x1, y1)),
+        _ => None,
+    }
+}
+
+/// The object numbers of the `n 0 R` references in `text`.
+fn references(text: &str) -> Vec<usize> {
+    let tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == '[')
+        .filter(|token| !token.is_empty())
+        .collect();
+    tokens
+        .windows(3)
+        .filter(|window| window[2] == "R")
+        .filter_map(|window| window[0].parse().ok())
+        .collect()
+}
+
+/// The dictionary of object `number` of `pdf`, as text.
+fn object(pdf: &[u8], number: usize) -> Option<String> {
+    let header = format!("{} 0 obj", number);
+    let mut from = 0;
+    let start = loop {
+        let at = from + find(&pdf[from..], header.as_bytes())?;
+        if at == 0 || pdf[at - 1].is_ascii_whitespace() {
+            break at + header.len();
+        }
+        from = at + 1;
+    };
+    let end = start + find(&pdf[start..], b"endobj")?;
+    Some(text(&pdf[start..end]).trim().to_string())
+}
+
+/// The number that follows `key` in `dict`, such as the object number of
+/// a reference.
+fn reference(dict: &str, key: &str) -> Option<usize> {
+    let start = dict.find(key)? + key.len();
+    dict[start..].split_whitespace().next()?.parse().ok()
+}
+
+/// A PDF text string holding `text`, as UTF-16 in hex.
+fn text_string(text: &str) -> String {
+    let mut string = String::from("<FEFF");
+    for unit in text.encode_utf16() {
+        string.push_str(&format!("{:04X}", unit));
+    }
+    string.push('>');
+    string
+}
+
+/// A graphviz `x0,y0,x1,y1` box.
+fn parse_box(text: &str) -> Option<(f64, f64, f64, f64)> {
+    let numbers: Vec<f64> = text
+        .split(',')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    match numbers[..] {
+        [x0, y0, x1, y1] => Some((x0, y0, x1, y1)),
+        _ => None,
+    }
+}
+
+/// A graphviz `x,y` point.
+fn parse_point(text: &str) -> Option<(f64, f64)> {
+    let (x, y) = text.split_once(',')?;
+    Some((x.parse().ok()?, y.parse().ok()?))
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod test;
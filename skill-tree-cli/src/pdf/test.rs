@@ -0,0 +1,196 @@
+use super::{add_bookmarks, add_outline, bookmarks, Bookmark, Drawing};
+use serde_json::json;
+use skill_tree_core::SkillTree;
+
+/// A PDF laid out like cairo's, with `pages` pages of `width` by
+/// `height` points.
+fn pdf_with_pages(pages: usize, width: u32, height: u32) -> Vec<u8> {
+    let kids: Vec<String> = (0..pages).map(|page| format!("{} 0 R", page + 5)).collect();
+    let mut objects = vec![
+        format!(
+            "<< /Type /Pages /Kids [ {} ] /Count {} >>",
+            kids.join(" "),
+            pages
+        ),
+        "<< /Type /Catalog\n   /Pages 1 0 R\n>>".to_string(),
+        "<< /Producer (cairo) >>".to_string(),
+        "<< >>".to_string(),
+    ];
+    for _ in 0..pages {
+        objects.push(format!(
+            "<< /Type /Page /Parent 1 0 R /MediaBox [ 0 0 {} {} ] >>",
+            width, height
+        ));
+    }
+    let mut pdf = b"%PDF-1.5\n".to_vec();
+    let mut offsets = vec![];
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).into_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 2 0 R /Info 3 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .into_bytes(),
+    );
+    pdf
+}
+
+/// A one-page PDF with a 208x108 page.
+fn pdf() -> Vec<u8> {
+    pdf_with_pages(1, 208, 108)
+}
+
+const TREE: &str = r#"
+[doc]
+goals = ["parser"]
+
+[[cluster]]
+name = "frontend"
+label = "Front end"
+
+[[group]]
+name = "parser"
+label = "Parsér"
+cluster = "frontend"
+items = []
+"#;
+
+fn layout() -> serde_json::Value {
+    json!({
+        "bb": "0,0,200,100",
+        "objects": [
+            { "name": "cluster_frontend", "bb": "8,8,192,92" },
+            { "name": "parser", "pos": "100,50", "width": "1", "height": "0.5" },
+        ],
+    })
+}
+
+fn drawing(rotated: bool) -> Drawing {
+    Drawing {
+        bb: (0.0, 0.0, 200.0, 100.0),
+        margin: (0.0, 0.0),
+        rotated,
+    }
+}
+
+fn front_end_and_parser() -> Vec<Bookmark> {
+    vec![
+        Bookmark {
+            title: "Front end".to_string(),
+            left: 8.0,
+            top: 92.0,
+            right: 192.0,
+        },
+        Bookmark {
+            title: "Parsér".to_string(),
+            left: 64.0,
+            top: 68.0,
+            right: 136.0,
+        },
+    ]
+}
+
+/// The new objects `output` appended to `original`.
+fn update(original: &[u8], output: &[u8]) -> String {
+    assert!(output.starts_with(original));
+    String::from_utf8(output[original.len()..].to_vec()).unwrap()
+}
+
+#[test]
+fn bookmarks_clusters_then_goals() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert_eq!(bookmarks(&tree, &layout()), front_end_and_parser());
+}
+
+#[test]
+fn appends_an_outline() {
+    let original = pdf();
+    let output = add_outline(&original, &drawing(false), &front_end_and_parser()).unwrap();
+    let update = update(&original, &output);
+
+    assert!(update.contains("2 0 obj\n<< /Type /Catalog\n   /Pages 1 0 R /Outlines 6 0 R /PageMode /UseOutlines >>\nendobj"));
+    assert!(update.contains("6 0 obj\n<< /Type /Outlines /First 7 0 R /Last 8 0 R /Count 2 >>"));
+    assert!(update.contains(
+        "7 0 obj\n<< /Title <FEFF00460072006F006E007400200065006E0064> /Parent 6 0 R /Dest [5 0 R /XYZ 12.00 96.00 null] /Next 8 0 R >>"
+    ));
+    assert!(update.contains("/Title <FEFF005000610072007300E90072>"));
+    assert!(update.contains("/Prev 7 0 R >>"));
+    assert!(update.contains("/Size 9 /Root 2 0 R /Info 3 0 R /Prev "));
+
+    // Each entry of the new cross-reference table points at its object.
+    let xref = update.find("xref\n").unwrap();
+    let mut lines = update[xref..].lines().skip(1);
+    for (start, count) in [(2, 1), (6, 3)] {
+        assert_eq!(lines.next().unwrap(), format!("{} {}", start, count));
+        for number in start..start + count {
+            let offset: usize = lines.next().unwrap()[..10].parse().unwrap();
+            assert!(output[offset..].starts_with(format!("{} 0 obj", number).as_bytes()));
+        }
+    }
+}
+
+#[test]
+fn points_at_the_tile_holding_each_bookmark() {
+    // Four 208x108 pages, two across and two up, hold the 308x158
+    // padded drawing at full size: the front end starts on the top left
+    // page, the parser on the bottom right one.
+    let original = pdf_with_pages(4, 208, 108);
+    let drawing = Drawing {
+        bb: (0.0, 0.0, 300.0, 150.0),
+        ..drawing(false)
+    };
+    let bookmarks = [
+        Bookmark {
+            title: "Front end".to_string(),
+            left: 8.0,
+            top: 140.0,
+            right: 292.0,
+        },
+        Bookmark {
+            title: "Parsér".to_string(),
+            left: 250.0,
+            top: 60.0,
+            right: 280.0,
+        },
+    ];
+    let output = add_outline(&original, &drawing, &bookmarks).unwrap();
+    let update = update(&original, &output);
+
+    assert!(update.contains("/Dest [7 0 R /XYZ 12.00 36.00 null]"));
+    assert!(update.contains("/Dest [6 0 R /XYZ 46.00 64.00 null]"));
+}
+
+#[test]
+fn turns_bookmarks_with_the_drawing() {
+    // On its side, the 208x108 padded drawing fills a 108x208 page: what
+    // was its top is now its left, and its right is now its top.
+    let original = pdf_with_pages(1, 108, 208);
+    let output = add_outline(&original, &drawing(true), &front_end_and_parser()).unwrap();
+    let update = update(&original, &output);
+
+    assert!(update.contains("/Dest [5 0 R /XYZ 12.00 196.00 null]"));
+    assert!(update.contains("/Dest [5 0 R /XYZ 36.00 140.00 null]"));
+}
+
+#[test]
+fn leaves_trees_without_clusters_or_goals_alone() {
+    let tree = SkillTree::parse("[[group]]\nname = \"parser\"\nitems = []\n").unwrap();
+    let layout = serde_json::to_vec(&layout()).unwrap();
+    assert_eq!(add_bookmarks(&tree, &pdf(), &layout).unwrap(), pdf());
+}
+
+#[test]
+fn rejects_cross_reference_streams() {
+    let pdf = b"%PDF-1.5\n1 0 obj\n<< /Type /XRef >>\nendobj\nstartxref\n9\n%%EOF\n";
+    let error = add_outline(pdf, &drawing(false), &[]).unwrap_err();
+    assert!(error.to_string().contains("cross-reference stream"));
+}
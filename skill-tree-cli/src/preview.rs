@@ -0,0 +1,46 @@
+//! The `preview-json` output used by editor plugins.
+//!
+//! It is a single JSON object:
+//!
+//! ```json
+//! {
+//!   "svg": "<svg ...>",
+//!   "nodes": { "parser": { "file": "tree.toml", "line": 12 } }
+//! }
+//! ```
+//!
+//! `nodes` maps each group name (the `<title>` of its node in the SVG) to
+//! the 1-based line declaring it, so a preview can jump back to the source.
+//! Groups whose definition can't be found (e.g., ones that come from an
+//! include) are left out.
+
+use crate::graphviz;
+use fehler::throws;
+use serde_json::{json, Map, Value};
+use skill_tree_core::source::group_definition_line;
+use skill_tree_core::SkillTree;
+use std::path::Path;
+
+#[throws(anyhow::Error)]
+pub fn preview_json(tree: &SkillTree, path: &Path, text: &str) -> Value {
+    json!({
+        "svg": graphviz::render_svg(&tree.to_graphviz()?, tree.title().as_deref())?,
+        "nodes": node_locations(tree, path, text),
+    })
+}
+
+fn node_locations(tree: &SkillTree, path: &Path, text: &str) -> Value {
+    let mut nodes = Map::new();
+    for group in tree.groups() {
+        if let Some(line) = group_definition_line(text, &group.name) {
+            nodes.insert(
+                group.name.clone(),
+                json!({ "file": path.display().to_string(), "line": line + 1 }),
+            );
+        }
+    }
+    Value::Object(nodes)
+}
+
+#[cfg(test)]
+mod test;
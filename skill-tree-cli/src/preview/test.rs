@@ -0,0 +1,29 @@
+use super::node_locations;
+use serde_json::json;
+use skill_tree_core::SkillTree;
+use std::path::Path;
+
+#[test]
+fn maps_groups_to_lines() {
+    let text = r#"[[cluster]]
+name = "frontend"
+label = "Frontend"
+
+[[group]]
+name = "lexer"
+items = []
+
+[[group]]
+name = "parser"
+requires = ["lexer"]
+items = []
+"#;
+    let tree = SkillTree::parse(text).unwrap();
+    assert_eq!(
+        node_locations(&tree, Path::new("tree.toml"), text),
+        json!({
+            "lexer": { "file": "tree.toml", "line": 6 },
+            "parser": { "file": "tree.toml", "line": 10 },
+        })
+    );
+}
@@ -0,0 +1,163 @@
+//! `render --pull-requests`: items whose `href` is a GitHub pull request
+//! are marked `Complete` once it is merged.
+//!
+//! What GitHub said is cached in `pull-requests.json` in the cache
+//! directory (see `cache::dir`). A merged pull request stays merged, so
+//! only the others are asked about again; with `--offline`, nothing is
+//! asked and the cache is all there is.
+
+use anyhow::Context;
+use serde_json::Value;
+use skill_tree_core::provider::StatusProvider;
+use skill_tree_core::Status;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const API_URL: &str = "https://api.github.com";
+
+/// A `StatusProvider` for GitHub pull requests.
+pub struct PullRequests {
+    /// Where the cache is kept, if anywhere.
+    cache: Option<PathBuf>,
+
+    /// Whether each pull request (`owner/repo#number`) is merged.
+    merged: BTreeMap<String, bool>,
+    offline: bool,
+    token: Option<String>,
+
+    /// Where the API is; `API_URL` but in tests.
+    api_url: String,
+
+    /// What went wrong asking about pull requests, which leaves their
+    /// items' status as it was.
+    failures: Vec<anyhow::Error>,
+}
+
+impl PullRequests {
+    /// Starts from what is cached in `cache_dir`. The API is asked with
+    /// the token in `GITHUB_TOKEN` (or the variable named by
+    /// `github_token_env` in the user configuration), if it is set.
+    pub fn new(cache_dir: Option<&Path>, offline: bool) -> anyhow::Result<PullRequests> {
+        let cache = cache_dir.map(|dir| dir.join("pull-requests.json"));
+        let merged = cache
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        let token = match offline {
+            true => None,
+            false => std::env::var(crate::config::get()?.github_token_env()).ok(),
+        };
+        Ok(PullRequests {
+            cache,
+            merged,
+            offline,
+            token,
+            api_url: API_URL.to_string(),
+            failures: vec![],
+        })
+    }
+
+    /// The pull requests we couldn't ask GitHub about, and why.
+    pub fn failures(&self) -> &[anyhow::Error] {
+        &self.failures
+    }
+
+    /// Writes what we know back to the cache. The cache is only an
+    /// optimization, so failing to write it is ignored.
+    pub fn save(&self) {
+        if let Some(path) = &self.cache {
+            let text = match serde_json::to_string_pretty(&self.merged) {
+                Ok(text) => text,
+                Err(_) => return,
+            };
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            let _ = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(path, text));
+        }
+    }
+
+    /// Asks GitHub whether `owner/repo#number` is merged.
+    fn fetch(&self, pull: &PullRequest) -> anyhow::Result<bool> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_url, pull.owner, pull.repo, pull.number
+        );
+        let mut request = ureq::get(&url)
+            .set("Accept", "application/vnd.github+json")
+            .set("User-Agent", "skill-tree");
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", &format!("bearer {}", token));
+        }
+        let response: Value = request
+            .call()
+            .with_context(|| format!("asking GitHub about {}", pull))?
+            .into_json()?;
+        match response["merged"].as_bool() {
+            Some(merged) => Ok(merged),
+            None => anyhow::bail!("GitHub didn't say whether {} is merged", pull),
+        }
+    }
+}
+
+impl StatusProvider for PullRequests {
+    fn status(&mut self, href: &str) -> anyhow::Result<Option<Status>> {
+        let pull = match PullRequest::parse(href) {
+            Some(pull) => pull,
+            None => return Ok(None),
+        };
+        let key = pull.to_string();
+        let merged = match self.merged.get(&key) {
+            Some(true) => true,
+            cached if self.offline => cached.copied().unwrap_or(false),
+            // One failed request (say, a rate limit) shouldn't hold up the
+            // rest of the tree.
+            _ => match self.fetch(&pull) {
+                Ok(merged) => {
+                    self.merged.insert(key, merged);
+                    merged
+                }
+                Err(error) => {
+                    self.failures.push(error);
+                    return Ok(None);
+                }
+            },
+        };
+        Ok(if merged { Some(Status::Complete) } else { None })
+    }
+}
+
+/// A pull request, as named by its URL.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PullRequest {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+impl PullRequest {
+    /// Parses `https://github.com/<owner>/<repo>/pull/<number>`, which may
+    /// go on to a tab (`/files`), a query or a fragment.
+    pub fn parse(href: &str) -> Option<PullRequest> {
+        let path = href.strip_prefix("https://github.com/")?;
+        let path = path.split(['?', '#']).next()?;
+        match path.split('/').collect::<Vec<_>>()[..] {
+            [owner, repo, "pull", number, ..] if !owner.is_empty() && !repo.is_empty() => {
+                Some(PullRequest {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    number: number.parse().ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PullRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,92 @@
+use super::{PullRequest, PullRequests};
+use crate::test::scratch;
+use skill_tree_core::provider::StatusProvider;
+use skill_tree_core::{SkillTree, Status};
+
+#[test]
+fn parse() {
+    let pull = |owner: &str, repo: &str, number| PullRequest {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        number,
+    };
+    assert_eq!(
+        PullRequest::parse("https://github.com/rust-lang/rust/pull/123"),
+        Some(pull("rust-lang", "rust", 123))
+    );
+    assert_eq!(
+        PullRequest::parse("https://github.com/a/b/pull/7/files#diff-1"),
+        Some(pull("a", "b", 7))
+    );
+    assert_eq!(
+        PullRequest::parse("https://github.com/a/b/pull/7?w=1"),
+        Some(pull("a", "b", 7))
+    );
+    assert_eq!(PullRequest::parse("https://github.com/a/b/issues/7"), None);
+    assert_eq!(PullRequest::parse("https://github.com/a/b/pull/x"), None);
+    assert_eq!(PullRequest::parse("https://gitlab.com/a/b/pull/7"), None);
+    assert_eq!(
+        pull("a", "b", 7).to_string(),
+        "a/b#7",
+        "the cache is keyed by this"
+    );
+}
+
+#[test]
+fn offline_uses_the_cache() {
+    let dir = scratch("pull-requests");
+    std::fs::write(
+        dir.join("pull-requests.json"),
+        r#"{ "a/b#1": true, "a/b#2": false }"#,
+    )
+    .unwrap();
+    let mut pulls = PullRequests::new(Some(&dir), true).unwrap();
+    let mut status = |href: &str| pulls.status(href).unwrap();
+    assert_eq!(
+        status("https://github.com/a/b/pull/1"),
+        Some(Status::Complete)
+    );
+    assert_eq!(status("https://github.com/a/b/pull/2"), None);
+    assert_eq!(status("https://github.com/a/b/pull/3"), None);
+    assert_eq!(status("https://example.org/a/b/pull/1"), None);
+
+    let mut tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "parser"
+items = [
+    { label = "exprs", href = "https://github.com/a/b/pull/1" },
+    { label = "stmts", href = "https://github.com/a/b/pull/2" },
+]
+"#,
+    )
+    .unwrap();
+    assert_eq!(tree.apply_statuses(&mut pulls).unwrap(), 1);
+    let group = tree.groups().next().unwrap();
+    assert_eq!(group.progress(&tree), (1, 2));
+}
+
+#[test]
+fn failed_requests_leave_the_status_alone() {
+    let mut pulls = PullRequests::new(None, false).unwrap();
+    // Nothing listens on port 1, so every request fails.
+    pulls.api_url = "http://127.0.0.1:1".to_string();
+    let mut tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "parser"
+items = [
+    { label = "exprs", href = "https://github.com/a/b/pull/1", status = "Assigned" },
+    { label = "stmts", href = "https://github.com/a/b/pull/2" },
+]
+"#,
+    )
+    .unwrap();
+    assert_eq!(tree.apply_statuses(&mut pulls).unwrap(), 0);
+    let group = tree.groups().next().unwrap();
+    assert_eq!(group.items[0]["status"], "Assigned");
+    assert_eq!(pulls.failures().len(), 2);
+    assert!(pulls.failures()[0]
+        .to_string()
+        .contains("asking GitHub about a/b#1"));
+}
@@ -0,0 +1,85 @@
+//! `render --stamp`: records which tool version rendered a diagram, from
+//! which source, and when, so published diagrams can be traced back to a
+//! revision.
+//!
+//! The source is identified by its git blob id (what `git hash-object`
+//! prints), so `git log --find-object=<id>` finds the commits that had
+//! that version of the file. Only the root file is hashed, not includes.
+
+use sha1::{Digest, Sha1};
+use std::path::Path;
+
+pub struct Stamp {
+    pub version: &'static str,
+    pub source: String,
+    pub blob: String,
+    pub rendered: String,
+}
+
+impl Stamp {
+    pub fn new(path: &Path, text: &str) -> Stamp {
+        Stamp {
+            version: env!("CARGO_PKG_VERSION"),
+            source: path.display().to_string(),
+            blob: git_blob_id(text.as_bytes()),
+            rendered: skill_tree_core::date::now(),
+        }
+    }
+
+    /// One line describing the render, used in every output format.
+    pub fn describe(&self) -> String {
+        format!(
+            "generated by skill-tree {} from {} (git blob {}) at {}",
+            self.version, self.source, self.blob, self.rendered
+        )
+    }
+
+    /// Prepends the stamp to a graphviz file as a comment.
+    pub fn dot(&self, dot_text: &str) -> String {
+        format!("// {}\n{}", self.describe(), dot_text)
+    }
+
+    /// Adds the stamp to an SVG as a `<metadata>` element, right after
+    /// the opening `<svg>` tag.
+    pub fn svg(&self, svg: &str) -> String {
+        let metadata = format!(
+            "\n<metadata>{}</metadata>",
+            htmlescape::encode_minimal(&self.describe())
+        );
+        match svg
+            .find("<svg")
+            .and_then(|start| Some(start + svg[start..].find('>')? + 1))
+        {
+            Some(end) => format!("{}{}{}", &svg[..end], metadata, &svg[end..]),
+            None => svg.to_string(),
+        }
+    }
+
+    /// Adds the stamp to HTML as a footer: at the end of the `<body>` of a
+    /// full page, or at the end of a snippet.
+    pub fn html(&self, html: &str) -> String {
+        let footer = format!(
+            "<p class=\"skill-tree-stamp\">{}</p>\n",
+            htmlescape::encode_minimal(&self.describe())
+        );
+        match html.rfind("</body>") {
+            Some(end) => format!("{}{}{}", &html[..end], footer, &html[end..]),
+            None => format!("{}{}", html, footer),
+        }
+    }
+}
+
+/// The id git gives `contents` as a blob.
+fn git_blob_id(contents: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("blob {}\0", contents.len()));
+    hasher.update(contents);
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,53 @@
+use super::{git_blob_id, Stamp};
+
+fn stamp() -> Stamp {
+    Stamp {
+        version: "1.0.0",
+        source: "roadmap.toml".to_string(),
+        blob: "abc123".to_string(),
+        rendered: "2024-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn matches_git_hash_object() {
+    // `printf 'hello\n' | git hash-object --stdin`
+    assert_eq!(
+        git_blob_id(b"hello\n"),
+        "ce013625030ba8dba906f756967f9e9ca394464a"
+    );
+}
+
+#[test]
+fn stamps_dot() {
+    assert_eq!(
+        stamp().dot("digraph g {\n}\n"),
+        "// generated by skill-tree 1.0.0 from roadmap.toml (git blob abc123) \
+         at 2024-01-01T00:00:00Z\ndigraph g {\n}\n"
+    );
+}
+
+#[test]
+fn stamps_svg() {
+    let svg = "<?xml version=\"1.0\"?>\n<svg width=\"8pt\">\n<g/>\n</svg>\n";
+    assert_eq!(
+        stamp().svg(svg),
+        "<?xml version=\"1.0\"?>\n<svg width=\"8pt\">\n<metadata>generated by skill-tree \
+         1.0.0 from roadmap.toml (git blob abc123) at 2024-01-01T00:00:00Z</metadata>\n\
+         <g/>\n</svg>\n"
+    );
+}
+
+#[test]
+fn stamps_html() {
+    assert!(stamp()
+        .html("<img>\n")
+        .ends_with("<p class=\"skill-tree-stamp\">generated by skill-tree 1.0.0 from roadmap.toml (git blob abc123) at 2024-01-01T00:00:00Z</p>\n"));
+}
+
+#[test]
+fn stamps_html_page() {
+    let html = stamp().html("<html><body>\n<h1>Roadmap</h1>\n</body></html>\n");
+    assert!(html.starts_with("<html><body>\n<h1>Roadmap</h1>\n<p class=\"skill-tree-stamp\">"));
+    assert!(html.ends_with("</p>\n</body></html>\n"));
+}
@@ -0,0 +1,215 @@
+//! `skill-tree sync`: keeps the labels of the GitHub issues that groups
+//! link to in step with the groups' statuses, for when the tree is the
+//! source of truth.
+//!
+//! A group whose `href` is an issue should have the label for its status
+//! (see `doc.issue_labels`) on that issue, and no other status's label.
+//! Without `--push`, the issues that don't are listed; with it, their
+//! labels are fixed. Nothing else about the issues is touched.
+
+use anyhow::Context;
+use fehler::throws;
+use serde_json::{json, Value};
+use skill_tree_core::SkillTree;
+use std::fmt;
+
+const API_URL: &str = "https://api.github.com";
+
+/// An issue, as named by its URL.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Issue {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+impl Issue {
+    /// Parses `https://github.com/<owner>/<repo>/issues/<number>`, which
+    /// may be followed by a query or a fragment.
+    pub fn parse(href: &str) -> Option<Issue> {
+        let path = href.strip_prefix("https://github.com/")?;
+        let path = path.split(['?', '#']).next()?;
+        match path.trim_end_matches('/').split('/').collect::<Vec<_>>()[..] {
+            [owner, repo, "issues", number] if !owner.is_empty() && !repo.is_empty() => {
+                Some(Issue {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    number: number.parse().ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn api_url(&self) -> String {
+        format!(
+            "{}/repos/{}/{}/issues/{}",
+            API_URL, self.owner, self.repo, self.number
+        )
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
+
+/// What has to change on one issue.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change<'t> {
+    /// The group linking to the issue.
+    pub group: &'t str,
+    pub issue: Issue,
+    pub add: Option<&'t str>,
+    pub remove: Vec<String>,
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (group `{}`):", self.issue, self.group)?;
+        let mut separator = " ";
+        if let Some(label) = self.add {
+            write!(f, "{}add `{}`", separator, label)?;
+            separator = ", ";
+        }
+        for label in &self.remove {
+            write!(f, "{}remove `{}`", separator, label)?;
+            separator = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// The changes needed to bring the issues of `tree`'s groups in line
+/// with the groups' statuses, given each issue's current `labels`. Groups
+/// without a `status` are left alone.
+#[throws(anyhow::Error)]
+pub fn plan<'t>(
+    tree: &'t SkillTree,
+    mut labels: impl FnMut(&Issue) -> anyhow::Result<Vec<String>>,
+) -> Vec<Change<'t>> {
+    let status_labels: Vec<&str> = tree
+        .status_names()
+        .into_iter()
+        .map(|name| tree.issue_label(name))
+        .collect();
+    let mut changes = vec![];
+    for group in tree.groups() {
+        let issue = match group.href.as_deref().and_then(Issue::parse) {
+            Some(issue) => issue,
+            None => continue,
+        };
+        let status = match &group.status {
+            Some(status) => status,
+            None => continue,
+        };
+        let wanted = tree.issue_label(status.name());
+        let current = labels(&issue)?;
+        let add = if current
+            .iter()
+            .any(|label| label.eq_ignore_ascii_case(wanted))
+        {
+            None
+        } else {
+            Some(wanted)
+        };
+        let remove: Vec<String> = current
+            .into_iter()
+            .filter(|label| {
+                !label.eq_ignore_ascii_case(wanted)
+                    && status_labels.iter().any(|s| label.eq_ignore_ascii_case(s))
+            })
+            .collect();
+        if add.is_some() || !remove.is_empty() {
+            changes.push(Change {
+                group: &group.name,
+                issue,
+                add,
+                remove,
+            });
+        }
+    }
+    changes
+}
+
+/// The GitHub REST API, with the token in `GITHUB_TOKEN` (or the variable
+/// named by `github_token_env` in the user configuration), if it is set.
+pub struct GitHub {
+    token: Option<String>,
+}
+
+impl GitHub {
+    #[throws(anyhow::Error)]
+    pub fn new() -> GitHub {
+        GitHub {
+            token: std::env::var(crate::config::get()?.github_token_env()).ok(),
+        }
+    }
+
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = ureq::request(method, url)
+            .set("Accept", "application/vnd.github+json")
+            .set("User-Agent", "skill-tree");
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("bearer {}", token)),
+            None => request,
+        }
+    }
+
+    /// The names of the labels on `issue`.
+    #[throws(anyhow::Error)]
+    pub fn labels(&self, issue: &Issue) -> Vec<String> {
+        let response: Value = self
+            .request("GET", &issue.api_url())
+            .call()
+            .with_context(|| format!("reading {} from GitHub", issue))?
+            .into_json()?;
+        response["labels"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|label| label["name"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    /// Makes `change` on GitHub.
+    #[throws(anyhow::Error)]
+    pub fn apply(&self, change: &Change<'_>) {
+        let labels_url = format!("{}/labels", change.issue.api_url());
+        for label in &change.remove {
+            self.request(
+                "DELETE",
+                &format!("{}/{}", labels_url, percent_encode(label)),
+            )
+            .call()
+            .with_context(|| format!("removing `{}` from {}", label, change.issue))?;
+        }
+        if let Some(label) = change.add {
+            self.request("POST", &labels_url)
+                .send_json(json!({ "labels": [label] }))
+                .with_context(|| format!("adding `{}` to {}", label, change.issue))?;
+        }
+    }
+}
+
+/// `text` as one segment of a URL path.
+pub fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,115 @@
+use super::{percent_encode, plan, Change, Issue};
+use skill_tree_core::SkillTree;
+
+#[test]
+fn parse_issue() {
+    assert_eq!(
+        Issue::parse("https://github.com/rust-lang/rust/issues/44265#issuecomment-1"),
+        Some(Issue {
+            owner: "rust-lang".to_string(),
+            repo: "rust".to_string(),
+            number: 44265,
+        })
+    );
+    assert_eq!(Issue::parse("https://github.com/a/b/pull/1"), None);
+    assert_eq!(Issue::parse("https://github.com/a/b/issues"), None);
+    assert_eq!(Issue::parse("https://example.org/a/b/issues/1"), None);
+}
+
+#[test]
+fn plans_label_changes() {
+    let tree = SkillTree::parse(
+        r#"
+[doc.statuses]
+Review = { color = "lightblue" }
+
+[doc.issue_labels]
+Blocked = "S-blocked"
+Assigned = "S-assigned"
+
+[[group]]
+name = "parser"
+status = "Assigned"
+href = "https://github.com/a/b/issues/1"
+items = []
+
+[[group]]
+name = "lexer"
+status = "Review"
+href = "https://github.com/a/b/issues/2"
+items = []
+
+[[group]]
+name = "types"
+status = "Blocked"
+href = "https://github.com/a/b/issues/3"
+items = []
+
+[[group]]
+name = "docs"
+href = "https://github.com/a/b/issues/4"
+items = []
+"#,
+    )
+    .unwrap();
+    tree.validate().unwrap();
+
+    let mut asked = vec![];
+    let changes = plan(&tree, |issue| {
+        asked.push(issue.number);
+        Ok(match issue.number {
+            1 => vec!["S-blocked".to_string(), "A-parser".to_string()],
+            2 => vec![],
+            _ => vec!["s-BLOCKED".to_string()],
+        })
+    })
+    .unwrap();
+    assert_eq!(asked, [1, 2, 3], "groups without a status are skipped");
+
+    let issue = |number| Issue {
+        owner: "a".to_string(),
+        repo: "b".to_string(),
+        number,
+    };
+    assert_eq!(
+        changes,
+        [
+            Change {
+                group: "parser",
+                issue: issue(1),
+                add: Some("S-assigned"),
+                remove: vec!["S-blocked".to_string()],
+            },
+            Change {
+                group: "lexer",
+                issue: issue(2),
+                add: Some("Review"),
+                remove: vec![],
+            },
+        ]
+    );
+    assert_eq!(
+        changes[0].to_string(),
+        "a/b#1 (group `parser`): add `S-assigned`, remove `S-blocked`"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "`doc.issue_labels` has a label for `Blockd`, which is not a status; did you mean `Blocked`?"
+)]
+fn unknown_status_label() {
+    SkillTree::parse("[doc.issue_labels]\nBlockd = \"S-blocked\"\n")
+        .unwrap()
+        .validate()
+        .unwrap();
+}
+
+#[test]
+fn encodes_label_names() {
+    assert_eq!(
+        percent_encode("status: in review"),
+        "status%3A%20in%20review"
+    );
+    assert_eq!(percent_encode("S-blocked"), "S-blocked");
+}
@@ -0,0 +1,80 @@
+use super::RenderOpts;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+fn jobs(args: &[&str]) -> Vec<(PathBuf, Vec<PathBuf>, Option<PathBuf>)> {
+    let opts = RenderOpts::from_iter(Some("render").into_iter().chain(args.iter().copied()));
+    opts.jobs()
+        .unwrap()
+        .into_iter()
+        .map(|job| (job.skill_tree, job.merge, job.output_path))
+        .collect()
+}
+
+/// A fresh directory under the system's temporary directory.
+pub(crate) fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("skill-tree-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn path(p: &str) -> PathBuf {
+    PathBuf::from(p)
+}
+
+#[test]
+fn single_input() {
+    assert_eq!(jobs(&["a.toml"]), [(path("a.toml"), vec![], None)]);
+    assert_eq!(
+        jobs(&["a.toml", "a.svg"]),
+        [(path("a.toml"), vec![], Some(path("a.svg")))]
+    );
+}
+
+#[test]
+fn merges_inputs() {
+    assert_eq!(
+        jobs(&["a.toml", "b.toml", "-o", "all.dot"]),
+        [(path("a.toml"), vec![path("b.toml")], Some(path("all.dot")))]
+    );
+}
+
+#[test]
+fn refuses_to_write_over_a_tree() {
+    let opts = RenderOpts::from_iter(&["render", "a.toml", "b.toml"]);
+    let error = opts.jobs().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`b.toml` looks like a tree, not where to write the output; pass `--output` to merge it"
+    );
+
+    let opts = RenderOpts::from_iter(&["render", "a.toml", "b.toml", "out.dot"]);
+    let error = opts.jobs().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`--output` is required to merge several trees"
+    );
+}
+
+#[test]
+fn separate_needs_output() {
+    let opts = RenderOpts::from_iter(&["render", "--separate", "a.toml", "b.toml"]);
+    let error = opts.jobs().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "an output directory is required with `--separate`"
+    );
+}
+
+#[test]
+fn reads_only_the_root_from_stdin() {
+    assert_eq!(jobs(&["-", "-"]), [(path("-"), vec![], Some(path("-")))]);
+
+    let opts = RenderOpts::from_iter(&["render", "a.toml", "-", "-o", "out.dot"]);
+    let error = opts.jobs().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "only the first tree can be read from stdin (`-`)"
+    );
+}
@@ -0,0 +1,246 @@
+//! `render --emit timeline`: an SVG with one bar per group on a horizontal
+//! time axis, from the group's `start` to its `due` date, with its
+//! dependencies drawn as arrows between the bars.
+//!
+//! Groups without dates are placed where the last of the groups they
+//! depend on ends, and drawn dashed. If no group has dates at all, the
+//! axis counts steps instead of days: each group takes one step, after
+//! the groups it depends on.
+
+use skill_tree_core::date::Date;
+use skill_tree_core::render::Renderer;
+use skill_tree_core::{EdgeKind, Group, SkillTree};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Where a group sits on the axis, in days (or steps).
+#[derive(Debug)]
+pub struct Bar<'t> {
+    pub group: &'t Group,
+    pub from: i64,
+    pub to: i64,
+
+    /// Whether the position comes from the group's own dates.
+    pub dated: bool,
+}
+
+/// Places each group on the axis, ordered by where they start. Returns
+/// the bars and whether the axis is in days.
+pub fn layout(tree: &SkillTree) -> (Vec<Bar<'_>>, bool) {
+    let days = tree
+        .groups()
+        .any(|group| group.start.is_some() || group.due.is_some());
+    let origin = tree
+        .groups()
+        .flat_map(|group| group.start.iter().chain(&group.due))
+        .map(|date| date.days())
+        .min()
+        .unwrap_or(0);
+
+    let mut placed = HashMap::new();
+    for group in tree.groups() {
+        place(tree, group, days, origin, &mut placed);
+    }
+    let mut bars: Vec<Bar<'_>> = tree
+        .groups()
+        .map(|group| {
+            let (from, to) = placed[&group.name[..]].unwrap_or((origin, origin));
+            let dated = group.start.is_some() || group.due.is_some();
+            Bar {
+                group,
+                from,
+                to,
+                dated,
+            }
+        })
+        .collect();
+    // Stable, so groups starting together keep the file's order.
+    bars.sort_by_key(|bar| bar.from);
+    (bars, days)
+}
+
+/// Works out where `group` goes, after placing the groups it depends on.
+/// `placed` maps a name to `None` while the group is being placed, which
+/// breaks dependency cycles.
+fn place<'t>(
+    tree: &'t SkillTree,
+    group: &'t Group,
+    days: bool,
+    origin: i64,
+    placed: &mut HashMap<&'t str, Option<(i64, i64)>>,
+) -> (i64, i64) {
+    if let Some(position) = placed.get(&group.name[..]) {
+        return position.unwrap_or((origin, origin));
+    }
+    if days {
+        if let Some(from) = group.start.or(group.due) {
+            let to = group.due.unwrap_or(from);
+            let position = (from.days(), to.days());
+            placed.insert(&group.name, Some(position));
+            return position;
+        }
+    }
+
+    placed.insert(&group.name, None);
+    let from = group
+        .dependencies()
+        .filter(|(_, kind)| *kind == EdgeKind::Hard)
+        .filter_map(|(requirement, _)| tree.group_named(requirement.group()))
+        .map(|required| place(tree, required, days, origin, placed).1)
+        .max()
+        .unwrap_or(origin);
+    let position = if days { (from, from) } else { (from, from + 1) };
+    placed.insert(&group.name, Some(position));
+    position
+}
+
+const LABEL_WIDTH: f64 = 180.0;
+const PLOT_WIDTH: f64 = 640.0;
+const MARGIN: f64 = 20.0;
+const ROW_HEIGHT: f64 = 28.0;
+const BAR_HEIGHT: f64 = 18.0;
+const MIN_BAR_WIDTH: f64 = 6.0;
+
+/// Draws the timeline of `tree`.
+pub fn svg(tree: &SkillTree) -> String {
+    let (bars, days) = layout(tree);
+    let start = bars.iter().map(|bar| bar.from).min().unwrap_or(0);
+    let end = bars.iter().map(|bar| bar.to).max().unwrap_or(0);
+    let span = (end - start).max(1) as f64;
+    let x = |position: i64| LABEL_WIDTH + (position - start) as f64 / span * PLOT_WIDTH;
+    let row: HashMap<&str, f64> = bars
+        .iter()
+        .enumerate()
+        .map(|(index, bar)| (&bar.group.name[..], MARGIN + index as f64 * ROW_HEIGHT))
+        .collect();
+
+    let width = LABEL_WIDTH + PLOT_WIDTH + MARGIN;
+    let height = 2.0 * MARGIN + bars.len() as f64 * ROW_HEIGHT + ROW_HEIGHT;
+    let title = tree
+        .title()
+        .unwrap_or_else(|| tree.translate("Skill tree").to_string());
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        w = width,
+        h = height
+    );
+    svg.push_str(&format!("<title>{}</title>\n", escape(&title)));
+    svg.push_str(
+        "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+         markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+         <path d=\"M0 0 L10 5 L0 10 z\"/></marker></defs>\n",
+    );
+
+    for bar in &bars {
+        let y = row[&bar.group.name[..]];
+        let left = x(bar.from);
+        let bar_width = (x(bar.to) - left).max(MIN_BAR_WIDTH);
+        let label = bar.group.label.as_ref().unwrap_or(&bar.group.name);
+        let dashes = if bar.dated || !days {
+            ""
+        } else {
+            " stroke-dasharray=\"4 2\""
+        };
+        let mut tooltip = label.clone();
+        if days && bar.dated {
+            tooltip.push_str(&format!(
+                " ({} – {})",
+                Date::from_days(bar.from),
+                Date::from_days(bar.to)
+            ));
+        }
+
+        svg.push_str(&format!(
+            "<g class=\"group\" data-group=\"{}\">",
+            escape(&bar.group.name)
+        ));
+        if let Some(href) = &bar.group.href {
+            let href = escape(&tree.link(href));
+            svg.push_str(&format!("<a href=\"{}\" xlink:href=\"{}\">", href, href));
+        }
+        svg.push_str(&format!("<title>{}</title>", escape(&tooltip)));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">{}</text>",
+            MARGIN,
+            y + BAR_HEIGHT - 5.0,
+            escape(label)
+        ));
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\" stroke=\"black\"{}/>",
+            left,
+            y,
+            bar_width,
+            BAR_HEIGHT,
+            escape(tree.header_color(bar.group)),
+            dashes
+        ));
+        if bar.group.href.is_some() {
+            svg.push_str("</a>");
+        }
+        svg.push_str("</g>\n");
+    }
+
+    // Arrows from the end of each required group to the start of the
+    // group that requires it.
+    for bar in &bars {
+        for (requirement, kind) in bar.group.dependencies() {
+            let required = match tree.group_named(requirement.group()) {
+                Some(required) => required,
+                None => continue,
+            };
+            let from_bar = bars.iter().find(|b| b.group.name == required.name).unwrap();
+            let x1 = x(from_bar.from) + (x(from_bar.to) - x(from_bar.from)).max(MIN_BAR_WIDTH);
+            let y1 = row[&required.name[..]] + BAR_HEIGHT / 2.0;
+            let x2 = x(bar.from);
+            let y2 = row[&bar.group.name[..]] + BAR_HEIGHT / 2.0;
+            let mid = (x1 + x2) / 2.0;
+            let dashes = match kind {
+                EdgeKind::Hard => "",
+                EdgeKind::Soft => " stroke-dasharray=\"4 2\"",
+            };
+            svg.push_str(&format!(
+                "<path d=\"M{x1:.1} {y1} C{mid:.1} {y1} {mid:.1} {y2} {x2:.1} {y2}\" \
+                 fill=\"none\" stroke=\"grey\" marker-end=\"url(#arrow)\"{dashes}/>\n",
+                x1 = x1,
+                y1 = y1,
+                mid = mid,
+                x2 = x2,
+                y2 = y2,
+                dashes = dashes
+            ));
+        }
+    }
+
+    if days && !bars.is_empty() {
+        let y = MARGIN + bars.len() as f64 * ROW_HEIGHT + 12.0;
+        for (position, anchor) in [(start, "start"), (end, "end")] {
+            svg.push_str(&format!(
+                "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"{}\">{}</text>\n",
+                x(position),
+                y,
+                anchor,
+                Date::from_days(position)
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// `svg`, as the renderer `timeline`.
+pub struct Timeline;
+
+impl Renderer for Timeline {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        output.write_all(svg(tree).as_bytes())?;
+        Ok(())
+    }
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
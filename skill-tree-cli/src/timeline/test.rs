@@ -0,0 +1,82 @@
+use super::{layout, svg};
+use skill_tree_core::SkillTree;
+
+fn positions(tree: &SkillTree) -> Vec<(&str, i64, i64)> {
+    let (bars, _) = layout(tree);
+    bars.iter()
+        .map(|bar| (&bar.group.name[..], bar.from, bar.to))
+        .collect()
+}
+
+#[test]
+fn lays_out_by_date() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "parser"
+start = "2024-01-11"
+due = "2024-01-20"
+requires = ["lexer"]
+items = []
+
+[[group]]
+name = "lexer"
+start = "2024-01-01"
+due = "2024-01-10"
+items = []
+
+[[group]]
+name = "codegen"
+requires = ["parser"]
+items = []
+"#,
+    )
+    .unwrap();
+    let day = |date: &str| date.parse::<skill_tree_core::date::Date>().unwrap().days();
+    assert_eq!(
+        positions(&tree),
+        [
+            ("lexer", day("2024-01-01"), day("2024-01-10")),
+            ("parser", day("2024-01-11"), day("2024-01-20")),
+            // Undated, so placed where `parser` ends.
+            ("codegen", day("2024-01-20"), day("2024-01-20")),
+        ]
+    );
+
+    let svg = svg(&tree);
+    assert_eq!(svg.matches("<rect").count(), 3);
+    assert_eq!(svg.matches("marker-end").count(), 2);
+    assert!(svg.contains("stroke-dasharray"), "{}", svg);
+    assert!(svg.contains(">2024-01-20</text>"), "{}", svg);
+}
+
+#[test]
+fn falls_back_to_dependency_order() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "c"
+requires = ["b", "a"]
+items = []
+
+[[group]]
+name = "b"
+requires = ["a"]
+items = []
+
+[[group]]
+name = "a"
+items = []
+
+[[group]]
+name = "loop"
+requires = ["loop"]
+items = []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        positions(&tree),
+        [("a", 0, 1), ("loop", 0, 1), ("b", 1, 2), ("c", 2, 3)]
+    );
+}
@@ -0,0 +1,176 @@
+//! `render --twemoji`: draws the emoji in SVG output as Twemoji graphics
+//! instead of text, so the tree looks the same whatever emoji font (if
+//! any) the viewer has.
+//!
+//! Only the emoji in `EMOJI` are drawn, from the copies in `src/twemoji/`,
+//! so rendering stays offline. graphviz doesn't tell us where each
+//! character of a label lands, so we only replace `<text>` elements that
+//! are nothing but emoji, like the cells of an item's status column;
+//! emoji mixed with other text are left to the font.
+
+/// The emoji we have graphics for. Variation selectors (U+FE0F) are
+/// ignored when matching.
+const EMOJI: &[(&str, &str)] = &[
+    ("⌚", include_str!("twemoji/231a.svg")),
+    ("🛠️", include_str!("twemoji/1f6e0.svg")),
+    ("☑️", include_str!("twemoji/2611.svg")),
+    ("🙋", include_str!("twemoji/1f64b.svg")),
+    ("✅", include_str!("twemoji/2705.svg")),
+    ("❌", include_str!("twemoji/274c.svg")),
+    ("⬜", include_str!("twemoji/2b1c.svg")),
+    ("⬛", include_str!("twemoji/2b1b.svg")),
+    ("📝", include_str!("twemoji/1f4dd.svg")),
+    ("👀", include_str!("twemoji/1f440.svg")),
+    ("🌑", include_str!("twemoji/1f311.svg")),
+    ("🌒", include_str!("twemoji/1f312.svg")),
+    ("🌓", include_str!("twemoji/1f313.svg")),
+    ("🌔", include_str!("twemoji/1f314.svg")),
+    ("🌕", include_str!("twemoji/1f315.svg")),
+    ("🚧", include_str!("twemoji/1f6a7.svg")),
+    ("🔒", include_str!("twemoji/1f512.svg")),
+    ("🔓", include_str!("twemoji/1f513.svg")),
+    ("⭐", include_str!("twemoji/2b50.svg")),
+    ("🎯", include_str!("twemoji/1f3af.svg")),
+    ("⏳", include_str!("twemoji/23f3.svg")),
+    ("⌛", include_str!("twemoji/231b.svg")),
+    ("🔥", include_str!("twemoji/1f525.svg")),
+    ("💡", include_str!("twemoji/1f4a1.svg")),
+    ("🐛", include_str!("twemoji/1f41b.svg")),
+    ("🚀", include_str!("twemoji/1f680.svg")),
+    ("⚠️", include_str!("twemoji/26a0.svg")),
+    ("🚫", include_str!("twemoji/1f6ab.svg")),
+    ("🟢", include_str!("twemoji/1f7e2.svg")),
+    ("🟡", include_str!("twemoji/1f7e1.svg")),
+    ("🔴", include_str!("twemoji/1f534.svg")),
+    ("🟠", include_str!("twemoji/1f7e0.svg")),
+    ("🔵", include_str!("twemoji/1f535.svg")),
+    ("✔️", include_str!("twemoji/2714.svg")),
+    ("✏️", include_str!("twemoji/270f.svg")),
+    ("📦", include_str!("twemoji/1f4e6.svg")),
+    ("🧪", include_str!("twemoji/1f9ea.svg")),
+    ("📌", include_str!("twemoji/1f4cc.svg")),
+    ("🏁", include_str!("twemoji/1f3c1.svg")),
+    ("💤", include_str!("twemoji/1f4a4.svg")),
+    ("❓", include_str!("twemoji/2753.svg")),
+    ("🤔", include_str!("twemoji/1f914.svg")),
+    ("🎉", include_str!("twemoji/1f389.svg")),
+    ("👍", include_str!("twemoji/1f44d.svg")),
+    ("👎", include_str!("twemoji/1f44e.svg")),
+    ("🔍", include_str!("twemoji/1f50d.svg")),
+    ("📅", include_str!("twemoji/1f4c5.svg")),
+    ("🔗", include_str!("twemoji/1f517.svg")),
+    ("🛑", include_str!("twemoji/1f6d1.svg")),
+    ("⏸️", include_str!("twemoji/23f8.svg")),
+    ("▶️", include_str!("twemoji/25b6.svg")),
+];
+
+/// Twemoji graphics are drawn on a 36x36 grid.
+const VIEW_BOX: &str = "0 0 36 36";
+
+/// How far above the baseline the top of an emoji sits, as a fraction of
+/// the font size.
+const ASCENT: f64 = 0.85;
+
+/// Replaces the `<text>` elements of `svg` (as rendered by graphviz) that
+/// hold only emoji we have graphics for with those graphics.
+pub fn inline_emoji(svg: &str) -> String {
+    let mut output = String::with_capacity(svg.len());
+    let mut rest = svg;
+    while let Some(start) = rest.find("<text ") {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find("</text>") {
+            Some(end) => end + "</text>".len(),
+            None => break,
+        };
+        match replace_text(&rest[..end]) {
+            Some(replacement) => output.push_str(&replacement),
+            None => output.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The graphics for a whole `<text ...>...</text>` element, if it is
+/// nothing but emoji we know.
+fn replace_text(element: &str) -> Option<String> {
+    let open_end = element.find('>')?;
+    let tag = &element[..open_end];
+    let content = &element[open_end + 1..element.len() - "</text>".len()];
+    let content = htmlescape::decode_html(content).ok()?;
+    let graphics = split_emoji(content.trim())?;
+
+    let x: f64 = attribute(tag, "x")?.parse().ok()?;
+    let y: f64 = attribute(tag, "y")?.parse().ok()?;
+    let size: f64 = attribute(tag, "font-size").unwrap_or("14").parse().ok()?;
+    let width = size * graphics.len() as f64;
+    let left = match attribute(tag, "text-anchor") {
+        Some("middle") => x - width / 2.0,
+        Some("end") => x - width,
+        _ => x,
+    };
+    let top = y - size * ASCENT;
+
+    let mut replacement = String::new();
+    for (index, (emoji, svg)) in graphics.into_iter().enumerate() {
+        replacement.push_str(&format!(
+            "<svg x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" viewBox=\"{}\" role=\"img\" aria-label=\"{}\">{}</svg>",
+            left + size * index as f64,
+            top,
+            size,
+            size,
+            VIEW_BOX,
+            emoji,
+            inner(svg),
+        ));
+    }
+    Some(replacement)
+}
+
+/// Splits `text` into emoji we have graphics for, or returns `None` if it
+/// is empty or anything in it isn't one.
+fn split_emoji(text: &str) -> Option<Vec<(&'static str, &'static str)>> {
+    let text: String = text.chars().filter(|&c| c != '\u{fe0f}').collect();
+    let mut rest = &text[..];
+    let mut graphics = vec![];
+    while !rest.is_empty() {
+        let (emoji, svg, len) = EMOJI
+            .iter()
+            .filter_map(|&(emoji, svg)| {
+                let key: String = emoji.chars().filter(|&c| c != '\u{fe0f}').collect();
+                if rest.starts_with(&key) {
+                    Some((emoji, svg, key.len()))
+                } else {
+                    None
+                }
+            })
+            .max_by_key(|&(_, _, len)| len)?;
+        graphics.push((emoji, svg));
+        rest = &rest[len..];
+    }
+    if graphics.is_empty() {
+        None
+    } else {
+        Some(graphics)
+    }
+}
+
+/// The value of `name="..."` in the opening tag `tag`.
+fn attribute<'t>(tag: &'t str, name: &str) -> Option<&'t str> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = start + tag[start..].find('"')?;
+    Some(&tag[start..end])
+}
+
+/// What is inside a Twemoji file's `<svg>` element.
+fn inner(svg: &str) -> &str {
+    let start = svg.find('>').map_or(0, |end| end + 1);
+    let end = svg.rfind("</svg>").unwrap_or(svg.len());
+    &svg[start..end]
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,36 @@
+use super::inline_emoji;
+
+#[test]
+fn replaces_emoji_only_text() {
+    let svg = "<g><text text-anchor=\"middle\" x=\"50\" y=\"-20\" font-family=\"Times,serif\" font-size=\"20.00\">☑️</text></g>";
+    let output = inline_emoji(svg);
+    assert!(
+        output.starts_with(
+            "<g><svg x=\"40.00\" y=\"-37.00\" width=\"20.00\" height=\"20.00\" viewBox=\"0 0 36 36\" role=\"img\" aria-label=\"☑️\"><path fill=\"#269\""
+        ),
+        "{}",
+        output
+    );
+    assert!(output.ends_with("</svg></g>"));
+    assert!(!output.contains("<text"));
+}
+
+#[test]
+fn lays_out_runs_of_emoji() {
+    let svg = "<text x=\"0\" y=\"10\" font-size=\"10.00\">🌕🌑</text>";
+    let output = inline_emoji(svg);
+    assert_eq!(output.matches("<svg ").count(), 2);
+    assert!(output.contains("<svg x=\"0.00\" y=\"1.50\""));
+    assert!(output.contains("<svg x=\"10.00\" y=\"1.50\""));
+}
+
+#[test]
+fn leaves_other_text_alone() {
+    for svg in [
+        "<text x=\"0\" y=\"10\">☑️ Done</text>",
+        "<text x=\"0\" y=\"10\">🦆</text>",
+        "<text x=\"0\" y=\"10\"></text>",
+    ] {
+        assert_eq!(inline_emoji(svg), svg);
+    }
+}
@@ -0,0 +1,45 @@
+//! Helpers for the URIs that the HTTP API and the language server are
+//! handed.
+
+use std::path::PathBuf;
+
+/// Undoes the percent-encoding of a URI path segment.
+pub fn percent_decode(text: &str) -> String {
+    let mut bytes = vec![];
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let hex = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (byte, hex) {
+            (b'%', Some(decoded)) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Converts a `file://` URI into a path. The host, if any, is dropped, and
+/// so is the slash before a Windows drive letter (`file:///C:/tree.toml`).
+pub fn file_path(uri: &str) -> PathBuf {
+    let path = match uri.strip_prefix("file://") {
+        Some(rest) => &rest[rest.find('/').unwrap_or(rest.len())..],
+        None => uri,
+    };
+    let path = percent_decode(path);
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        return PathBuf::from(&path[1..]);
+    }
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,29 @@
+use super::{file_path, percent_decode};
+use std::path::PathBuf;
+
+#[test]
+fn decodes_paths() {
+    assert_eq!(percent_decode("type%20checker"), "type checker");
+    assert_eq!(percent_decode("caf%C3%A9"), "café");
+    assert_eq!(percent_decode("100%"), "100%");
+}
+
+#[test]
+fn converts_file_uris() {
+    assert_eq!(
+        file_path("file:///home/me/my%20tree%231.toml"),
+        PathBuf::from("/home/me/my tree#1.toml")
+    );
+    assert_eq!(
+        file_path("file://localhost/tmp/tree.toml"),
+        PathBuf::from("/tmp/tree.toml")
+    );
+    assert_eq!(
+        file_path("file:///C:/trees/tree.toml"),
+        PathBuf::from("C:/trees/tree.toml")
+    );
+    assert_eq!(
+        file_path("file:///c%3A/trees/tree.toml"),
+        PathBuf::from("c:/trees/tree.toml")
+    );
+}
@@ -0,0 +1,103 @@
+//! `skill-tree workspace`: renders every tree of a workspace (see
+//! `SkillTree::load_workspace`) into a directory, as a page per tree
+//! (`<name>.html`, as `render --emit html` makes) and an `index.html`
+//! overview linking to them with the progress of each.
+
+use crate::{graphviz, html};
+use anyhow::Context;
+use fehler::throws;
+use skill_tree_core::workspace::Workspace;
+use skill_tree_core::SkillTree;
+use std::fs;
+use std::path::Path;
+
+#[throws(anyhow::Error)]
+pub fn run(dir: &Path, out_dir: &Path) {
+    let workspace = SkillTree::load_workspace(dir)?;
+    if workspace.trees.is_empty() {
+        anyhow::bail!(
+            "no `*.skill-tree.toml` files found under `{}`",
+            dir.display()
+        );
+    }
+    workspace.validate()?;
+
+    fs::create_dir_all(out_dir).with_context(|| format!("creating `{}`", out_dir.display()))?;
+    for named in &workspace.trees {
+        let tree = &named.tree;
+        let svg = graphviz::render_svg(&tree.to_graphviz()?, tree.title().as_deref())
+            .with_context(|| format!("rendering `{}`", named.path.display()))?;
+        crate::write_output(&out_dir.join(page(&named.name)), html::page(tree, &svg)?)?;
+    }
+
+    let title = dir
+        .canonicalize()
+        .ok()
+        .and_then(|dir| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| dir.display().to_string());
+    crate::write_output(
+        &out_dir.join("index.html"),
+        overview_html(&workspace, &title),
+    )?;
+    eprintln!(
+        "wrote {} pages and an index to `{}`",
+        workspace.trees.len(),
+        out_dir.display()
+    );
+}
+
+/// The file a tree's page is written to.
+fn page(name: &str) -> String {
+    format!("{}.html", name)
+}
+
+/// The overview of `workspace`, headed `title`: each tree's title (or
+/// name), linking to its page, with how many groups it has and how many
+/// of their items are done. Each tree's line is in the tree's own
+/// `doc.locale`.
+pub fn overview_html(workspace: &Workspace, title: &str) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(title)));
+    html.push_str("<style>\nli { margin-bottom: 0.5em; }\n</style>\n");
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+
+    html.push_str("<ul>\n");
+    for named in &workspace.trees {
+        let tree = &named.tree;
+        let label = tree.title().unwrap_or_else(|| named.name.clone());
+        let groups = match tree.groups().count() {
+            1 => tree.translate("1 group").to_string(),
+            n => tree
+                .translate("{count} groups")
+                .replace("{count}", &n.to_string()),
+        };
+        let (done, total) = tree.progress(tree.groups());
+        let percent = (done * 100).checked_div(total).unwrap_or(0);
+        let progress = tree
+            .translate("{done}/{total} items done ({percent}%)")
+            .replace("{done}", &done.to_string())
+            .replace("{total}", &total.to_string())
+            .replace("{percent}", &percent.to_string());
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>: {}, {}</li>\n",
+            escape(&page(&named.name)),
+            escape(&label),
+            escape(&groups),
+            escape(&progress),
+        ));
+    }
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    htmlescape::encode_minimal(text)
+}
+
+#[cfg(test)]
+mod test;
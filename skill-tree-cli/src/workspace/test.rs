@@ -0,0 +1,29 @@
+use super::overview_html;
+use skill_tree_core::SkillTree;
+use std::path::Path;
+
+#[test]
+fn overview_links_each_tree() {
+    let workspace =
+        SkillTree::load_workspace(Path::new("../skill-tree-core/test-data/workspace")).unwrap();
+    assert_eq!(
+        overview_html(&workspace, "Teams & trees"),
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Teams &amp; trees</title>\n\
+         <style>\n\
+         li { margin-bottom: 0.5em; }\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <h1>Teams &amp; trees</h1>\n\
+         <ul>\n\
+         <li><a href=\"compiler.html\">Compiler</a>: 1 group, 1/1 items done (100%)</li>\n\
+         <li><a href=\"tooling.html\">Tooling</a>: 2 groups, 1/2 items done (50%)</li>\n\
+         </ul>\n\
+         </body>\n\
+         </html>\n"
+    );
+}
@@ -0,0 +1,69 @@
+//! Times parsing and rendering a generated tree of 5,000 items to graphviz:
+//!
+//! ```text
+//! cargo bench -p skill-tree-core
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use skill_tree_core::SkillTree;
+use std::fmt::Write;
+
+const STATUSES: &[&str] = &["todo", "wip", "blocked", "done", "dropped"];
+
+fn render(c: &mut Criterion) {
+    let text = generate(500, 10);
+    c.bench_function("parse 5k items", |b| {
+        b.iter(|| SkillTree::parse(&text).unwrap())
+    });
+
+    let tree = SkillTree::parse(&text).unwrap();
+    c.bench_function("render 5k items", |b| {
+        b.iter(|| tree.to_graphviz().unwrap())
+    });
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
+
+/// A tree of `groups` groups in a chain, each with `items` items, emoji
+/// tables with glob and numeric patterns, and defaults for the columns.
+fn generate(groups: usize, items: usize) -> String {
+    let mut text = String::from(
+        r#"[doc]
+columns = ["status", "progress"]
+defaults = { status = "todo", progress = "0" }
+
+[doc.emoji.status]
+"todo" = "⬜"
+"wip*" = "🛠️"
+"blocked" = "⛔"
+"done" = "☑️"
+"dropped" = "🗑️"
+
+[doc.emoji.progress]
+">=100" = "🌕"
+">=50" = "🌓"
+"<50" = "🌑"
+"#,
+    );
+    for group in 0..groups {
+        writeln!(text, "\n[[group]]\nname = \"g{}\"", group).unwrap();
+        if group > 0 {
+            writeln!(text, "requires = [\"g{}\"]", group - 1).unwrap();
+        }
+        text.push_str("items = [\n");
+        for item in 0..items {
+            let n = group * items + item;
+            writeln!(
+                text,
+                "    {{ label = \"item {}\", status = \"{}\", progress = \"{}\" }},",
+                n,
+                STATUSES[n % STATUSES.len()],
+                n % 101
+            )
+            .unwrap();
+        }
+        text.push_str("]\n");
+    }
+    text
+}
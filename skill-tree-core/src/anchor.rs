@@ -0,0 +1,75 @@
+//! The ids rendered SVG and HTML give each group's node (`group-parser`)
+//! and item (`item-parser-3`, for its third item), so documentation can
+//! link straight to one (`roadmap.html#group-parser`). They come from the
+//! group's name, not its position, so links keep working as the tree is
+//! edited and re-rendered.
+
+use crate::tree::{Group, SkillTree};
+use std::collections::{HashMap, HashSet};
+
+/// The ids of a tree's groups, worked out once for a render.
+pub struct Anchors<'t> {
+    /// Each group's name as a slug, made unique, by name.
+    slugs: HashMap<&'t str, String>,
+}
+
+impl SkillTree {
+    /// Works out the ids of the groups. A group's slug is its name as a
+    /// slug; if an earlier group already has that slug (`type checker`
+    /// and `type-checker`, say), `-2`, `-3` and so on are added, skipping
+    /// any slug already taken.
+    pub fn anchors(&self) -> Anchors<'_> {
+        let mut taken = HashSet::new();
+        let mut slugs = HashMap::new();
+        for group in self.groups() {
+            let base = slug(&group.name);
+            let mut unique = base.clone();
+            let mut n = 1;
+            while !taken.insert(unique.clone()) {
+                n += 1;
+                unique = format!("{}-{}", base, n);
+            }
+            slugs.entry(&group.name[..]).or_insert(unique);
+        }
+        Anchors { slugs }
+    }
+}
+
+impl Anchors<'_> {
+    /// The id of `group`'s node: `group-` and its slug.
+    pub fn group_id(&self, group: &Group) -> String {
+        format!("group-{}", self.slug(group))
+    }
+
+    /// The id of the item at `index` (0-based) of `group`: `item-`, the
+    /// group's slug and the item's number, from 1.
+    pub fn item_id(&self, group: &Group, index: usize) -> String {
+        format!("item-{}-{}", self.slug(group), index + 1)
+    }
+
+    /// The slug worked out for `group`, or its name as a slug if it isn't
+    /// one of the tree's groups.
+    fn slug(&self, group: &Group) -> String {
+        match self.slugs.get(&group.name[..]) {
+            Some(slug) => slug.clone(),
+            None => slug(&group.name),
+        }
+    }
+}
+
+/// Turns free text into something usable in an id or name: lowercase
+/// letters and digits, with a `-` for each run of anything else.
+pub fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod test;
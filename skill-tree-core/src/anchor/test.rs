@@ -0,0 +1,51 @@
+use crate::SkillTree;
+
+#[test]
+fn ids() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "Type checker"
+items = [{ label = "Inference" }, { label = "Traits" }]
+
+[[group]]
+name = "parser"
+items = []
+
+[[group]]
+name = "type-checker"
+items = [{ label = "Again" }]
+"#,
+    )
+    .unwrap();
+    let anchors = tree.anchors();
+    let groups: Vec<_> = tree.groups().collect();
+    assert_eq!(anchors.group_id(groups[0]), "group-type-checker");
+    assert_eq!(anchors.item_id(groups[0], 1), "item-type-checker-2");
+    assert_eq!(anchors.group_id(groups[1]), "group-parser");
+    assert_eq!(anchors.group_id(groups[2]), "group-type-checker-2");
+    assert_eq!(anchors.item_id(groups[2], 0), "item-type-checker-2-1");
+}
+
+#[test]
+fn skips_taken_ids() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "a b"
+items = []
+
+[[group]]
+name = "a-b"
+items = []
+
+[[group]]
+name = "a-b-2"
+items = []
+"#,
+    )
+    .unwrap();
+    let anchors = tree.anchors();
+    let ids: Vec<String> = tree.groups().map(|g| anchors.group_id(g)).collect();
+    assert_eq!(ids, ["group-a-b", "group-a-b-2", "group-a-b-2-2"]);
+}
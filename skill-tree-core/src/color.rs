@@ -0,0 +1,146 @@
+//! Reading graphviz colors, to check how readable text on them is, and
+//! to write them where graphviz's names mean nothing, such as CSS.
+
+/// graphviz's X11 color names and their values, one `name rrggbb` per
+/// line.
+const X11: &str = include_str!("color/x11.txt");
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+pub const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+impl Rgb {
+    /// Reads a graphviz color: `#rrggbb` (or `#rrggbbaa`), an `H,S,V`
+    /// triple of numbers from 0 to 1, or an X11 color name. Returns
+    /// `None` for anything else, such as colors from other schemes.
+    pub fn parse(color: &str) -> Option<Rgb> {
+        let color = color.trim();
+        if let Some(hex) = color.strip_prefix('#') {
+            return match hex.len() {
+                6 | 8 => Some(Rgb {
+                    r: u8::from_str_radix(hex.get(0..2)?, 16).ok()?,
+                    g: u8::from_str_radix(hex.get(2..4)?, 16).ok()?,
+                    b: u8::from_str_radix(hex.get(4..6)?, 16).ok()?,
+                }),
+                _ => None,
+            };
+        }
+        if color.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            let hsv: Vec<f64> = color
+                .split([',', ' '])
+                .filter(|part| !part.is_empty())
+                .map(|part| part.parse().ok())
+                .collect::<Option<_>>()?;
+            return match hsv[..] {
+                [h, s, v] => Some(Rgb::from_hsv(h, s, v)),
+                _ => None,
+            };
+        }
+        let name = color.to_ascii_lowercase();
+        X11.lines().find_map(|line| {
+            let (n, hex) = line.split_once(' ')?;
+            if n == name {
+                Rgb::parse(&format!("#{}", hex))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn from_hsv(h: f64, s: f64, v: f64) -> Rgb {
+        let h = (h.clamp(0.0, 1.0) * 6.0) % 6.0;
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let c = v * s;
+        let x = c * (1.0 - ((h % 2.0) - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = v - c;
+        let byte = |value: f64| ((value + m) * 255.0).round() as u8;
+        Rgb {
+            r: byte(r),
+            g: byte(g),
+            b: byte(b),
+        }
+    }
+
+    /// `#rrggbb`.
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// The relative luminance, as WCAG defines it.
+    pub fn luminance(self) -> f64 {
+        let channel = |value: u8| {
+            let value = f64::from(value) / 255.0;
+            if value <= 0.03928 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(self.r) + 0.7152 * channel(self.g) + 0.0722 * channel(self.b)
+    }
+
+    /// The WCAG contrast ratio between `self` and `other`, from 1 (none)
+    /// to 21 (black on white).
+    pub fn contrast(self, other: Rgb) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+/// Colors for clusters that don't choose their own, from ColorBrewer's
+/// `Set3`: one for the headers of their groups, and a lighter one for
+/// their background.
+pub const CLUSTER_PALETTE: &[(&str, &str)] = &[
+    ("#8dd3c7", "#ddf2ee"),
+    ("#ffffb3", "#ffffe8"),
+    ("#bebada", "#eceaf4"),
+    ("#fb8072", "#fed9d5"),
+    ("#80b1d3", "#d9e8f2"),
+    ("#fdb462", "#fee8d0"),
+    ("#b3de69", "#e8f5d2"),
+    ("#fccde5", "#fef0f7"),
+    ("#bc80bd", "#ebd9eb"),
+    ("#ccebc5", "#f0f9ee"),
+    ("#ffed6f", "#fffad4"),
+];
+
+/// A hash of `text` that is the same on every platform and Rust version
+/// (32-bit FNV-1a), so colors picked with it don't change between runs.
+pub fn stable_hash(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Words graphviz accepts as colors besides the X11 names.
+const SPECIAL: &[&str] = &["transparent", "none", "invis"];
+
+/// Whether graphviz will accept `color`. Anything we can't read but that
+/// names a color scheme (`/accent3/1`) or lists several colors
+/// (`red:blue`) is let through for graphviz to judge.
+pub fn is_valid(color: &str) -> bool {
+    Rgb::parse(color).is_some()
+        || SPECIAL.contains(&&color.to_ascii_lowercase()[..])
+        || color.contains(['/', ':'])
+}
+
+/// The X11 color names, for suggesting one in place of a typo.
+pub fn names<'a>() -> impl Iterator<Item = &'a str> {
+    X11.lines().filter_map(|line| line.split(' ').next())
+}
+
+#[cfg(test)]
+mod test;
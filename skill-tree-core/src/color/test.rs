@@ -0,0 +1,37 @@
+use super::{Rgb, BLACK};
+
+#[test]
+fn parses_graphviz_colors() {
+    let navy = Rgb {
+        r: 0,
+        g: 0,
+        b: 0x80,
+    };
+    assert_eq!(Rgb::parse("#000080"), Some(navy));
+    assert_eq!(Rgb::parse("#000080ff"), Some(navy));
+    assert_eq!(Rgb::parse("navy"), Some(navy));
+    assert_eq!(Rgb::parse("NavyBlue"), Some(navy));
+    assert_eq!(Rgb::parse("0.0,1.0,1.0"), Some(Rgb { r: 255, g: 0, b: 0 }));
+    assert_eq!(Rgb::parse("0.333 1 1"), Some(Rgb { r: 1, g: 255, b: 0 }));
+    assert_eq!(
+        Rgb::parse("darkgoldenrod1"),
+        Some(Rgb {
+            r: 0xff,
+            g: 0xb9,
+            b: 0x0f
+        })
+    );
+    assert_eq!(Rgb::parse("/accent3/1"), None);
+    assert_eq!(Rgb::parse("#12"), None);
+    assert_eq!(Rgb::parse("not-a-color"), None);
+}
+
+#[test]
+fn contrast_ratios() {
+    let white = Rgb::parse("white").unwrap();
+    assert!((BLACK.contrast(white) - 21.0).abs() < 1e-9);
+    assert!((white.contrast(white) - 1.0).abs() < 1e-9);
+    let navy = Rgb::parse("navy").unwrap();
+    assert!(BLACK.contrast(navy) < 2.0);
+    assert!(BLACK.contrast(Rgb::parse("lightblue").unwrap()) > 4.5);
+}
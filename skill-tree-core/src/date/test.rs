@@ -0,0 +1,34 @@
+use super::{civil_from_days, days_from_civil, Date};
+
+#[test]
+fn converts_days_to_dates() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+}
+
+#[test]
+fn parses_dates() {
+    let date: Date = "2024-02-29".parse().unwrap();
+    assert_eq!(date.to_string(), "2024-02-29");
+    assert!(date < "2024-03-01".parse().unwrap());
+    assert_eq!(
+        "2023-02-29".parse::<Date>(),
+        Err("`2023-02-29` is not a date in the calendar".to_string())
+    );
+    assert_eq!(
+        "2024-1-10".parse::<Date>(),
+        Err("invalid date `2024-1-10`, expected `YYYY-MM-DD`".to_string())
+    );
+}
+
+#[test]
+fn converts_dates_to_days() {
+    for days in [-1, 0, 11_016, 19_723, 20_000] {
+        let (year, month, day) = civil_from_days(days);
+        assert_eq!(days_from_civil(year, month, day), days);
+    }
+    assert_eq!("2024-01-01".parse::<Date>().unwrap().days(), 19_723);
+    assert_eq!(Date::from_days(19_723).to_string(), "2024-01-01");
+}
@@ -0,0 +1,52 @@
+//! Spellings that still parse but that we'd like people to stop using.
+//!
+//! Each one is reported as a warning pointing at the line that uses it,
+//! together with what to write instead.
+
+use crate::tree::{Requirement, SkillTree};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Deprecation {
+    /// 1-based line of the first use of `old` in the file.
+    pub line: usize,
+
+    /// The deprecated text, as it appears in the file.
+    pub old: String,
+
+    /// What to write instead.
+    pub new: String,
+}
+
+/// Finds the deprecated spellings used by `tree`, which was parsed from
+/// `text`.
+pub fn find(tree: &SkillTree, text: &str) -> Vec<Deprecation> {
+    let mut deprecations = vec![];
+
+    // `"group:port"` predates structured requires.
+    for group in tree.groups() {
+        for (requirement, _) in group.dependencies() {
+            if let (Requirement::Name(name), Some(port)) = (requirement, requirement.port()) {
+                deprecations.push(deprecation(
+                    text,
+                    format!(r#""{}""#, name),
+                    format!(
+                        r#"{{ group = "{}", port = "{}" }}"#,
+                        requirement.group(),
+                        port
+                    ),
+                ));
+            }
+        }
+    }
+
+    deprecations
+}
+
+fn deprecation(text: &str, old: String, new: String) -> Deprecation {
+    let line = text
+        .lines()
+        .position(|line| line.contains(&old))
+        .map(|index| index + 1)
+        .unwrap_or(1);
+    Deprecation { line, old, new }
+}
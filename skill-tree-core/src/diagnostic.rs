@@ -0,0 +1,93 @@
+//! Problems found in a tree, with enough structure for the CLI, the
+//! language server and renderers to show them without parsing messages.
+//! See `SkillTree::diagnostics`.
+
+use crate::source::Source;
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The tree can't be rendered until this is fixed.
+    Error,
+
+    /// The tree renders, but probably not as intended.
+    Warning,
+}
+
+/// What a diagnostic is about.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Location {
+    /// A line (1-based) of a file.
+    Line { path: PathBuf, line: usize },
+
+    /// The `[[group]]` with this name.
+    Group(String),
+
+    /// The item at `index` (0-based) in a group's `items`.
+    Item { group: String, index: usize },
+
+    /// The `[[cluster]]` with this name.
+    Cluster(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: Severity,
+
+    /// A stable name for the kind of problem, such as the name of the
+    /// lint that found it.
+    pub code: Option<&'static str>,
+
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            level: Severity::Error,
+            code: None,
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    pub fn warning(code: &'static str, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            level: Severity::Warning,
+            code: Some(code),
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    pub fn at(self, location: Location) -> Diagnostic {
+        Diagnostic {
+            location: Some(location),
+            ..self
+        }
+    }
+}
+
+impl From<&Source> for Location {
+    fn from(source: &Source) -> Location {
+        Location::Line {
+            path: source.path.clone(),
+            line: source.line,
+        }
+    }
+}
+
+/// The message, prefixed with `file:line:` if it has that location.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(Location::Line { path, line }) = &self.location {
+            write!(f, "{}:{}: ", path.display(), line)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod test;
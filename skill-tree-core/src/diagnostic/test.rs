@@ -0,0 +1,71 @@
+use super::{Diagnostic, Location, Severity};
+use crate::SkillTree;
+use std::path::PathBuf;
+
+#[test]
+fn shows_line_locations() {
+    let diagnostic = Diagnostic::warning("deprecated", "`x` is deprecated").at(Location::Line {
+        path: PathBuf::from("tree.toml"),
+        line: 3,
+    });
+    assert_eq!(diagnostic.to_string(), "tree.toml:3: `x` is deprecated");
+
+    let diagnostic = Diagnostic::error("broken").at(Location::Group("parser".to_string()));
+    assert_eq!(diagnostic.to_string(), "broken");
+}
+
+#[test]
+fn collects_errors_warnings_and_lints() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+locale = "tlh"
+
+[[cluster]]
+name = "unused"
+label = "Unused"
+
+[[group]]
+name = "lexer"
+aliases = ["tokenizer"]
+items = []
+
+[[group]]
+name = "parser"
+requires = ["tokenizer", "missing"]
+items = []
+"#,
+    )
+    .unwrap();
+
+    let summary: Vec<_> = tree
+        .diagnostics()
+        .into_iter()
+        .map(|d| (d.level, d.code, d.location))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                Severity::Error,
+                None,
+                Some(Location::Group("parser".to_string()))
+            ),
+            (Severity::Warning, Some("unknown-locale"), None),
+            (
+                Severity::Warning,
+                Some("old-group-name"),
+                Some(Location::Group("parser".to_string()))
+            ),
+            (
+                Severity::Warning,
+                Some("unused-cluster"),
+                Some(Location::Cluster("unused".to_string()))
+            ),
+        ]
+    );
+
+    // The older APIs see the same problems.
+    assert_eq!(tree.validate_all().len(), 1);
+    assert_eq!(tree.warnings().len(), 3);
+}
@@ -0,0 +1,33 @@
+//! `SkillTree::to_petgraph`, with the `petgraph` feature: the dependency
+//! graph as a petgraph `DiGraph`, for its algorithms (toposort,
+//! condensation, dominators and so on).
+
+use crate::tree::{EdgeKind, GroupIndex, SkillTree};
+use petgraph::graph::DiGraph;
+use std::collections::HashSet;
+
+impl SkillTree {
+    /// The dependency graph, with a node for each group and an edge from
+    /// each group to the groups that require or suggest it. The groups'
+    /// nodes are added in the order the groups are declared, so the node
+    /// with index `i` has the weight `GroupIndex(i)`; look it up with
+    /// `group_at`. Dependencies on groups that don't exist are left out, as
+    /// are repeated edges (say, to two ports of the same group).
+    pub fn to_petgraph(&self) -> DiGraph<GroupIndex, EdgeKind> {
+        let mut graph = DiGraph::new();
+        let nodes: Vec<_> = (0..self.groups().count())
+            .map(|index| graph.add_node(GroupIndex(index)))
+            .collect();
+        let mut seen = HashSet::new();
+        for edge in self.edges() {
+            let (from, to) = (nodes[edge.to.0], nodes[edge.from.0]);
+            if seen.insert((from, to, edge.kind)) {
+                graph.add_edge(from, to, edge.kind);
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,54 @@
+use crate::{EdgeKind, GroupIndex, SkillTree};
+use petgraph::algo::toposort;
+use petgraph::visit::EdgeRef;
+
+#[test]
+fn to_petgraph() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "types"
+requires = ["parser", "missing"]
+suggests = ["lexer"]
+items = []
+
+[[group]]
+name = "parser"
+requires = ["lexer", "lexer:tokens"]
+items = []
+
+[[group]]
+name = "lexer"
+items = [{ label = "tokens", port = "tokens" }]
+"#,
+    )
+    .unwrap();
+    let graph = tree.to_petgraph();
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+
+    let names = |nodes: Vec<_>| -> Vec<&str> {
+        nodes
+            .into_iter()
+            .map(|node| &tree.group_at(graph[node]).unwrap().name[..])
+            .collect()
+    };
+    assert_eq!(
+        names(toposort(&graph, None).unwrap()),
+        ["lexer", "parser", "types"]
+    );
+
+    let lexer = graph
+        .node_indices()
+        .find(|&node| graph[node] == GroupIndex(2))
+        .unwrap();
+    let mut kinds: Vec<(&str, EdgeKind)> = graph
+        .edges(lexer)
+        .map(|edge| (names(vec![edge.target()])[0], *edge.weight()))
+        .collect();
+    kinds.sort_by_key(|(name, _)| *name);
+    assert_eq!(
+        kinds,
+        [("parser", EdgeKind::Hard), ("types", EdgeKind::Soft)]
+    );
+}
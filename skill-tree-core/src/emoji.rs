@@ -0,0 +1,130 @@
+//! Looking values up in an `EmojiMap`.
+//!
+//! Besides exact values, keys may be patterns, so that numeric progress
+//! columns don't need every value spelled out:
+//!
+//! * `">=50"`, `">50"`, `"<=10"`, `"<10"` compare numerically;
+//! * `"25..50"` is a half-open range and `"25..=50"` an inclusive one;
+//! * `"wip*"` matches any value starting with `wip`.
+//!
+//! An exact key always wins. Otherwise the narrowest matching numeric
+//! pattern wins (between `">=25"` and `">=50"`, the one whose bound is
+//! closer to the value), then the longest matching prefix. Numeric values
+//! may carry a trailing `%`.
+
+use crate::suggest;
+use crate::tree::EmojiMap;
+use std::cmp::Ordering;
+
+/// Finds the emoji for `input` in `map`, if any key matches it.
+pub fn lookup<'m>(map: &'m EmojiMap, input: &str) -> Option<&'m String> {
+    if let Some(output) = map.get(input) {
+        return Some(output);
+    }
+
+    if let Some(value) = number(input) {
+        let best = map
+            .iter()
+            .filter_map(|(key, output)| {
+                let (low, high) = range(key)?;
+                if low.contains(value, true) && high.contains(value, false) {
+                    Some((rank(low, high, value), key, output))
+                } else {
+                    None
+                }
+            })
+            .min_by(|a, b| {
+                (a.0)
+                    .partial_cmp(&b.0)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| a.1.cmp(b.1))
+            });
+        if let Some((_, _, output)) = best {
+            return Some(output);
+        }
+    }
+
+    map.iter()
+        .filter_map(|(key, output)| {
+            let prefix = key.strip_suffix('*')?;
+            if input.starts_with(prefix) {
+                Some((prefix.len(), key, output))
+            } else {
+                None
+            }
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(a.1)))
+        .map(|(_, _, output)| output)
+}
+
+/// The plain key of `map` that `input` is most likely a typo of (see
+/// `suggest::closest`). Patterns are never suggested.
+pub fn suggest<'m>(map: &'m EmojiMap, input: &str) -> Option<&'m str> {
+    let keys = map
+        .keys()
+        .filter(|key| !key.ends_with('*') && range(key).is_none());
+    suggest::closest(input, keys.map(|key| &key[..]))
+}
+
+/// One end of a numeric pattern: `(bound, inclusive)`, or `None` if the
+/// pattern is open on that side.
+#[derive(Copy, Clone)]
+struct Bound(Option<(f64, bool)>);
+
+impl Bound {
+    /// Whether `value` is on the right side of this bound, which is a
+    /// lower bound if `lower` and an upper bound otherwise.
+    fn contains(self, value: f64, lower: bool) -> bool {
+        match self.0 {
+            None => true,
+            Some((bound, true)) if lower => value >= bound,
+            Some((bound, false)) if lower => value > bound,
+            Some((bound, true)) => value <= bound,
+            Some((bound, false)) => value < bound,
+        }
+    }
+}
+
+/// Parses a numeric pattern into its lower and upper bounds.
+fn range(key: &str) -> Option<(Bound, Bound)> {
+    let open = Bound(None);
+    let bound = |text: &str, inclusive| Some(Bound(Some((number(text)?, inclusive))));
+    if let Some(rest) = key.strip_prefix(">=") {
+        Some((bound(rest, true)?, open))
+    } else if let Some(rest) = key.strip_prefix('>') {
+        Some((bound(rest, false)?, open))
+    } else if let Some(rest) = key.strip_prefix("<=") {
+        Some((open, bound(rest, true)?))
+    } else if let Some(rest) = key.strip_prefix('<') {
+        Some((open, bound(rest, false)?))
+    } else if let Some((low, high)) = key.split_once("..=") {
+        Some((bound(low, true)?, bound(high, true)?))
+    } else if let Some((low, high)) = key.split_once("..") {
+        Some((bound(low, true)?, bound(high, false)?))
+    } else {
+        None
+    }
+}
+
+/// Orders matching patterns: narrower ranges first, then those with a
+/// bound closer to `value`.
+fn rank(low: Bound, high: Bound, value: f64) -> (f64, f64) {
+    let width = match (low.0, high.0) {
+        (Some((low, _)), Some((high, _))) => high - low,
+        _ => f64::INFINITY,
+    };
+    let distance = [low.0, high.0]
+        .iter()
+        .flatten()
+        .map(|(bound, _)| (value - bound).abs())
+        .fold(f64::INFINITY, f64::min);
+    (width, distance)
+}
+
+fn number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    text.strip_suffix('%').unwrap_or(text).trim().parse().ok()
+}
+
+#[cfg(test)]
+mod test;
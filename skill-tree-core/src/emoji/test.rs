@@ -0,0 +1,63 @@
+use super::{lookup, suggest};
+use crate::tree::EmojiMap;
+
+fn map(entries: &[(&str, &str)]) -> EmojiMap {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn moon() -> EmojiMap {
+    map(&[
+        ("0", "🌑"),
+        ("<50", "🌒"),
+        (">=50", "🌓"),
+        (">=75", "🌔"),
+        ("100", "🌕"),
+    ])
+}
+
+#[test]
+fn exact_key_wins() {
+    assert_eq!(lookup(&moon(), "100").unwrap(), "🌕");
+    assert_eq!(lookup(&moon(), "0").unwrap(), "🌑");
+}
+
+#[test]
+fn closest_threshold_wins() {
+    assert_eq!(lookup(&moon(), "20").unwrap(), "🌒");
+    assert_eq!(lookup(&moon(), "50").unwrap(), "🌓");
+    assert_eq!(lookup(&moon(), "80%").unwrap(), "🌔");
+}
+
+#[test]
+fn ranges() {
+    let map = map(&[("0..50", "low"), ("50..=100", "high"), (">=0", "any")]);
+    assert_eq!(lookup(&map, "49.5").unwrap(), "low");
+    assert_eq!(lookup(&map, "50").unwrap(), "high");
+    assert_eq!(lookup(&map, "100").unwrap(), "high");
+    assert_eq!(lookup(&map, "101").unwrap(), "any");
+}
+
+#[test]
+fn prefixes() {
+    let map = map(&[("wip*", "🛠️"), ("wip-review*", "👀")]);
+    assert_eq!(lookup(&map, "wip-tests").unwrap(), "🛠️");
+    assert_eq!(lookup(&map, "wip-review-2").unwrap(), "👀");
+    assert_eq!(lookup(&map, "done"), None);
+}
+
+#[test]
+fn non_numbers_skip_numeric_patterns() {
+    assert_eq!(lookup(&moon(), "n/a"), None);
+}
+
+#[test]
+fn suggests_likely_typos() {
+    let map = map(&[("complete", "☑️"), ("blocked", "⌚"), ("wip*", "🛠️")]);
+    assert_eq!(suggest(&map, "compelte"), Some("complete"));
+    assert_eq!(suggest(&map, "Blocked"), Some("blocked"));
+    assert_eq!(suggest(&map, "wip"), None);
+    assert_eq!(suggest(&map, "unassigned"), None);
+}
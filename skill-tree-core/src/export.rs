@@ -0,0 +1,87 @@
+//! The dependency graph as CSV, for analysis in other tools (pandas, R, a
+//! spreadsheet) that don't need a drawing: the renderers `edges` and
+//! `adjacency` (see `Renderers`).
+
+use crate::render::Renderer;
+use crate::tree::{EdgeKind, SkillTree};
+use crate::visit::{Edge, TreeVisitor};
+use std::io::Write;
+
+/// One row per dependency: `from,to,port,kind`, where `to` requires (or
+/// suggests) `from`, `port` is the item of `from` it points at, if any,
+/// and `kind` is `requires` or `suggests`.
+pub struct EdgeList;
+
+impl Renderer for EdgeList {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        writeln!(output, "from,to,port,kind")?;
+        for edge in edges(tree) {
+            let kind = match edge.kind {
+                EdgeKind::Hard => "requires",
+                EdgeKind::Soft => "suggests",
+            };
+            writeln!(
+                output,
+                "{},{},{},{}",
+                csv_field(&edge.from.name),
+                csv_field(&edge.to.name),
+                csv_field(edge.requirement.port().unwrap_or("")),
+                kind
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A matrix with a row and a column for each group, goals first and
+/// otherwise in the order they are declared, and a 1 where the column's group requires or suggests the
+/// row's group (0 elsewhere).
+pub struct AdjacencyMatrix;
+
+impl Renderer for AdjacencyMatrix {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        let names: Vec<&str> = tree
+            .groups_goals_first()
+            .map(|group| &group.name[..])
+            .collect();
+        let mut matrix = vec![vec![0; names.len()]; names.len()];
+        let index = |name: &str| names.iter().position(|n| *n == name).unwrap();
+        for edge in edges(tree) {
+            matrix[index(&edge.from.name)][index(&edge.to.name)] = 1;
+        }
+
+        let header: Vec<String> = names.iter().map(|name| csv_field(name)).collect();
+        writeln!(output, ",{}", header.join(","))?;
+        for (name, row) in names.iter().zip(matrix) {
+            let cells: Vec<String> = row.iter().map(|cell| cell.to_string()).collect();
+            writeln!(output, "{},{}", csv_field(name), cells.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// The tree's edges, in the order `SkillTree::walk` visits them.
+fn edges(tree: &SkillTree) -> Vec<Edge<'_>> {
+    struct Edges<'t>(Vec<Edge<'t>>);
+
+    impl<'t> TreeVisitor<'t> for Edges<'t> {
+        fn visit_edge(&mut self, edge: &Edge<'t>) {
+            self.0.push(*edge);
+        }
+    }
+
+    let mut edges = Edges(vec![]);
+    tree.walk(&mut edges);
+    edges.0
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test;
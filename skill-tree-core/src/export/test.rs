@@ -0,0 +1,64 @@
+use crate::render::Renderers;
+use crate::SkillTree;
+
+const TREE: &str = r#"
+[[group]]
+name = "lexer"
+items = [{ label = "tokens", port = "tokens" }]
+
+[[group]]
+name = "parser, v2"
+requires = ["lexer:tokens"]
+items = []
+
+[[group]]
+name = "types"
+requires = ["parser, v2"]
+suggests = ["lexer"]
+items = []
+"#;
+
+#[test]
+fn edge_list() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert_eq!(
+        Renderers::default()
+            .render_to_string("edges", &tree)
+            .unwrap(),
+        "from,to,port,kind\n\
+         lexer,\"parser, v2\",tokens,requires\n\
+         \"parser, v2\",types,,requires\n\
+         lexer,types,,suggests\n"
+    );
+}
+
+#[test]
+fn adjacency_matrix() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert_eq!(
+        Renderers::default()
+            .render_to_string("adjacency", &tree)
+            .unwrap(),
+        ",lexer,\"parser, v2\",types\n\
+         lexer,0,1,1\n\
+         \"parser, v2\",0,0,1\n\
+         types,0,0,0\n"
+    );
+}
+
+#[test]
+fn goals_first() {
+    let tree = SkillTree::parse(&format!("[doc]\ngoals = [\"types\"]\n{}", TREE)).unwrap();
+    let renderers = Renderers::default();
+    assert_eq!(
+        renderers.render_to_string("edges", &tree).unwrap(),
+        "from,to,port,kind\n\
+         \"parser, v2\",types,,requires\n\
+         lexer,types,,suggests\n\
+         lexer,\"parser, v2\",tokens,requires\n"
+    );
+    assert!(renderers
+        .render_to_string("adjacency", &tree)
+        .unwrap()
+        .starts_with(",types,lexer,\"parser, v2\"\ntypes,0,0,0\n"));
+}
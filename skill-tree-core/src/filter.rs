@@ -0,0 +1,297 @@
+//! A small query language for picking out groups, e.g. `status=blocked
+//! and cluster=frontend` or `requires~"parser"`.
+//!
+//! A test is a field, an operator and a value: `=` (the field has the
+//! value), `!=` (it doesn't) or `~` (some value of the field contains it).
+//! Comparisons ignore case. Values with spaces or operators in them are
+//! quoted, as in `label="type inference"`. Tests combine with `and`, `or`
+//! and `not`, and parentheses; `and` binds tighter than `or`.
+//!
+//! The fields are:
+//!
+//! * `name`, `label` (the name if there is no label), `cluster`, `href`;
+//! * `status`: the group's `status`, or if it has none, its items';
+//! * `requires` and `suggests`: the names of the groups depended on, as
+//!   `tree::group` for groups in another tree of the workspace;
+//! * `goal`: `true` or `false`;
+//! * anything else is an item column (or key), which a group has if any
+//!   of its items has it, with `doc.defaults` applied.
+
+use crate::tree::{Group, ItemExt, SkillTree};
+use fehler::throws;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Test {
+        field: String,
+        op: Op,
+        value: String,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    /// `=`
+    Equals,
+
+    /// `!=`
+    NotEquals,
+
+    /// `~`
+    Contains,
+}
+
+impl Filter {
+    #[throws(anyhow::Error)]
+    pub fn parse(text: &str) -> Filter {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser {
+            text,
+            tokens: &tokens,
+            next: 0,
+        };
+        let filter = parser.or()?;
+        if let Some(token) = parser.peek() {
+            anyhow::bail!("unexpected {} in `{}`", token, text);
+        }
+        filter
+    }
+
+    /// Whether `group` of `tree` passes this filter.
+    pub fn matches(&self, tree: &SkillTree, group: &Group) -> bool {
+        match self {
+            Filter::And(a, b) => a.matches(tree, group) && b.matches(tree, group),
+            Filter::Or(a, b) => a.matches(tree, group) || b.matches(tree, group),
+            Filter::Not(filter) => !filter.matches(tree, group),
+            Filter::Test { field, op, value } => {
+                let value = value.to_lowercase();
+                let mut values = field_values(tree, group, field)
+                    .into_iter()
+                    .map(str::to_lowercase);
+                match op {
+                    Op::Equals => values.any(|v| v == value),
+                    Op::NotEquals => !values.any(|v| v == value),
+                    Op::Contains => values.any(|v| v.contains(&value)),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Filter> {
+        Filter::parse(text)
+    }
+}
+
+/// The values `group` has for `field`; see the module docs.
+fn field_values<'t>(tree: &'t SkillTree, group: &'t Group, field: &str) -> Vec<&'t str> {
+    let dependencies = |list: &'t Option<Vec<crate::Requirement>>| {
+        list.iter()
+            .flatten()
+            .map(|requirement| requirement.qualified_group())
+            .collect()
+    };
+    match field {
+        "name" => vec![&group.name],
+        "label" => vec![group.label.as_ref().unwrap_or(&group.name)],
+        "cluster" => group.cluster.iter().map(String::as_str).collect(),
+        "href" => group.href.iter().map(String::as_str).collect(),
+        "status" if group.status.is_some() => vec![group.status.as_ref().unwrap().name()],
+        "requires" => dependencies(&group.requires),
+        "suggests" => dependencies(&group.suggests),
+        "goal" if tree.is_goal(group) => vec!["true"],
+        "goal" => vec!["false"],
+        column => group
+            .items()
+            .map(|item| item.column_value(tree, group, column))
+            .filter(|value| !value.is_empty())
+            .collect(),
+    }
+}
+
+impl SkillTree {
+    /// The groups that pass `filter`.
+    pub fn groups_matching<'t>(&'t self, filter: &'t Filter) -> impl Iterator<Item = &'t Group> {
+        self.groups()
+            .filter(move |group| filter.matches(self, group))
+    }
+
+    /// Drops the groups that don't pass `filter`. Groups that depended on
+    /// a dropped group now depend on what it depended on, as with
+    /// `doc.exclude_groups`.
+    pub fn select_groups(&mut self, filter: &Filter) {
+        let excluded: HashSet<String> = self
+            .groups()
+            .filter(|group| !filter.matches(self, group))
+            .map(|group| group.name.clone())
+            .collect();
+        self.remove_groups(&excluded);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{}`", word),
+            Token::Quoted(text) => write!(f, "{:?}", text),
+            Token::Op(Op::Equals) => write!(f, "`=`"),
+            Token::Op(Op::NotEquals) => write!(f, "`!=`"),
+            Token::Op(Op::Contains) => write!(f, "`~`"),
+            Token::Open => write!(f, "`(`"),
+            Token::Close => write!(f, "`)`"),
+        }
+    }
+}
+
+#[throws(anyhow::Error)]
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '=' => tokens.push(Token::Op(Op::Equals)),
+            '~' => tokens.push(Token::Op(Op::Contains)),
+            '!' if chars.peek() == Some(&'=') => {
+                chars.next();
+                tokens.push(Token::Op(Op::NotEquals));
+            }
+            '"' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => quoted.push(c),
+                            None => anyhow::bail!("unterminated string in `{}`", text),
+                        },
+                        Some(c) => quoted.push(c),
+                        None => anyhow::bail!("unterminated string in `{}`", text),
+                    }
+                }
+                tokens.push(Token::Quoted(quoted));
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if !is_word_char(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            c => anyhow::bail!("unexpected `{}` in `{}`", c, text),
+        }
+    }
+    tokens
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !"()=~!\"".contains(c)
+}
+
+/// A recursive descent parser over the tokens of `text`.
+struct Parser<'p> {
+    text: &'p str,
+    tokens: &'p [Token],
+    next: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word == keyword => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// `and ("or" and)*`
+    #[throws(anyhow::Error)]
+    fn or(&mut self) -> Filter {
+        let mut filter = self.and()?;
+        while self.eat_keyword("or") {
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        filter
+    }
+
+    /// `unary ("and" unary)*`
+    #[throws(anyhow::Error)]
+    fn and(&mut self) -> Filter {
+        let mut filter = self.unary()?;
+        while self.eat_keyword("and") {
+            filter = Filter::And(Box::new(filter), Box::new(self.unary()?));
+        }
+        filter
+    }
+
+    /// `"not" unary | "(" or ")" | field op value`
+    #[throws(anyhow::Error)]
+    fn unary(&mut self) -> Filter {
+        if self.eat_keyword("not") {
+            return Filter::Not(Box::new(self.unary()?));
+        }
+        if self.peek() == Some(&Token::Open) {
+            self.next += 1;
+            let filter = self.or()?;
+            if self.peek() != Some(&Token::Close) {
+                anyhow::bail!("expected `)` in `{}`", self.text);
+            }
+            self.next += 1;
+            return filter;
+        }
+        let field = match self.peek() {
+            Some(Token::Word(word)) => word.clone(),
+            Some(token) => anyhow::bail!("expected a field, found {} in `{}`", token, self.text),
+            None => anyhow::bail!("expected a field at the end of `{}`", self.text),
+        };
+        self.next += 1;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => anyhow::bail!(
+                "expected `=`, `!=` or `~` after `{}` in `{}`",
+                field,
+                self.text
+            ),
+        };
+        self.next += 1;
+        let value = match self.peek() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => value.clone(),
+            _ => anyhow::bail!("expected a value after `{}` in `{}`", field, self.text),
+        };
+        self.next += 1;
+        Filter::Test { field, op, value }
+    }
+}
+
+#[cfg(test)]
+mod test;
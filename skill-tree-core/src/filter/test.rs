@@ -0,0 +1,133 @@
+use super::{Filter, Op};
+use crate::SkillTree;
+
+const TREE: &str = r#"
+[doc]
+columns = ["status", "owner"]
+defaults = { status = "todo" }
+
+[[group]]
+name = "lexer"
+cluster = "frontend"
+status = "Blocked"
+items = [{ label = "tokens", owner = "ana" }]
+
+[[group]]
+name = "parser"
+label = "Parser rewrite"
+cluster = "frontend"
+requires = ["lexer"]
+items = [{ label = "grammar", status = "done" }, { label = "errors" }]
+
+[[group]]
+name = "types"
+cluster = "middle"
+requires = ["parser"]
+suggests = ["lexer"]
+goal = true
+items = [{ label = "inference", status = "blocked" }]
+"#;
+
+fn matching(query: &str) -> Vec<String> {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let filter = Filter::parse(query).unwrap();
+    tree.groups_matching(&filter)
+        .map(|group| group.name.clone())
+        .collect()
+}
+
+#[test]
+fn parse() {
+    let test = |field: &str, op, value: &str| Filter::Test {
+        field: field.to_string(),
+        op,
+        value: value.to_string(),
+    };
+    assert_eq!(
+        Filter::parse(r#"a=b or not c~"d e" and (f!=g)"#).unwrap(),
+        Filter::Or(
+            Box::new(test("a", Op::Equals, "b")),
+            Box::new(Filter::And(
+                Box::new(Filter::Not(Box::new(test("c", Op::Contains, "d e")))),
+                Box::new(test("f", Op::NotEquals, "g")),
+            )),
+        )
+    );
+}
+
+#[test]
+fn fields() {
+    assert_eq!(matching("status=blocked"), ["lexer", "types"]);
+    assert_eq!(matching("status=blocked and cluster=frontend"), ["lexer"]);
+    assert_eq!(matching(r#"requires~"pars""#), ["types"]);
+    assert_eq!(
+        matching("suggests=lexer or requires=lexer"),
+        ["parser", "types"]
+    );
+    assert_eq!(matching("status=todo"), ["parser"]);
+    assert_eq!(matching("owner=ANA"), ["lexer"]);
+    assert_eq!(matching(r#"label="parser rewrite""#), ["parser"]);
+    assert_eq!(matching("not goal=true"), ["lexer", "parser"]);
+    assert_eq!(matching("cluster!=frontend"), ["types"]);
+    assert_eq!(
+        matching("(cluster=middle or name=lexer) and not status=done"),
+        ["lexer", "types"]
+    );
+}
+
+#[test]
+fn cross_tree_requirements() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "ci"
+items = []
+
+[[group]]
+name = "release"
+requires = ["ci"]
+items = []
+
+[[group]]
+name = "deploy"
+requires = ["infra::ci:runner", { group = "infra::ci", port = "cache" }]
+items = []
+"#,
+    )
+    .unwrap();
+    let matching = |query: &str| -> Vec<String> {
+        let filter = Filter::parse(query).unwrap();
+        tree.groups_matching(&filter)
+            .map(|group| group.name.clone())
+            .collect()
+    };
+    assert_eq!(matching("requires=ci"), ["release"]);
+    assert_eq!(matching("requires=infra::ci"), ["deploy"]);
+}
+
+#[test]
+fn errors() {
+    let error = |query: &str| Filter::parse(query).unwrap_err().to_string();
+    assert_eq!(
+        error("status"),
+        "expected `=`, `!=` or `~` after `status` in `status`"
+    );
+    assert_eq!(
+        error("status="),
+        "expected a value after `status` in `status=`"
+    );
+    assert_eq!(error("(a=b"), "expected `)` in `(a=b`");
+    assert_eq!(error("a=b c=d"), "unexpected `c` in `a=b c=d`");
+    assert_eq!(error(r#"a="b"#), r#"unterminated string in `a="b`"#);
+    assert_eq!(error("a=b and"), "expected a field at the end of `a=b and`");
+}
+
+#[test]
+fn select() {
+    let mut tree = SkillTree::parse(TREE).unwrap();
+    tree.select_groups(&Filter::parse("name!=parser").unwrap());
+    let types = tree.group_named("types").unwrap();
+    let requires: Vec<&str> = types.requires.iter().flatten().map(|r| r.group()).collect();
+    assert_eq!(requires, ["lexer"]);
+    assert_eq!(tree.groups().count(), 2);
+}
@@ -0,0 +1,732 @@
+use crate::anchor::Anchors;
+use crate::lookup::CellLookup;
+use crate::render::Renderer;
+use crate::tree::{Cluster, EdgeKind, Group, ItemExt, Requirement, SkillTree, Status};
+use fehler::throws;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::Write;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+impl SkillTree {
+    /// Writes graphviz representing this skill-tree to the given output.
+    #[throws(anyhow::Error)]
+    pub fn write_graphviz(&self, output: &mut dyn Write) {
+        write_graphviz(self, output, None)?
+    }
+
+    /// Generates a string containing graphviz content for this skill-tree.
+    #[throws(anyhow::Error)]
+    pub fn to_graphviz(&self) -> String {
+        let mut output = Vec::new();
+        write_graphviz(self, &mut output, None)?;
+        String::from_utf8(output)?
+    }
+
+    /// The pages the tree is split into by `page_to_graphviz`: each
+    /// cluster that has groups, then `None` for the groups outside any
+    /// cluster, if there are some.
+    pub fn pages(&self) -> Vec<Option<&str>> {
+        let mut pages: Vec<Option<&str>> = self
+            .cluster
+            .iter()
+            .flatten()
+            .map(|cluster| &cluster.name[..])
+            .filter(|name| self.groups().any(|g| g.cluster.as_deref() == Some(*name)))
+            .map(Some)
+            .collect();
+        if self.groups().any(|g| g.cluster.is_none()) {
+            pages.push(None);
+        }
+        pages
+    }
+
+    /// Generates graphviz for one page of the tree: the groups in
+    /// `cluster` (or in no cluster). Dependencies between these groups and
+    /// groups on other pages are drawn as stub nodes linking to the other
+    /// page, whose address `page_href` provides; so are `#group` links to
+    /// groups on other pages.
+    #[throws(anyhow::Error)]
+    pub fn page_to_graphviz(
+        &self,
+        cluster: Option<&str>,
+        page_href: &dyn Fn(Option<&str>) -> String,
+    ) -> String {
+        let mut output = Vec::new();
+        let page = Page {
+            cluster,
+            href: page_href,
+        };
+        write_graphviz(self, &mut output, Some(&page))?;
+        String::from_utf8(output)?
+    }
+}
+
+/// The graphviz `dot` generator, as the renderer `dot` (see
+/// `Renderers`).
+pub struct Dot;
+
+impl Renderer for Dot {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        write_graphviz(tree, output, None)
+    }
+}
+
+/// A page of split output.
+struct Page<'p> {
+    cluster: Option<&'p str>,
+    href: &'p dyn Fn(Option<&str>) -> String,
+}
+
+impl Page<'_> {
+    fn contains(&self, group: &Group) -> bool {
+        group.cluster.as_deref() == self.cluster
+    }
+}
+
+/// `href` as written into `page`: like `SkillTree::link`, except that a
+/// `#group` link to a group drawn on another page goes to that page.
+fn page_link<'h>(
+    tree: &SkillTree,
+    anchors: &Anchors<'_>,
+    page: Option<&Page<'_>>,
+    href: &'h str,
+) -> Cow<'h, str> {
+    let group = href
+        .strip_prefix('#')
+        .and_then(|name| tree.group_named(name));
+    match (group, page) {
+        (Some(group), Some(page)) if !page.contains(group) => Cow::Owned(format!(
+            "{}#{}",
+            (page.href)(group.cluster.as_deref()),
+            anchors.group_id(group)
+        )),
+        (Some(group), _) => Cow::Owned(format!("#{}", anchors.group_id(group))),
+        (None, _) => tree.link(href),
+    }
+}
+
+#[throws(anyhow::Error)]
+fn write_graphviz(tree: &SkillTree, output: &mut dyn Write, page: Option<&Page<'_>>) {
+    let graphviz = tree.graphviz.as_ref();
+    let rankdir = graphviz.and_then(|g| g.rankdir).unwrap_or_default().dot();
+    let fontname = attribute_str(
+        "fontname",
+        &graphviz.and_then(|g| g.fontname.as_deref()).map(quote),
+        "",
+    );
+    let fontsize = attribute_str("fontsize", &graphviz.and_then(|g| g.fontsize), "");
+    let bgcolor = attribute_str(
+        "bgcolor",
+        &graphviz
+            .and_then(|g| g.bgcolor.as_deref())
+            .map(|c| quote(tree.color(c))),
+        "",
+    );
+    let layout: String = [
+        ("size", graphviz.and_then(|g| g.size.clone())),
+        ("ratio", graphviz.and_then(|g| g.ratio.clone())),
+        ("page", graphviz.and_then(|g| g.page.clone())),
+        (
+            "margin",
+            graphviz.and_then(|g| g.margin).map(|m| m.to_string()),
+        ),
+        (
+            "nodesep",
+            graphviz.and_then(|g| g.nodesep).map(|n| n.to_string()),
+        ),
+        (
+            "ranksep",
+            graphviz.and_then(|g| g.ranksep).map(|r| r.to_string()),
+        ),
+        (
+            "concentrate",
+            graphviz.and_then(|g| g.concentrate).map(|c| c.to_string()),
+        ),
+        (
+            "splines",
+            graphviz
+                .and_then(|g| g.splines)
+                .map(|s| s.dot().to_string()),
+        ),
+        (
+            "overlap",
+            graphviz
+                .and_then(|g| g.overlap)
+                .map(|o| o.dot().to_string()),
+        ),
+    ]
+    .iter()
+    .map(|(name, value)| attribute_str(name, value, ""))
+    .collect();
+    let node_fontsize = graphviz.and_then(|g| g.node_fontsize).unwrap_or(16.0);
+    let label = graph_label(tree);
+    let lookup = tree.cell_lookup();
+    let anchors = tree.anchors();
+
+    // Goals drawn on this page; they are ranked last. Clustered nodes
+    // only honour rank constraints with `newrank`.
+    let goals: Vec<&Group> = tree
+        .groups()
+        .filter(|group| tree.is_goal(group))
+        .filter(|group| page.is_none_or(|page| page.contains(group)))
+        .collect();
+    let newrank = if goals.is_empty() {
+        ""
+    } else {
+        r#" newrank="true""#
+    };
+    // Summary edges start at the cluster's border, which needs `compound`.
+    let summaries = graphviz.and_then(|g| g.cluster_summaries) == Some(true);
+    let compound = if summaries { r#" compound="true""# } else { "" };
+
+    let [graph_attrs, node_attrs, edge_attrs] = match graphviz {
+        Some(graphviz) => graphviz
+            .passthrough_attrs()
+            .map(|(_, attrs)| passthrough_str(attrs)),
+        None => Default::default(),
+    };
+
+    writeln!(output, r#"digraph g {{"#)?;
+    writeln!(
+        output,
+        r#"graph [ rankdir = "{rankdir}"{bgcolor}{layout}{fontname}{fontsize}{newrank}{compound}{label}{graph_attrs} ];"#,
+        rankdir = rankdir,
+        bgcolor = bgcolor,
+        layout = layout,
+        fontname = fontname,
+        fontsize = fontsize,
+        newrank = newrank,
+        compound = compound,
+        label = label,
+        graph_attrs = graph_attrs,
+    )?;
+    writeln!(
+        output,
+        r#"node [ fontsize="{node_fontsize}", shape = "ellipse"{fontname}{node_attrs} ];"#,
+        node_fontsize = node_fontsize,
+        fontname = fontname,
+        node_attrs = node_attrs,
+    )?;
+    writeln!(
+        output,
+        r#"edge [{fontname}{fontsize}{edge_attrs} ];"#,
+        fontname = fontname,
+        fontsize = fontsize,
+        edge_attrs = edge_attrs,
+    )?;
+
+    let cluster_colors = tree.cluster_colors();
+    if let Some(clusters) = &tree.cluster {
+        for cluster in clusters {
+            match page {
+                Some(page) if page.cluster != Some(&cluster.name[..]) => continue,
+                _ => {}
+            }
+            let cluster_name = format!("cluster_{}", cluster.name);
+            writeln!(
+                output,
+                r#"subgraph {cluster_name} {{"#,
+                cluster_name = cluster_name
+            )?;
+            writeln!(output, r#"    label="{}";"#, cluster.label)?;
+            // A cluster's own `color` only tints its headers.
+            if cluster.color.is_none() {
+                if let Some(color) = cluster_colors.get(&cluster.name[..]) {
+                    writeln!(output, r#"    bgcolor="{}";"#, color.background)?;
+                }
+            }
+            write_cluster(tree, &lookup, &anchors, page, output, Some(&cluster.name))?;
+            writeln!(output, r#"}}"#)?;
+            if summaries {
+                write_cluster_summary(tree, cluster, output)?;
+            }
+        }
+    }
+    match page {
+        Some(Page {
+            cluster: Some(_), ..
+        }) => {}
+        _ => write_cluster(tree, &lookup, &anchors, page, output, None)?,
+    }
+    if !goals.is_empty() {
+        let names: Vec<String> = goals
+            .iter()
+            .map(|group| format!(r#""{}";"#, group.name))
+            .collect();
+        writeln!(output, r#"{{ rank = "sink"; {} }}"#, names.join(" "))?;
+    }
+
+    // Groups on other pages that we've drawn a stub for.
+    let mut stubs: Vec<&Group> = vec![];
+
+    for group in tree.groups() {
+        for (requirement, kind) in group.dependencies() {
+            // Edges to an alias point at the group's current name.
+            let required = tree.group_named(requirement.group());
+            let target = match required {
+                Some(required) => &required.name[..],
+                None => requirement.group(),
+            };
+            let mut target = match requirement.port() {
+                Some(port) => format!(r#""{}":"{}""#, target, port),
+                None => format!(r#""{}""#, target),
+            };
+            let mut source = format!(r#""{}""#, group.name);
+
+            // On a page, an edge crossing to another page ends at a stub.
+            if let (Some(page), Some(required)) = (page, required) {
+                match (page.contains(group), page.contains(required)) {
+                    (true, true) => {}
+                    (true, false) => target = stub_node(required, &mut stubs),
+                    (false, true) => source = stub_node(group, &mut stubs),
+                    (false, false) => continue,
+                }
+            }
+
+            writeln!(
+                output,
+                r#"{} -> {}{};"#,
+                target,
+                source,
+                edge_attributes(requirement, kind)
+            )?;
+        }
+    }
+
+    // Groups in other trees of the workspace are drawn as a stub linking
+    // to that tree's page, as `skill-tree workspace` names it.
+    let mut external: Vec<(&str, &str)> = vec![];
+    for group in tree.groups() {
+        if page.is_some_and(|page| !page.contains(group)) {
+            continue;
+        }
+        for (requirement, kind) in group.external_dependencies() {
+            let other = (requirement.tree().unwrap(), requirement.group());
+            if !external.contains(&other) {
+                external.push(other);
+            }
+            writeln!(
+                output,
+                r#""tree:{}::{}" -> "{}"{};"#,
+                other.0,
+                other.1,
+                group.name,
+                edge_attributes(requirement, kind)
+            )?;
+        }
+    }
+    for (other_tree, other_group) in external {
+        let see = tree.translate("→ see {page}").replace("{page}", other_tree);
+        writeln!(
+            output,
+            r#""tree:{}::{}" [ label = "{}\n{}", shape = "note", href = "{}.html" ];"#,
+            other_tree,
+            other_group,
+            other_group.replace('"', "\\\""),
+            see.replace('"', "\\\""),
+            other_tree,
+        )?;
+    }
+
+    if let Some(page) = page {
+        for group in stubs {
+            let href = (page.href)(group.cluster.as_deref());
+            let label = group.label.as_ref().unwrap_or(&group.name);
+            let see = tree.translate("→ see {page}").replace("{page}", &href);
+            writeln!(
+                output,
+                r#""stub:{}" [ label = "{}\n{}", shape = "note", href = "{}" ];"#,
+                group.name,
+                label.replace('"', "\\\""),
+                see.replace('"', "\\\""),
+                href,
+            )?;
+        }
+    }
+
+    writeln!(output, r#"}}"#)?;
+}
+
+/// The attributes of the edge for `requirement`, as ` [ ... ]`, or
+/// nothing if it has none.
+fn edge_attributes(requirement: &Requirement, kind: EdgeKind) -> String {
+    let mut attributes = vec![];
+    if let Some(label) = requirement.label() {
+        attributes.push(format!(r#"label = "{}""#, label.replace('"', "\\\"")));
+    }
+    if let EdgeKind::Soft = kind {
+        // Soft dependencies shouldn't affect the layout.
+        attributes.push(r#"style = "dashed""#.to_string());
+        attributes.push(r#"constraint = false"#.to_string());
+    }
+    if attributes.is_empty() {
+        String::new()
+    } else {
+        format!(" [ {} ]", attributes.join(", "))
+    }
+}
+
+/// Records that `group` needs a stub node, returning the node's id.
+fn stub_node<'t>(group: &'t Group, stubs: &mut Vec<&'t Group>) -> String {
+    if !stubs.iter().any(|g| g.name == group.name) {
+        stubs.push(group);
+    }
+    format!(r#""stub:{}""#, group.name)
+}
+
+/// The graph's `label` (and `labelloc`) attributes showing the title and
+/// caption, if there are any.
+fn graph_label(tree: &SkillTree) -> String {
+    let mut lines = vec![];
+    if let Some(title) = tree.title() {
+        lines.push(format!("<b>{}</b>", escape(&title)));
+    }
+    if let Some(caption) = tree.caption() {
+        lines.push(escape(&caption));
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!(r#" label=<{}> labelloc="t""#, lines.join("<br/>"))
+}
+
+#[throws(anyhow::Error)]
+fn write_cluster(
+    tree: &SkillTree,
+    lookup: &CellLookup<'_>,
+    anchors: &Anchors<'_>,
+    page: Option<&Page<'_>>,
+    output: &mut dyn Write,
+    cluster: Option<&String>,
+) {
+    for group in tree.groups_goals_first() {
+        // If we are doing a cluster, the group must be in it;
+        // otherwise, the group must not be in any cluster.
+        match (&group.cluster, cluster) {
+            (None, None) => {}
+            (Some(c1), Some(c2)) if c1 == c2 => {}
+            _ => continue,
+        }
+        writeln!(output, r#""{}" ["#, group.name)?;
+        writeln!(output, r#"  id = "{}""#, anchors.group_id(group))?;
+        write_group_label(tree, lookup, anchors, page, group, output)?;
+        if tree.is_goal(group) {
+            // A double border around the table, and larger text.
+            let node_fontsize = tree.graphviz.as_ref().and_then(|g| g.node_fontsize);
+            writeln!(output, r#"  shape = "box""#)?;
+            writeln!(output, r#"  peripheries = 2"#)?;
+            writeln!(
+                output,
+                r#"  fontsize = "{}""#,
+                node_fontsize.unwrap_or(16.0) * GOAL_FONT_SCALE
+            )?;
+        } else {
+            writeln!(output, r#"  shape = "none""#)?;
+        }
+        writeln!(output, r#"  margin = 0"#)?;
+        writeln!(output, r#"]"#)?;
+    }
+}
+
+/// A node next to `cluster` with how many of its groups have each
+/// status and how many of its items are done, joined to the cluster's
+/// border by a dotted line.
+#[throws(anyhow::Error)]
+fn write_cluster_summary(tree: &SkillTree, cluster: &Cluster, output: &mut dyn Write) {
+    let mut groups = tree.groups_in(Some(&cluster.name)).peekable();
+    let first = match groups.peek() {
+        Some(first) => &first.name,
+        None => return,
+    };
+
+    let mut counts: Vec<(&str, usize)> = vec![];
+    for group in groups {
+        let status = match &group.status {
+            Some(status) => tree.status_name(status),
+            None => tree.translate("No status"),
+        };
+        match counts.iter_mut().find(|(name, _)| *name == status) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((status, 1)),
+        }
+    }
+    let (done, total) = tree.progress(tree.groups_in(Some(&cluster.name)));
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    let progress = tree
+        .translate("{done}/{total} items done ({percent}%)")
+        .replace("{done}", &done.to_string())
+        .replace("{total}", &total.to_string())
+        .replace("{percent}", &percent.to_string());
+
+    writeln!(output, r#""summary:{}" ["#, cluster.name)?;
+    writeln!(output, r#"  label = <<table>"#)?;
+    writeln!(
+        output,
+        r#"    <tr><td bgcolor="lightgrey" colspan="2"><b>{}</b></td></tr>"#,
+        escape(&cluster.label)
+    )?;
+    for (status, count) in counts {
+        writeln!(
+            output,
+            r#"    <tr><td align="left">{}</td><td align="right">{}</td></tr>"#,
+            escape(status),
+            count
+        )?;
+    }
+    writeln!(
+        output,
+        r#"    <tr><td colspan="2">{}</td></tr>"#,
+        escape(&progress)
+    )?;
+    writeln!(output, r#"  </table>>"#)?;
+    writeln!(output, r#"  shape = "none""#)?;
+    writeln!(output, r#"  margin = 0"#)?;
+    writeln!(output, r#"]"#)?;
+    writeln!(
+        output,
+        r#""{}" -> "summary:{}" [ ltail = "cluster_{}", style = "dotted", arrowhead = "none" ];"#,
+        first, cluster.name, cluster.name
+    )?;
+}
+
+/// `label` cut to `doc.max_label_chars` columns, the last being `…`, or
+/// `None` if it is short enough already. Like `group_width`, this counts
+/// emoji and wide characters as two columns, so CJK labels aren't left
+/// twice as long as Latin ones.
+fn shorten(tree: &SkillTree, label: &str) -> Option<String> {
+    let max = tree.doc.as_ref()?.max_label_chars?;
+    if label.width() <= max {
+        return None;
+    }
+    let mut short = String::new();
+    let mut width = 0;
+    for c in label.chars() {
+        width += c.width().unwrap_or(0);
+        if width + 1 > max {
+            break;
+        }
+        short.push(c);
+    }
+    let mut short = short.trim_end().to_string();
+    short.push('…');
+    Some(short)
+}
+
+/// Average width of a character, as a fraction of the font size.
+const CHAR_WIDTH: f64 = 0.6;
+
+/// Room around the text of each cell (padding and borders), in points.
+const CELL_PADDING: f64 = 8.0;
+
+/// How wide `group`'s table needs to be for its widest row, estimated
+/// from the number of columns each character takes up. Graphviz measures
+/// emoji and wide characters as narrower than they are, so leaving it to
+/// graphviz cuts them off.
+#[throws(anyhow::Error)]
+fn group_width(tree: &SkillTree, lookup: &CellLookup<'_>, group: &Group) -> f64 {
+    let mut fontsize = tree
+        .graphviz
+        .as_ref()
+        .and_then(|g| g.node_fontsize)
+        .unwrap_or(16.0);
+    if tree.is_goal(group) {
+        fontsize *= GOAL_FONT_SCALE;
+    }
+    let cells = |texts: &[&str]| -> f64 {
+        texts
+            .iter()
+            .map(|text| text.width() as f64 * CHAR_WIDTH * fontsize + CELL_PADDING)
+            .sum()
+    };
+
+    let header = tree.group_header(group)?;
+    let mut widest = cells(&[&header]);
+    for line in group.description.iter().flatten() {
+        widest = widest.max(cells(&[line]));
+    }
+    for (index, item) in group.items.iter().enumerate() {
+        let mut row: Vec<&str> = tree
+            .columns()
+            .iter()
+            .map(|column| {
+                let value = lookup.value(group, item, column.name());
+                lookup.emoji(group, column.name(), value)
+            })
+            .collect();
+        let label = group.item_label(index)?;
+        let short = shorten(tree, label);
+        row.push(short.as_deref().unwrap_or(label));
+        widest = widest.max(cells(&row));
+    }
+    widest.ceil()
+}
+
+/// How much larger a goal's text is than other nodes'.
+const GOAL_FONT_SCALE: f64 = 1.25;
+
+const WATCH_EMOJI: &str = "⌚";
+const HAMMER_WRENCH_EMOJI: &str = "🛠️";
+const CHECKED_BOX_EMOJI: &str = "☑️";
+const RAISED_HAND_EMOJI: &str = "🙋";
+
+fn escape(s: &str) -> String {
+    htmlescape::encode_minimal(s).replace('\n', "<br/>")
+}
+
+#[throws(anyhow::Error)]
+fn write_group_label(
+    tree: &SkillTree,
+    lookup: &CellLookup<'_>,
+    anchors: &Anchors<'_>,
+    page: Option<&Page<'_>>,
+    group: &Group,
+    output: &mut dyn Write,
+) {
+    let width = match group.width {
+        Some(width) => width,
+        None => group_width(tree, lookup, group)?,
+    };
+    writeln!(output, r#"  label = <<table width="{:.0}">"#, width)?;
+
+    let label = escape(&tree.group_header(group)?);
+    let group_href = attribute_str(
+        "href",
+        &group
+            .href
+            .as_deref()
+            .map(|h| page_link(tree, anchors, page, h)),
+        "",
+    );
+    let header_color = tree.header_color(group);
+    let description_color = group
+        .description_color
+        .as_deref()
+        .map_or("darkgoldenrod1", |color| tree.color(color));
+
+    // We have one column for each thing specified by user, plus the label.
+    let columns = tree.columns().len() + 1;
+
+    writeln!(
+        output,
+        r#"    <tr><td bgcolor="{header_color}" colspan="{columns}"{group_href}>{label}</td></tr>"#,
+        group_href = group_href,
+        label = label,
+        header_color = header_color,
+        columns = columns,
+    )?;
+
+    for label in group.description.iter().flatten() {
+        writeln!(
+            output,
+            r#"    <tr><td bgcolor="{description_color}" colspan="{columns}"{group_href}>{label}</td></tr>"#,
+            group_href = group_href,
+            label = label,
+            description_color = description_color,
+            columns = columns,
+        )?;
+    }
+
+    for (index, item) in group.items.iter().enumerate() {
+        let item_status = Status::Unassigned; // XXX
+        let (_emoji, _fontcolor, mut start_tag, mut end_tag) = match item_status {
+            Status::Blocked => (
+                WATCH_EMOJI,
+                None,
+                "<i><font color=\"lightgrey\">",
+                "</font></i>",
+            ),
+            Status::Unassigned => (RAISED_HAND_EMOJI, Some("red"), "", ""),
+            Status::Assigned => (HAMMER_WRENCH_EMOJI, None, "", ""),
+            Status::Complete => (CHECKED_BOX_EMOJI, None, "<s>", "</s>"),
+            Status::Custom(_) => ("", None, "", ""),
+        };
+
+        let bgcolor = tree
+            .status_definition(lookup.value(group, item, "status"))
+            .and_then(|status| status.color.as_deref())
+            .map_or("cornsilk", |color| tree.color(color));
+        let bgcolor = attribute_str("bgcolor", &Some(bgcolor), "");
+        let href = attribute_str(
+            "href",
+            &item.href().map(|h| page_link(tree, anchors, page, h)),
+            "",
+        );
+        let port = attribute_str("port", &item.port(), "");
+        if item.href().is_some() && start_tag.is_empty() {
+            start_tag = "<u>";
+            end_tag = "</u>";
+        }
+        write!(output, "    <tr>")?;
+
+        for column in tree.columns() {
+            let item_value = lookup.value(group, item, column.name());
+            let emoji = lookup.emoji(group, column.name(), item_value);
+            write!(
+                output,
+                "<td{bgcolor}>{emoji}</td>",
+                bgcolor = bgcolor,
+                emoji = emoji
+            )?;
+        }
+
+        let label = group.item_label(index)?;
+        let (label, tooltip) = match shorten(tree, label) {
+            Some(short) => (escape(&short), Some(escape(label))),
+            None => (escape(label), None),
+        };
+        write!(
+            output,
+            "<td{bgcolor}{href}{port}{tooltip} ID=\"{id}\">{start_tag}{label}{end_tag}</td>",
+            bgcolor = bgcolor,
+            id = anchors.item_id(group, index),
+            href = href,
+            port = port,
+            tooltip = attribute_str("tooltip", &tooltip, ""),
+            label = label,
+            start_tag = start_tag,
+            end_tag = end_tag,
+        )?;
+
+        writeln!(output, "</tr>")?;
+    }
+
+    writeln!(output, r#"  </table>>"#)?;
+}
+
+/// A `[graphviz]` passthrough table as attributes, each value quoted.
+fn passthrough_str(attrs: Option<&BTreeMap<String, String>>) -> String {
+    attrs
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, quote(value)))
+        .collect()
+}
+
+/// Escapes `value` to sit between double quotes in dot. Escapes that
+/// are already there, like `\l` or `\"`, are kept; bare quotes, newlines
+/// and a trailing backslash are escaped.
+fn quote(value: &str) -> String {
+    let mut quoted = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                quoted.push('\\');
+                quoted.push(chars.next().unwrap_or('\\'));
+            }
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted
+}
+
+fn attribute_str(label: &str, text: &Option<impl Display>, suffix: &str) -> String {
+    match text {
+        None => String::new(),
+        Some(t) => format!(" {}=\"{}{}\"", label, t, suffix),
+    }
+}
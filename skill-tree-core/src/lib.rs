@@ -0,0 +1,32 @@
+pub mod anchor;
+pub mod color;
+pub mod date;
+mod deprecated;
+pub mod diagnostic;
+#[cfg(feature = "petgraph")]
+mod digraph;
+mod emoji;
+pub mod export;
+pub mod filter;
+#[cfg(feature = "graphviz")]
+mod graphviz;
+pub mod link;
+pub mod lint;
+pub mod locale;
+pub mod lookup;
+pub mod mermaid;
+pub mod metrics;
+mod migrate;
+pub mod provider;
+pub mod render;
+pub mod source;
+mod suggest;
+mod template;
+mod test_support;
+mod tree;
+pub mod visit;
+pub mod workspace;
+pub use link::LinkStyle;
+pub use migrate::{migrate, SCHEMA_VERSION};
+pub use tree::*;
+mod test;
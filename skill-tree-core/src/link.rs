@@ -0,0 +1,155 @@
+//! How `href`s are written into rendered output, set by `doc.base_url`
+//! and `doc.links`. By default they are left as written; with a base URL
+//! they can all be made absolute (for SVGs that are copied around) or,
+//! where they point under the base URL, relative to it (for SVGs published
+//! on the site they link into).
+//!
+//! A `#` href always names a group (`validate` rejects any other) and
+//! links to that group's node: it is written as `#` and the node's id
+//! (see `SkillTree::anchors`), whatever `doc.links` says. Pages that don't
+//! draw the group link to the page that does instead.
+
+use crate::tree::SkillTree;
+use serde_derive::Deserialize;
+use std::borrow::Cow;
+
+/// `doc.links`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkStyle {
+    /// Leave hrefs as they are written.
+    #[default]
+    AsWritten,
+
+    /// Resolve relative hrefs against `doc.base_url`.
+    Absolute,
+
+    /// Make hrefs under `doc.base_url` relative to it.
+    Relative,
+}
+
+impl SkillTree {
+    /// `doc.links`, or `as-written` if it isn't set.
+    pub fn link_style(&self) -> LinkStyle {
+        self.doc
+            .as_ref()
+            .and_then(|doc| doc.links)
+            .unwrap_or_default()
+    }
+
+    /// `href` as it should be written into rendered output, following
+    /// `doc.links`.
+    pub fn link<'h>(&self, href: &'h str) -> Cow<'h, str> {
+        if let Some(name) = href.strip_prefix('#') {
+            return match self.group_named(name) {
+                Some(group) => Cow::Owned(format!("#{}", self.anchors().group_id(group))),
+                None => Cow::Borrowed(href),
+            };
+        }
+        let base = match self.doc.as_ref().and_then(|doc| doc.base_url.as_deref()) {
+            Some(base) => base,
+            None => return Cow::Borrowed(href),
+        };
+        match self.link_style() {
+            LinkStyle::AsWritten => Cow::Borrowed(href),
+            LinkStyle::Absolute => Cow::Owned(resolve(base, href)),
+            LinkStyle::Relative => match href.strip_prefix(directory(base)) {
+                Some(rest) if !rest.is_empty() => Cow::Owned(rest.to_string()),
+                _ => Cow::Borrowed(href),
+            },
+        }
+    }
+}
+
+/// Whether `href` starts with a scheme, like `https:` or `mailto:`.
+pub fn is_absolute(href: &str) -> bool {
+    match href.find(':') {
+        Some(colon) => {
+            let scheme = &href[..colon];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// `href` resolved against `base`, as a browser would.
+pub fn resolve(base: &str, href: &str) -> String {
+    if is_absolute(href) || href.is_empty() {
+        return href.to_string();
+    }
+    if href.starts_with('#') {
+        let base = base.split('#').next().unwrap_or(base);
+        return format!("{}{}", base, href);
+    }
+    if let Some(rest) = href.strip_prefix("//") {
+        let scheme = base.split(':').next().unwrap_or("https");
+        return format!("{}://{}", scheme, rest);
+    }
+    let origin = origin(base);
+    let path = if href.starts_with('/') {
+        href.to_string()
+    } else {
+        let directory = &directory(base)[origin.len()..];
+        let directory = if directory.is_empty() { "/" } else { directory };
+        format!("{}{}", directory, href)
+    };
+    format!("{}{}", origin, remove_dot_segments(&path))
+}
+
+/// `scheme://host` of `url`, or `""` if it has none.
+fn origin(url: &str) -> &str {
+    match url.find("://") {
+        Some(start) => {
+            let host = start + "://".len();
+            match url[host..].find('/') {
+                Some(end) => &url[..host + end],
+                None => url,
+            }
+        }
+        None => "",
+    }
+}
+
+/// `url` up to and including its last `/`, so `https://a.org/b/c.html`
+/// gives `https://a.org/b/`.
+fn directory(url: &str) -> &str {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let origin = origin(url);
+    match url[origin.len()..].rfind('/') {
+        Some(slash) => &url[..origin.len() + slash + 1],
+        None => url,
+    }
+}
+
+/// Resolves the `.` and `..` segments of `path`.
+fn remove_dot_segments(path: &str) -> String {
+    let (path, suffix) = match path.find(['?', '#']) {
+        Some(end) => path.split_at(end),
+        None => (path, ""),
+    };
+    let mut segments: Vec<&str> = vec![];
+    let parts: Vec<&str> = path.split('/').collect();
+    for (index, part) in parts.iter().enumerate() {
+        let last = index + 1 == parts.len();
+        match *part {
+            "." if last => segments.push(""),
+            "." => {}
+            ".." => {
+                if segments.len() > 1 {
+                    segments.pop();
+                }
+                if last {
+                    segments.push("");
+                }
+            }
+            part => segments.push(part),
+        }
+    }
+    format!("{}{}", segments.join("/"), suffix)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,78 @@
+use super::{is_absolute, resolve};
+use crate::SkillTree;
+
+#[test]
+fn resolves_like_a_browser() {
+    let base = "https://example.org/docs/roadmap.html";
+    for (href, expected) in [
+        ("parser.html", "https://example.org/docs/parser.html"),
+        (
+            "./parser.html#top",
+            "https://example.org/docs/parser.html#top",
+        ),
+        ("../blog/", "https://example.org/blog/"),
+        ("../../../x", "https://example.org/x"),
+        ("/issues/1", "https://example.org/issues/1"),
+        ("#parser", "https://example.org/docs/roadmap.html#parser"),
+        ("//cdn.example.org/a", "https://cdn.example.org/a"),
+        ("mailto:team@example.org", "mailto:team@example.org"),
+        ("https://github.com/a/b", "https://github.com/a/b"),
+    ] {
+        assert_eq!(resolve(base, href), expected, "{}", href);
+    }
+    assert_eq!(resolve("https://example.org", "a"), "https://example.org/a");
+}
+
+#[test]
+fn recognizes_schemes() {
+    assert!(is_absolute("https://example.org"));
+    assert!(is_absolute("mailto:a@b"));
+    assert!(!is_absolute("docs/a:b.html"));
+    assert!(!is_absolute("/a"));
+}
+
+fn tree(links: &str) -> SkillTree {
+    SkillTree::parse(&format!(
+        "[doc]\nbase_url = \"https://example.org/docs/\"\nlinks = \"{}\"\n",
+        links
+    ))
+    .unwrap()
+}
+
+#[test]
+fn link_styles() {
+    let absolute = tree("absolute");
+    assert_eq!(
+        absolute.link("parser.html"),
+        "https://example.org/docs/parser.html"
+    );
+    assert_eq!(absolute.link("https://a.org/"), "https://a.org/");
+
+    let relative = tree("relative");
+    assert_eq!(
+        relative.link("https://example.org/docs/parser.html"),
+        "parser.html"
+    );
+    assert_eq!(
+        relative.link("https://example.org/blog/"),
+        "https://example.org/blog/"
+    );
+    assert_eq!(
+        relative.link("https://example.org/docs/"),
+        "https://example.org/docs/"
+    );
+
+    let as_written = tree("as-written");
+    assert_eq!(as_written.link("parser.html"), "parser.html");
+}
+
+#[test]
+fn links_to_groups() {
+    let tree = SkillTree::parse(
+        "[doc]\nbase_url = \"https://example.org/docs/\"\nlinks = \"absolute\"\n\n\
+         [[group]]\nname = \"type checker\"\naliases = [\"typeck\"]\nitems = []\n",
+    )
+    .unwrap();
+    assert_eq!(tree.link("#type checker"), "#group-type-checker");
+    assert_eq!(tree.link("#typeck"), "#group-type-checker");
+}
@@ -0,0 +1,378 @@
+//! Named checks for things that are usually mistakes but don't stop a tree
+//! from rendering. Like rustc's lints, each has a level: `allow` ignores
+//! it, `warn` reports it among the tree's warnings, and `deny` makes it an
+//! error. Levels are set in `[doc.lints]` and can be overridden from the
+//! command line.
+
+use crate::color::{self, Rgb};
+use crate::diagnostic::{Diagnostic, Location, Severity};
+use crate::emoji;
+use crate::suggest;
+use crate::tree::{ItemExt, SkillTree};
+use serde_derive::Deserialize;
+use std::collections::HashSet;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+pub struct Lint {
+    pub name: &'static str,
+    pub default: Level,
+    pub description: &'static str,
+    check: fn(&SkillTree) -> Vec<(Location, String)>,
+}
+
+/// Every lint we know.
+pub static LINTS: &[Lint] = &[
+    Lint {
+        name: "unused-cluster",
+        default: Level::Warn,
+        description: "a `[[cluster]]` that no group is in",
+        check: unused_cluster,
+    },
+    Lint {
+        name: "orphan-group",
+        default: Level::Warn,
+        description: "a group that neither depends on nor is depended on by another group, unless it is `standalone`",
+        check: orphan_group,
+    },
+    Lint {
+        name: "missing-href",
+        default: Level::Allow,
+        description: "a group without an `href`",
+        check: missing_href,
+    },
+    Lint {
+        name: "empty-description",
+        default: Level::Allow,
+        description: "a group without a `description`",
+        check: empty_description,
+    },
+    Lint {
+        name: "unreachable-group",
+        default: Level::Warn,
+        description: "a group that no goal depends on, even indirectly",
+        check: unreachable_group,
+    },
+    Lint {
+        name: "missing-emoji",
+        default: Level::Warn,
+        description: "an item value that its column's emoji table has no entry for",
+        check: missing_emoji,
+    },
+    Lint {
+        name: "unknown-item-key",
+        default: Level::Warn,
+        description: "an item key that is not a column, `label`, `href`, `port` or `status`",
+        check: unknown_item_key,
+    },
+    Lint {
+        name: "low-contrast",
+        default: Level::Warn,
+        description: "a `header_color`, `description_color` or status color too dark to read black text on",
+        check: low_contrast,
+    },
+];
+
+/// Looks up a lint by name.
+pub fn find(name: &str) -> Option<&'static Lint> {
+    LINTS.iter().find(|lint| lint.name == name)
+}
+
+/// Runs every lint that isn't allowed in `tree`, returning the problems
+/// found. Each message ends with the lint's name, so the reader knows
+/// what to configure.
+pub fn run(tree: &SkillTree) -> Vec<Diagnostic> {
+    let mut found = vec![];
+    for lint in LINTS {
+        let level = match tree.lint_level(lint) {
+            Level::Allow => continue,
+            Level::Warn => Severity::Warning,
+            Level::Deny => Severity::Error,
+        };
+        for (location, message) in (lint.check)(tree) {
+            found.push(Diagnostic {
+                level,
+                code: Some(lint.name),
+                message: format!("{} [{}]", message, lint.name),
+                location: Some(location),
+            });
+        }
+    }
+    found
+}
+
+fn unused_cluster(tree: &SkillTree) -> Vec<(Location, String)> {
+    let used: HashSet<&str> = tree
+        .groups()
+        .filter_map(|group| group.cluster.as_deref())
+        .collect();
+    tree.cluster
+        .iter()
+        .flatten()
+        .filter(|cluster| !used.contains(&cluster.name[..]))
+        .map(|cluster| {
+            let location = match cluster.source() {
+                Some(source) => Location::from(source),
+                None => Location::Cluster(cluster.name.clone()),
+            };
+            (
+                location,
+                format!("the cluster `{}` has no groups", cluster.name),
+            )
+        })
+        .collect()
+}
+
+fn orphan_group(tree: &SkillTree) -> Vec<(Location, String)> {
+    if tree.groups().count() < 2 {
+        return vec![];
+    }
+    let mut connected: HashSet<&str> = HashSet::new();
+    for group in tree.groups() {
+        for (requirement, _) in group.dependencies() {
+            if let Some(other) = tree.group_named(requirement.group()) {
+                connected.insert(&group.name);
+                connected.insert(&other.name);
+            }
+        }
+    }
+    tree.groups()
+        .filter(|group| group.standalone != Some(true) && !connected.contains(&group.name[..]))
+        .map(|group| {
+            (
+                Location::Group(group.name.clone()),
+                format!(
+                    "the group `{}` neither requires nor is required by another group",
+                    group.name
+                ),
+            )
+        })
+        .collect()
+}
+
+fn unreachable_group(tree: &SkillTree) -> Vec<(Location, String)> {
+    let reachable = match tree.reachable_from_goals() {
+        Some(reachable) => reachable,
+        None => return vec![],
+    };
+    tree.groups()
+        .filter(|group| !reachable.contains(&group.name[..]))
+        .map(|group| {
+            (
+                Location::Group(group.name.clone()),
+                format!("no goal depends on the group `{}`", group.name),
+            )
+        })
+        .collect()
+}
+
+fn missing_href(tree: &SkillTree) -> Vec<(Location, String)> {
+    tree.groups()
+        .filter(|group| group.href.is_none())
+        .map(|group| {
+            (
+                Location::Group(group.name.clone()),
+                format!("the group `{}` has no `href`", group.name),
+            )
+        })
+        .collect()
+}
+
+fn empty_description(tree: &SkillTree) -> Vec<(Location, String)> {
+    tree.groups()
+        .filter(|group| {
+            group
+                .description
+                .iter()
+                .flatten()
+                .all(|line| line.trim().is_empty())
+        })
+        .map(|group| {
+            (
+                Location::Group(group.name.clone()),
+                format!("the group `{}` has no description", group.name),
+            )
+        })
+        .collect()
+}
+
+/// Values without an emoji are shown as text, which is usually a typo in
+/// the value or a gap in the table. Typos get the key they were probably
+/// meant to be.
+fn missing_emoji(tree: &SkillTree) -> Vec<(Location, String)> {
+    let doc_maps = tree.doc.as_ref().and_then(|doc| doc.emoji.as_ref());
+    let mut found = vec![];
+    for group in tree.groups() {
+        for column in tree.columns() {
+            let column = column.name();
+            let maps: Vec<_> = [group.emoji.as_ref(), doc_maps]
+                .iter()
+                .flatten()
+                .filter_map(|maps| maps.get(column))
+                .collect();
+            if maps.is_empty() {
+                continue;
+            }
+            for (index, item) in group.items().enumerate() {
+                let value = item.column_value(tree, group, column);
+                if value.is_empty()
+                    || maps.iter().any(|map| emoji::lookup(map, value).is_some())
+                    || tree.status_emoji(column, value).is_some()
+                {
+                    continue;
+                }
+                let mut message = format!(
+                    "item #{} in group `{}` has `{} = \"{}\"`, which has no emoji",
+                    index + 1,
+                    group.name,
+                    column,
+                    value
+                );
+                if let Some(key) = maps.iter().find_map(|map| emoji::suggest(map, value)) {
+                    message.push_str(&format!("; did you mean `{}`?", key));
+                }
+                found.push((
+                    Location::Item {
+                        group: group.name.clone(),
+                        index,
+                    },
+                    message,
+                ));
+            }
+        }
+    }
+    found
+}
+
+/// Item keys that mean something without being declared as columns.
+const ITEM_KEYS: &[&str] = &["label", "href", "port", "status"];
+
+/// Keys that aren't columns are never shown, which usually means the key
+/// or the column is misspelled.
+fn unknown_item_key(tree: &SkillTree) -> Vec<(Location, String)> {
+    let known = || {
+        ITEM_KEYS
+            .iter()
+            .copied()
+            .chain(tree.columns().iter().map(|column| column.name()))
+    };
+    let mut found = vec![];
+    for group in tree.groups() {
+        for (index, item) in group.items().enumerate() {
+            let mut keys: Vec<&String> = item
+                .keys()
+                .filter(|key| !known().any(|known| known == *key))
+                .collect();
+            keys.sort();
+            for key in keys {
+                found.push((
+                    Location::Item {
+                        group: group.name.clone(),
+                        index,
+                    },
+                    format!(
+                        "item #{} in group `{}` has the key `{}`, which is not a column{}",
+                        index + 1,
+                        group.name,
+                        key,
+                        suggest::did_you_mean(key, known()),
+                    ),
+                ));
+            }
+        }
+    }
+    found
+}
+
+/// The least contrast WCAG 2 (level AA) allows between normal text and
+/// its background.
+const MIN_CONTRAST: f64 = 4.5;
+
+/// Labels are drawn in black, so a hand-picked dark background makes them
+/// hard to read. Each status color is reported once, where it is first
+/// used. Colors we can't read (other color schemes) are skipped.
+fn low_contrast(tree: &SkillTree) -> Vec<(Location, String)> {
+    let mut found = vec![];
+    let mut check = |location: Location, what: String, color: &str| {
+        let ratio = match Rgb::parse(tree.color(color)) {
+            Some(rgb) => color::BLACK.contrast(rgb),
+            None => return,
+        };
+        if ratio < MIN_CONTRAST {
+            found.push((
+                location,
+                format!(
+                    "{} is `{}`, on which black text has a contrast ratio of {:.1}:1 \
+                     (below {}:1)",
+                    what, color, ratio, MIN_CONTRAST
+                ),
+            ));
+        }
+    };
+    let status_color = |name: &str| {
+        tree.status_definition(name)
+            .and_then(|status| status.color.as_deref())
+    };
+    let mut statuses_seen = HashSet::new();
+    for group in tree.groups() {
+        let location = || Location::Group(group.name.clone());
+        if let Some(color) = &group.header_color {
+            check(
+                location(),
+                format!("the `header_color` of group `{}`", group.name),
+                color,
+            );
+        } else if let Some(status) = &group.status {
+            if let Some(color) = status_color(status.name()) {
+                if statuses_seen.insert(status.name()) {
+                    check(
+                        location(),
+                        format!(
+                            "the color of status `{}` (on group `{}`)",
+                            status.name(),
+                            group.name
+                        ),
+                        color,
+                    );
+                }
+            }
+        }
+        if let Some(color) = &group.description_color {
+            check(
+                location(),
+                format!("the `description_color` of group `{}`", group.name),
+                color,
+            );
+        }
+        for (index, item) in group.items().enumerate() {
+            let status = item.column_value(tree, group, "status");
+            if let Some(color) = status_color(status) {
+                if statuses_seen.insert(status) {
+                    check(
+                        Location::Item {
+                            group: group.name.clone(),
+                            index,
+                        },
+                        format!(
+                            "the color of status `{}` (on item #{} in group `{}`)",
+                            status,
+                            index + 1,
+                            group.name
+                        ),
+                        color,
+                    );
+                }
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod test;
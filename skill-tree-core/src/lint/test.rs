@@ -0,0 +1,237 @@
+use super::{find, run, Level, LINTS};
+use crate::diagnostic::{Location, Severity};
+use crate::SkillTree;
+use std::path::PathBuf;
+
+const TREE: &str = r#"
+[[cluster]]
+name = "frontend"
+label = "Frontend"
+
+[[cluster]]
+name = "backend"
+label = "Backend"
+
+[[group]]
+name = "editor"
+cluster = "frontend"
+description = ["Where code is written."]
+href = "https://example.com/editor"
+requires = ["api"]
+items = []
+
+[[group]]
+name = "api"
+description = [""]
+items = []
+
+[[group]]
+name = "docs"
+items = []
+"#;
+
+fn messages(tree: &SkillTree, level: Severity) -> Vec<String> {
+    run(tree)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.level == level)
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn names_are_unique() {
+    for lint in LINTS {
+        assert!(
+            std::ptr::eq(find(lint.name).unwrap(), lint),
+            "{}",
+            lint.name
+        );
+    }
+}
+
+#[test]
+fn default_levels() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert_eq!(
+        messages(&tree, Severity::Warning),
+        [
+            "the cluster `backend` has no groups [unused-cluster]",
+            "the group `docs` neither requires nor is required by another group [orphan-group]",
+        ]
+    );
+    assert!(messages(&tree, Severity::Error).is_empty());
+}
+
+#[test]
+fn levels_from_doc() {
+    let text = format!(
+        "[doc.lints]\nunused-cluster = \"allow\"\nmissing-href = \"warn\"\nempty-description = \"deny\"\n{}",
+        TREE
+    );
+    let tree = SkillTree::parse(&text).unwrap();
+    assert_eq!(
+        messages(&tree, Severity::Warning),
+        [
+            "the group `docs` neither requires nor is required by another group [orphan-group]",
+            "the group `api` has no `href` [missing-href]",
+            "the group `docs` has no `href` [missing-href]",
+        ]
+    );
+    assert_eq!(
+        messages(&tree, Severity::Error),
+        [
+            "the group `api` has no description [empty-description]",
+            "the group `docs` has no description [empty-description]",
+        ]
+    );
+    assert_eq!(tree.validate_all().len(), 2);
+}
+
+#[test]
+fn overrides_and_unknown_lints() {
+    let mut tree = SkillTree::parse(TREE).unwrap();
+    tree.set_lint_level("orphan-group", Level::Deny).unwrap();
+    assert_eq!(
+        tree.validate().unwrap_err().to_string(),
+        "the group `docs` neither requires nor is required by another group [orphan-group]"
+    );
+    assert!(tree.set_lint_level("orphans", Level::Deny).is_err());
+
+    let tree = SkillTree::parse(&format!("[doc.lints]\norphans = \"deny\"\n{}", TREE)).unwrap();
+    assert!(tree.validate_all()[0]
+        .to_string()
+        .starts_with("unknown lint `orphans` in `doc.lints`"));
+}
+
+#[test]
+fn missing_emoji() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+columns = ["state"]
+
+[doc.emoji.state]
+"done" = "☑️"
+"wip*" = "🛠️"
+
+[[group]]
+name = "parser"
+items = [{ label = "a", state = "done" }, { label = "b", state = "dnoe" }, { label = "c" }]
+
+[[group]]
+name = "lexer"
+requires = ["parser"]
+emoji = { state = { "dnoe" = "😅" } }
+items = [{ label = "a", state = "dnoe" }, { label = "b", state = "wip-1" }]
+"#,
+    )
+    .unwrap();
+    let found = run(&tree);
+    assert_eq!(found.len(), 1, "{:?}", found);
+    assert_eq!(
+        found[0].message,
+        "item #2 in group `parser` has `state = \"dnoe\"`, which has no emoji; \
+         did you mean `done`? [missing-emoji]"
+    );
+    assert_eq!(found[0].code, Some("missing-emoji"));
+    assert_eq!(
+        found[0].location,
+        Some(Location::Item {
+            group: "parser".to_string(),
+            index: 1
+        })
+    );
+}
+
+#[test]
+fn unused_cluster_points_at_its_definition() {
+    let tree = SkillTree::load(&PathBuf::from("test-data/split_pages.toml")).unwrap();
+    let found: Vec<_> = run(&tree)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.code == Some("unused-cluster"))
+        .collect();
+    assert_eq!(found.len(), 1, "{:?}", found);
+    assert_eq!(
+        found[0].to_string(),
+        "test-data/split_pages.toml:10: the cluster `empty` has no groups [unused-cluster]"
+    );
+
+    // Without a file there is no line, so the cluster is named instead.
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert!(run(&tree)
+        .iter()
+        .any(|diagnostic| diagnostic.location == Some(Location::Cluster("backend".to_string()))));
+}
+
+#[test]
+fn standalone_groups_are_not_orphans() {
+    let tree = SkillTree::parse(
+        &TREE.replace("name = \"docs\"\n", "name = \"docs\"\nstandalone = true\n"),
+    )
+    .unwrap();
+    assert!(messages(&tree, Severity::Warning)
+        .iter()
+        .all(|message| !message.ends_with("[orphan-group]")));
+}
+
+#[test]
+fn unknown_item_key() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+columns = ["assignee"]
+
+[[group]]
+name = "parser"
+standalone = true
+items = [{ label = "a", asignee = "niko", status = "Complete", port = "a" }]
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        messages(&tree, Severity::Warning),
+        [
+            "item #1 in group `parser` has the key `asignee`, which is not a column; \
+          did you mean `assignee`? [unknown-item-key]"
+        ]
+    );
+}
+
+#[test]
+fn low_contrast() {
+    let tree = SkillTree::parse(
+        r##"
+[doc.statuses]
+Review = { color = "darkblue" }
+Parked = { color = "lightblue" }
+
+[[group]]
+name = "parser"
+standalone = true
+header_color = "#8b0000"
+description_color = "lightyellow"
+items = [
+    { label = "a", status = "Review" },
+    { label = "b", status = "Review" },
+    { label = "c", status = "Parked" },
+]
+
+[[group]]
+name = "lexer"
+standalone = true
+status = "Review"
+header_color = "/accent3/1"
+items = []
+"##,
+    )
+    .unwrap();
+    assert_eq!(
+        messages(&tree, Severity::Warning),
+        [
+            "the `header_color` of group `parser` is `#8b0000`, on which black text has \
+             a contrast ratio of 2.1:1 (below 4.5:1) [low-contrast]",
+            "the color of status `Review` (on item #1 in group `parser`) is `darkblue`, \
+             on which black text has a contrast ratio of 1.4:1 (below 4.5:1) [low-contrast]",
+        ]
+    );
+}
@@ -0,0 +1,140 @@
+//! Translations of the strings we put in rendered output (status names,
+//! page links, the HTML search box, ...) for `doc.locale`.
+//!
+//! Strings are keyed by their English text, which is also what a file's
+//! `doc.translations` table uses. Some contain `{placeholders}` that the
+//! caller fills in after translating.
+
+/// The locales we have built-in tables for.
+pub const LOCALES: &[&str] = &["de", "es", "fr"];
+
+/// Looks `text` up in the built-in table for `locale`.
+pub fn builtin(locale: &str, text: &str) -> Option<&'static str> {
+    let table = match locale {
+        "de" => DE,
+        "es" => ES,
+        "fr" => FR,
+        _ => return None,
+    };
+    table.iter().find(|(en, _)| *en == text).map(|(_, t)| *t)
+}
+
+const DE: &[(&str, &str)] = &[
+    ("Blocked", "Blockiert"),
+    ("Unassigned", "Nicht zugewiesen"),
+    ("Assigned", "In Arbeit"),
+    ("Complete", "Erledigt"),
+    ("Skill tree", "Skill-Tree"),
+    ("Other groups", "Weitere Gruppen"),
+    ("→ see {page}", "→ siehe {page}"),
+    (
+        "Search groups and items",
+        "Gruppen und Einträge durchsuchen",
+    ),
+    ("{count} match", "{count} Treffer"),
+    ("{count} matches", "{count} Treffer"),
+    ("1 group", "1 Gruppe"),
+    ("{count} groups", "{count} Gruppen"),
+    (
+        "{done}/{total} items done ({percent}%)",
+        "{done}/{total} Einträge erledigt ({percent} %)",
+    ),
+    ("Status: {status}.", "Status: {status}."),
+    (
+        "{done} of {total} items done: {items}.",
+        "{done} von {total} Einträgen erledigt: {items}.",
+    ),
+    ("Requires {groups}.", "Benötigt {groups}."),
+    ("Required by {groups}.", "Benötigt von {groups}."),
+    (
+        "Shown on another page; follow the link to see it.",
+        "Auf einer anderen Seite; folge dem Link, um sie zu sehen.",
+    ),
+    ("No status", "Ohne Status"),
+    ("{done}/{total} done", "{done}/{total} erledigt"),
+    ("Cluster", "Bereich"),
+    ("Groups", "Gruppen"),
+    ("Items done", "Erledigte Einträge"),
+    ("Total", "Gesamt"),
+    ("Contents", "Inhalt"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("Blocked", "Bloqueado"),
+    ("Unassigned", "Sin asignar"),
+    ("Assigned", "En curso"),
+    ("Complete", "Completado"),
+    ("Skill tree", "Árbol de habilidades"),
+    ("Other groups", "Otros grupos"),
+    ("→ see {page}", "→ ver {page}"),
+    ("Search groups and items", "Buscar grupos y elementos"),
+    ("{count} match", "{count} resultado"),
+    ("{count} matches", "{count} resultados"),
+    ("1 group", "1 grupo"),
+    ("{count} groups", "{count} grupos"),
+    (
+        "{done}/{total} items done ({percent}%)",
+        "{done}/{total} elementos completados ({percent} %)",
+    ),
+    ("Status: {status}.", "Estado: {status}."),
+    (
+        "{done} of {total} items done: {items}.",
+        "{done} de {total} elementos completados: {items}.",
+    ),
+    ("Requires {groups}.", "Requiere {groups}."),
+    ("Required by {groups}.", "Requerido por {groups}."),
+    (
+        "Shown on another page; follow the link to see it.",
+        "Está en otra página; sigue el enlace para verlo.",
+    ),
+    ("No status", "Sin estado"),
+    ("{done}/{total} done", "{done}/{total} completados"),
+    ("Cluster", "Área"),
+    ("Groups", "Grupos"),
+    ("Items done", "Elementos completados"),
+    ("Total", "Total"),
+    ("Contents", "Contenido"),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("Blocked", "Bloqué"),
+    ("Unassigned", "Non attribué"),
+    ("Assigned", "En cours"),
+    ("Complete", "Terminé"),
+    ("Skill tree", "Arbre de compétences"),
+    ("Other groups", "Autres groupes"),
+    ("→ see {page}", "→ voir {page}"),
+    (
+        "Search groups and items",
+        "Rechercher des groupes et des éléments",
+    ),
+    ("{count} match", "{count} résultat"),
+    ("{count} matches", "{count} résultats"),
+    ("1 group", "1 groupe"),
+    ("{count} groups", "{count} groupes"),
+    (
+        "{done}/{total} items done ({percent}%)",
+        "{done}/{total} éléments terminés ({percent} %)",
+    ),
+    ("Status: {status}.", "Statut : {status}."),
+    (
+        "{done} of {total} items done: {items}.",
+        "{done} sur {total} éléments terminés : {items}.",
+    ),
+    ("Requires {groups}.", "Nécessite {groups}."),
+    ("Required by {groups}.", "Nécessaire pour {groups}."),
+    (
+        "Shown on another page; follow the link to see it.",
+        "Sur une autre page ; suivez le lien pour le voir.",
+    ),
+    ("No status", "Sans statut"),
+    ("{done}/{total} done", "{done}/{total} terminés"),
+    ("Cluster", "Domaine"),
+    ("Groups", "Groupes"),
+    ("Items done", "Éléments terminés"),
+    ("Total", "Total"),
+    ("Contents", "Sommaire"),
+];
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,43 @@
+use super::{builtin, DE, ES, FR};
+
+#[test]
+fn tables_cover_the_same_strings() {
+    fn keys(table: &[(&'static str, &'static str)]) -> Vec<&'static str> {
+        table.iter().map(|(en, _)| *en).collect()
+    }
+    assert_eq!(keys(DE), keys(ES));
+    assert_eq!(keys(DE), keys(FR));
+}
+
+#[test]
+fn translations_keep_placeholders() {
+    for table in [DE, ES, FR] {
+        for (en, translated) in table {
+            for placeholder in [
+                "{page}",
+                "{count}",
+                "{done}",
+                "{total}",
+                "{percent}",
+                "{status}",
+                "{items}",
+                "{groups}",
+            ] {
+                assert_eq!(
+                    en.contains(placeholder),
+                    translated.contains(placeholder),
+                    "{} / {}",
+                    en,
+                    translated
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn looks_up_builtin_strings() {
+    assert_eq!(builtin("de", "Complete"), Some("Erledigt"));
+    assert_eq!(builtin("de", "not a built-in string"), None);
+    assert_eq!(builtin("tlh", "Complete"), None);
+}
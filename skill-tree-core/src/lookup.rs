@@ -0,0 +1,87 @@
+//! Lookup tables for rendering item cells. `ItemExt::column_value` and
+//! `SkillTree::group_emoji` walk `doc.defaults` and the emoji tables
+//! (trying every pattern) for each cell they are asked about, which adds
+//! up on large trees. `SkillTree::cell_lookup` resolves each distinct
+//! value once instead. The tables borrow the tree, so they can't go stale
+//! when it is changed after loading (by `--set`, say).
+
+use crate::emoji;
+use crate::tree::{Group, Item, SkillTree};
+use std::collections::HashMap;
+
+pub struct CellLookup<'t> {
+    tree: &'t SkillTree,
+
+    /// `doc.defaults`, by cluster (`None` for the document-wide ones) and
+    /// column.
+    defaults: HashMap<(Option<&'t str>, &'t str), &'t str>,
+
+    /// What `SkillTree::emoji` gives for each value in the tree, by column
+    /// and then value.
+    emoji: HashMap<&'t str, HashMap<&'t str, &'t str>>,
+}
+
+impl SkillTree {
+    /// Builds the lookup tables for the cells of this tree.
+    pub fn cell_lookup(&self) -> CellLookup<'_> {
+        let mut defaults = HashMap::new();
+        if let Some(doc_defaults) = self.doc.as_ref().and_then(|doc| doc.defaults.as_ref()) {
+            for (column, value) in &doc_defaults.columns {
+                defaults.insert((None, &column[..]), &value[..]);
+            }
+            for (cluster, values) in &doc_defaults.cluster {
+                for (column, value) in values {
+                    defaults.insert((Some(&cluster[..]), &column[..]), &value[..]);
+                }
+            }
+        }
+        let mut lookup = CellLookup {
+            tree: self,
+            defaults,
+            emoji: HashMap::new(),
+        };
+        for column in self.columns() {
+            let mut emoji = HashMap::new();
+            for group in self.groups() {
+                for item in group.items() {
+                    let value = lookup.value(group, item, column.name());
+                    emoji
+                        .entry(value)
+                        .or_insert_with(|| self.emoji(column.name(), value));
+                }
+            }
+            lookup.emoji.insert(column.name(), emoji);
+        }
+        lookup
+    }
+}
+
+impl<'t> CellLookup<'t> {
+    /// Like `ItemExt::column_value`.
+    pub fn value(&self, group: &'t Group, item: &'t Item, column: &str) -> &'t str {
+        if let Some(value) = item.get(column) {
+            return value;
+        }
+        let cluster = group.cluster.as_deref();
+        self.defaults
+            .get(&(cluster, column))
+            .or_else(|| self.defaults.get(&(None, column)))
+            .copied()
+            .unwrap_or("")
+    }
+
+    /// Like `SkillTree::group_emoji`.
+    pub fn emoji(&self, group: &'t Group, column: &str, value: &'t str) -> &'t str {
+        let group_map = group.emoji.as_ref().and_then(|maps| maps.get(column));
+        if let Some(output) = group_map.and_then(|map| emoji::lookup(map, value)) {
+            return output;
+        }
+        match self.emoji.get(column).and_then(|values| values.get(value)) {
+            Some(output) => output,
+            None => self.tree.emoji(column, value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
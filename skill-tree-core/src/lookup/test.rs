@@ -0,0 +1,56 @@
+use crate::{ItemExt, SkillTree};
+
+#[test]
+fn agrees_with_the_tree() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+columns = ["status", "progress"]
+defaults = { status = "todo", cluster = { back = { progress = "60" } } }
+
+[doc.emoji.status]
+"todo" = "⬜"
+"done" = "☑️"
+"wip*" = "🛠️"
+
+[doc.emoji.progress]
+">=50" = "🌕"
+"<50" = "🌑"
+
+[[group]]
+name = "parser"
+emoji = { status = { "todo" = "📝" } }
+items = [
+    { label = "a", status = "done", progress = "80" },
+    { label = "b", progress = "10%" },
+    { label = "c", status = "wip-2", progress = "n/a" },
+]
+
+[[cluster]]
+name = "back"
+label = "Back end"
+
+[[group]]
+name = "lexer"
+cluster = "back"
+items = [{ label = "d" }, { label = "e", status = "??" }]
+"#,
+    )
+    .unwrap();
+    let lookup = tree.cell_lookup();
+    for group in tree.groups() {
+        for item in group.items() {
+            for column in ["status", "progress", "label", "missing"] {
+                let value = item.column_value(&tree, group, column);
+                assert_eq!(lookup.value(group, item, column), value);
+                assert_eq!(
+                    lookup.emoji(group, column, value),
+                    tree.group_emoji(group, column, value),
+                    "{} {}",
+                    group.name,
+                    column
+                );
+            }
+        }
+    }
+}
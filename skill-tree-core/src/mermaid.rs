@@ -0,0 +1,146 @@
+//! The tree as a Mermaid `flowchart`, which GitHub and many other Markdown
+//! viewers draw from a fenced code block: the renderer `mermaid` (see
+//! `Renderers`). Mermaid can't draw the item tables, so each group is a
+//! box with its label and how many of its items are done.
+
+use crate::render::Renderer;
+use crate::tree::{Cluster, EdgeKind, Group, SkillTree};
+use crate::visit::{Edge, TreeVisitor};
+use std::collections::HashMap;
+use std::io::Write;
+
+pub struct Mermaid;
+
+impl Renderer for Mermaid {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        let mut flowchart = Flowchart {
+            tree,
+            lines: vec![],
+            ids: HashMap::new(),
+            clusters: 0,
+            cluster: None,
+            done: vec![],
+            links: vec![],
+        };
+        tree.walk(&mut flowchart);
+        flowchart.end_cluster();
+
+        let rankdir = tree.graphviz.as_ref().and_then(|g| g.rankdir);
+        writeln!(
+            output,
+            "flowchart {}",
+            rankdir.unwrap_or_default().mermaid()
+        )?;
+        for line in flowchart.lines.iter().chain(&flowchart.links) {
+            writeln!(output, "{}", line)?;
+        }
+        if !flowchart.done.is_empty() {
+            writeln!(output, "    classDef done fill:#d4f4d4,stroke:#2e7d32")?;
+            writeln!(output, "    class {} done", flowchart.done.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+struct Flowchart<'t> {
+    tree: &'t SkillTree,
+    lines: Vec<String>,
+
+    /// Node ids by group name. Group names can be anything, and some
+    /// (like `end`) are Mermaid keywords, so nodes are numbered instead.
+    ids: HashMap<&'t str, String>,
+
+    /// How many `subgraph`s there have been, to number the next one.
+    clusters: usize,
+
+    /// The cluster whose `subgraph` is open.
+    cluster: Option<&'t str>,
+
+    /// The ids of groups whose items are all done.
+    done: Vec<String>,
+
+    /// `click` lines for groups with an `href`.
+    links: Vec<String>,
+}
+
+impl Flowchart<'_> {
+    fn end_cluster(&mut self) {
+        if self.cluster.take().is_some() {
+            self.lines.push("    end".to_string());
+        }
+    }
+}
+
+impl<'t> TreeVisitor<'t> for Flowchart<'t> {
+    fn visit_cluster(&mut self, cluster: &'t Cluster) {
+        self.end_cluster();
+        self.lines.push(format!(
+            "    subgraph c{}[\"{}\"]",
+            self.clusters,
+            escape(&cluster.label)
+        ));
+        self.clusters += 1;
+        self.cluster = Some(&cluster.name);
+    }
+
+    fn visit_group(&mut self, group: &'t Group) {
+        if self.cluster.is_some() && group.cluster.as_deref() != self.cluster {
+            self.end_cluster();
+        }
+        let id = format!("g{}", self.ids.len());
+        let indent = if self.cluster.is_some() {
+            "        "
+        } else {
+            "    "
+        };
+        let mut label = escape(group.label.as_ref().unwrap_or(&group.name));
+        let (done, total) = group.progress(self.tree);
+        if total > 0 {
+            let progress = self
+                .tree
+                .translate("{done}/{total} done")
+                .replace("{done}", &done.to_string())
+                .replace("{total}", &total.to_string());
+            label.push_str("<br/>");
+            label.push_str(&escape(&progress));
+            if done == total {
+                self.done.push(id.clone());
+            }
+        }
+        self.lines.push(format!("{}{}[\"{}\"]", indent, id, label));
+        if let Some(href) = &group.href {
+            self.links.push(format!(
+                "    click {} href \"{}\"",
+                id,
+                escape(&self.tree.link(href))
+            ));
+        }
+        self.ids.insert(&group.name, id);
+    }
+
+    fn visit_edge(&mut self, edge: &Edge<'t>) {
+        self.end_cluster();
+        let arrow = match edge.kind {
+            EdgeKind::Hard => "-->",
+            EdgeKind::Soft => "-.->",
+        };
+        self.lines.push(format!(
+            "    {} {} {}",
+            self.ids[&edge.from.name[..]],
+            arrow,
+            self.ids[&edge.to.name[..]]
+        ));
+    }
+}
+
+/// `text` for a quoted Mermaid string, which can't hold `"` and reads
+/// `<` and `>` as HTML.
+fn escape(text: &str) -> String {
+    text.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod test;
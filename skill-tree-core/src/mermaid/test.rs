@@ -0,0 +1,80 @@
+use crate::render::Renderers;
+use crate::SkillTree;
+
+#[test]
+fn flowchart() {
+    let tree = SkillTree::parse(
+        r##"
+[graphviz]
+rankdir = "LR"
+
+[[cluster]]
+name = "frontend"
+label = "Front \"end\""
+
+[[group]]
+name = "lexer"
+cluster = "frontend"
+href = "https://example.org/lexer"
+items = [{ label = "tokens", status = "Complete" }]
+
+[[group]]
+name = "parser"
+label = "Parser <#1>"
+cluster = "frontend"
+requires = ["lexer"]
+items = [{ label = "exprs", status = "Complete" }, { label = "stmts" }]
+
+[[group]]
+name = "end"
+requires = ["parser"]
+suggests = ["lexer"]
+items = []
+"##,
+    )
+    .unwrap();
+    assert_eq!(
+        Renderers::default()
+            .render_to_string("mermaid", &tree)
+            .unwrap(),
+        r#"flowchart LR
+    subgraph c0["Front #quot;end#quot;"]
+        g0["lexer<br/>1/1 done"]
+        g1["Parser #lt;#35;1#gt;<br/>1/2 done"]
+    end
+    g2["end"]
+    g0 --> g1
+    g1 --> g2
+    g0 -.-> g2
+    click g0 href "https://example.org/lexer"
+    classDef done fill:#d4f4d4,stroke:#2e7d32
+    class g0 done
+"#
+    );
+}
+
+#[test]
+fn goals_first() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+goals = ["parser"]
+
+[[group]]
+name = "lexer"
+items = []
+
+[[group]]
+name = "parser"
+requires = ["lexer"]
+items = []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Renderers::default()
+            .render_to_string("mermaid", &tree)
+            .unwrap(),
+        "flowchart LR\n    g0[\"parser\"]\n    g1[\"lexer\"]\n    g1 --> g0\n"
+    );
+}
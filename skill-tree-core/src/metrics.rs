@@ -0,0 +1,148 @@
+//! `SkillTree::metrics`: numbers about the shape of the dependency graph,
+//! e.g. for ranking which groups block the most work. Only `requires`
+//! edges count, as `suggests` don't block anything; dependencies on
+//! groups that don't exist are ignored.
+
+use crate::tree::{EdgeKind, SkillTree};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct Metrics<'t> {
+    /// One entry per group, in the order the groups are declared.
+    pub groups: Vec<GroupMetrics<'t>>,
+
+    /// The longest chain of groups each requiring the one before, starting
+    /// from a group that requires nothing. Empty if there are no groups.
+    pub longest_path: Vec<&'t str>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GroupMetrics<'t> {
+    pub name: &'t str,
+
+    /// How many groups this one requires.
+    pub in_degree: usize,
+
+    /// How many groups require this one.
+    pub out_degree: usize,
+
+    /// The length of the longest chain of requirements below this group:
+    /// 0 if it requires nothing.
+    pub depth: usize,
+
+    /// How many groups require this one, directly or through others.
+    pub dependents: usize,
+}
+
+impl<'t> Metrics<'t> {
+    pub fn group(&self, name: &str) -> Option<&GroupMetrics<'t>> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+}
+
+impl SkillTree {
+    /// Works out the `Metrics` of this tree. Dependency cycles are broken
+    /// where the search first comes back to a group.
+    pub fn metrics(&self) -> Metrics<'_> {
+        // The groups each group requires, and is required by.
+        let mut requires: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut required_by: HashMap<&str, Vec<&str>> = HashMap::new();
+        let names: Vec<&str> = self.groups().map(|group| &group.name[..]).collect();
+        for &name in &names {
+            requires.entry(name).or_default();
+        }
+        for edge in self.edges() {
+            if edge.kind != EdgeKind::Hard {
+                continue;
+            }
+            let (name, required) = (names[edge.from.0], names[edge.to.0]);
+            let list = requires.get_mut(name).unwrap();
+            if !list.contains(&required) {
+                list.push(required);
+                required_by.entry(required).or_default().push(name);
+            }
+        }
+
+        let mut depths = HashMap::new();
+        for group in self.groups() {
+            depth(&group.name, &requires, &mut depths);
+        }
+
+        let groups: Vec<GroupMetrics<'_>> = self
+            .groups()
+            .map(|group| {
+                let name = &group.name[..];
+                GroupMetrics {
+                    name,
+                    in_degree: requires[name].len(),
+                    out_degree: required_by.get(name).map_or(0, Vec::len),
+                    depth: depths[name].map_or(0, |(depth, _)| depth),
+                    dependents: dependents(name, &required_by),
+                }
+            })
+            .collect();
+
+        // Walk down from the deepest group (the first, if several are as
+        // deep), then turn the chain around.
+        let mut longest_path = vec![];
+        let mut next = groups
+            .iter()
+            .rev()
+            .max_by_key(|group| group.depth)
+            .map(|group| group.name);
+        while let Some(name) = next {
+            longest_path.push(name);
+            next = depths[name].and_then(|(_, below)| below);
+        }
+        longest_path.reverse();
+
+        Metrics {
+            groups,
+            longest_path,
+        }
+    }
+}
+
+/// The depth of `name` (see `GroupMetrics::depth`) and the requirement
+/// its longest chain goes through. `depths` maps a name to `None` while
+/// its depth is being worked out, which breaks cycles.
+fn depth<'t>(
+    name: &'t str,
+    requires: &HashMap<&'t str, Vec<&'t str>>,
+    depths: &mut HashMap<&'t str, Option<(usize, Option<&'t str>)>>,
+) -> usize {
+    if let Some(known) = depths.get(name) {
+        return known.map_or(0, |(depth, _)| depth);
+    }
+    depths.insert(name, None);
+    let mut deepest = (0, None);
+    for &required in &requires[name] {
+        if depths.get(required) == Some(&None) {
+            continue;
+        }
+        let below = depth(required, requires, depths) + 1;
+        if below > deepest.0 {
+            deepest = (below, Some(required));
+        }
+    }
+    depths.insert(name, Some(deepest));
+    deepest.0
+}
+
+/// How many groups require `name`, directly or not.
+fn dependents(name: &str, required_by: &HashMap<&str, Vec<&str>>) -> usize {
+    let mut seen = HashSet::new();
+    let mut stack = vec![name];
+    while let Some(name) = stack.pop() {
+        for &dependent in required_by.get(name).into_iter().flatten() {
+            if seen.insert(dependent) {
+                stack.push(dependent);
+            }
+        }
+    }
+    seen.remove(name);
+    seen.len()
+}
+
+#[cfg(test)]
+mod test;
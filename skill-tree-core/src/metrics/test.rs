@@ -0,0 +1,87 @@
+use super::GroupMetrics;
+use crate::SkillTree;
+
+#[test]
+fn metrics() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "lexer"
+items = []
+
+[[group]]
+name = "parser"
+requires = ["lexer", "lexer:tokens"]
+items = []
+
+[[group]]
+name = "types"
+requires = ["parser", "lexer", "missing"]
+items = []
+
+[[group]]
+name = "docs"
+requires = ["lexer"]
+suggests = ["types"]
+items = []
+
+[[group]]
+name = "codegen"
+requires = ["types"]
+items = []
+"#,
+    )
+    .unwrap();
+    let metrics = tree.metrics();
+    assert_eq!(
+        metrics.group("lexer"),
+        Some(&GroupMetrics {
+            name: "lexer",
+            in_degree: 0,
+            out_degree: 3,
+            depth: 0,
+            dependents: 4,
+        })
+    );
+    assert_eq!(
+        metrics.group("types"),
+        Some(&GroupMetrics {
+            name: "types",
+            in_degree: 2,
+            out_degree: 1,
+            depth: 2,
+            dependents: 1,
+        })
+    );
+    assert_eq!(metrics.group("docs").unwrap().dependents, 0);
+    assert_eq!(
+        metrics.longest_path,
+        ["lexer", "parser", "types", "codegen"]
+    );
+}
+
+#[test]
+fn cycles() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "a"
+requires = ["c"]
+items = []
+
+[[group]]
+name = "b"
+requires = ["a"]
+items = []
+
+[[group]]
+name = "c"
+requires = ["b"]
+items = []
+"#,
+    )
+    .unwrap();
+    let metrics = tree.metrics();
+    assert_eq!(metrics.group("a").unwrap().dependents, 2);
+    assert_eq!(metrics.longest_path, ["b", "c", "a"]);
+}
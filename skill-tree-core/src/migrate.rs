@@ -0,0 +1,126 @@
+//! Rewriting files written for older versions of the schema.
+//!
+//! Version history:
+//!
+//! 1. The original format.
+//! 2. Ports in `requires` are written `{ group = "g", port = "p" }` rather
+//!    than `"g:p"`.
+
+use crate::tree::{Requirement, SkillTree};
+use fehler::throws;
+use toml_edit::{DocumentMut, InlineTable, Item, Table, Value};
+
+/// The schema version written by `migrate`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Rewrites `text` to use the current schema, stamping it with
+/// `doc.schema_version`. Only the values that changed are touched, so
+/// comments and formatting survive, and a file that needs no changes is
+/// returned as it is. Included files are not followed.
+#[throws(anyhow::Error)]
+pub fn migrate(text: &str) -> String {
+    let tree = SkillTree::parse(text)?;
+    let version = tree.schema_version();
+    if version > SCHEMA_VERSION {
+        anyhow::bail!(
+            "cannot migrate from schema version {}, which is newer than {}",
+            version,
+            SCHEMA_VERSION,
+        );
+    }
+
+    let mut document: DocumentMut = text.parse()?;
+    let mut changed = false;
+    if version < 2 {
+        changed |= structure_ports(&mut document);
+    }
+    if !changed {
+        return text.to_string();
+    }
+    set_schema_version(&mut document);
+    document.to_string()
+}
+
+/// Rewrites each `"group:port"` in the `requires` and `suggests` of the
+/// groups as `{ group = "group", port = "port" }`, returning whether there
+/// were any.
+fn structure_ports(document: &mut DocumentMut) -> bool {
+    let groups = match document
+        .get_mut("group")
+        .and_then(|groups| groups.as_array_of_tables_mut())
+    {
+        Some(groups) => groups,
+        None => return false,
+    };
+    let mut changed = false;
+    for group in groups.iter_mut() {
+        for key in ["requires", "suggests"] {
+            let requirements = match group.get_mut(key).and_then(|r| r.as_array_mut()) {
+                Some(requirements) => requirements,
+                None => continue,
+            };
+            for value in requirements.iter_mut() {
+                let requirement = match value.as_str() {
+                    Some(name) => Requirement::Name(name.to_string()),
+                    None => continue,
+                };
+                let port = match requirement.port() {
+                    Some(port) if requirement.tree().is_none() => port,
+                    _ => continue,
+                };
+                let mut structured = InlineTable::new();
+                structured.insert("group", requirement.group().into());
+                structured.insert("port", port.into());
+                structured.fmt();
+                let decor = value.decor().clone();
+                *value = Value::InlineTable(structured);
+                *value.decor_mut() = decor;
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// Sets `schema_version` in the `[doc]` table, adding the table at the
+/// top of the file if there is none.
+fn set_schema_version(document: &mut DocumentMut) {
+    let version = toml_edit::value(i64::from(SCHEMA_VERSION));
+    if let Some(doc) = document
+        .get_mut("doc")
+        .and_then(|doc| doc.as_table_like_mut())
+    {
+        doc.insert("schema_version", version);
+        return;
+    }
+
+    // Keep a blank line between the new table and what used to come first.
+    if let Some(first) = first_table(document) {
+        let prefix = first
+            .decor()
+            .prefix()
+            .and_then(|p| p.as_str())
+            .unwrap_or("");
+        let prefix = format!("\n{}", prefix);
+        first.decor_mut().set_prefix(prefix);
+    }
+    let mut doc = Table::new();
+    doc.set_position(0);
+    doc.insert("schema_version", version);
+    document.insert("doc", Item::Table(doc));
+}
+
+/// The table that comes first in the file.
+fn first_table(document: &mut DocumentMut) -> Option<&mut Table> {
+    document
+        .iter_mut()
+        .filter_map(|(_, item)| match item {
+            Item::Table(table) => Some(table),
+            Item::ArrayOfTables(tables) => tables.iter_mut().next(),
+            _ => None,
+        })
+        .min_by_key(|table| table.position())
+}
+
+#[cfg(test)]
+mod test;
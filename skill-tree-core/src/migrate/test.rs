@@ -0,0 +1,96 @@
+use super::migrate;
+
+#[test]
+fn rewrites_port_requires() {
+    let text = r#"# Our tree
+[[group]]
+name = "lexer"
+items = [{ label = "Tokens", port = "tokens" }]
+
+[[group]]
+name = "parser"
+requires = ["lexer:tokens"] # keep this comment
+items = []
+"#;
+
+    let expected = r#"[doc]
+schema_version = 2
+
+# Our tree
+[[group]]
+name = "lexer"
+items = [{ label = "Tokens", port = "tokens" }]
+
+[[group]]
+name = "parser"
+requires = [{ group = "lexer", port = "tokens" }] # keep this comment
+items = []
+"#;
+
+    assert_eq!(migrate(text).unwrap(), expected);
+    assert_eq!(migrate(expected).unwrap(), expected);
+}
+
+#[test]
+fn updates_existing_doc_table() {
+    let text = r#"[doc]
+columns = ["team"]
+schema_version = 1
+
+[doc.defaults]
+team = "async"
+
+[[group]]
+name = "parser"
+suggests = [
+    "lexer",
+    "lexer:tokens", # for spans
+]
+items = [{ label = "Spans", port = "tokens" }]
+
+[[group]]
+name = "lexer"
+items = [{ label = "Tokens", port = "tokens" }]
+"#;
+
+    let expected = r#"[doc]
+columns = ["team"]
+schema_version = 2
+
+[doc.defaults]
+team = "async"
+
+[[group]]
+name = "parser"
+suggests = [
+    "lexer",
+    { group = "lexer", port = "tokens" }, # for spans
+]
+items = [{ label = "Spans", port = "tokens" }]
+
+[[group]]
+name = "lexer"
+items = [{ label = "Tokens", port = "tokens" }]
+"#;
+
+    assert_eq!(migrate(text).unwrap(), expected);
+}
+
+#[test]
+fn leaves_current_files_alone() {
+    for text in [
+        "# No ports here\n[[group]]\nname = \"lexer\"\nitems = []\n",
+        "[doc]\nschema_version = 1\n\n[[group]]\nname = \"lexer\"\nitems = []\n",
+    ] {
+        assert_eq!(migrate(text).unwrap(), text);
+    }
+}
+
+#[test]
+fn refuses_newer_schema() {
+    let error = migrate("[doc]\nschema_version = 99\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "cannot migrate from schema version 99, which is newer than 2"
+    );
+}
@@ -0,0 +1,42 @@
+//! Statuses kept outside the tree's files, such as whether a linked pull
+//! request was merged. A `StatusProvider` looks up the status of whatever
+//! an item's `href` points at, and `SkillTree::apply_statuses` writes what
+//! it finds into the items, where it counts towards progress like a
+//! status written by hand.
+
+use crate::tree::{SkillTree, Status};
+use fehler::throws;
+
+pub trait StatusProvider {
+    /// The status of what `href` points at, or `None` to leave the item's
+    /// status as it is (say, for links the provider doesn't know).
+    fn status(&mut self, href: &str) -> anyhow::Result<Option<Status>>;
+}
+
+impl SkillTree {
+    /// Sets the `status` of each item with an `href` to what `provider`
+    /// says, returning how many items changed.
+    #[throws(anyhow::Error)]
+    pub fn apply_statuses(&mut self, provider: &mut dyn StatusProvider) -> usize {
+        let mut changed = 0;
+        for group in self.group.iter_mut().flatten() {
+            for item in &mut group.items {
+                let status = match item.get("href") {
+                    Some(href) => provider.status(href)?,
+                    None => None,
+                };
+                if let Some(status) = status {
+                    let name = status.name();
+                    if item.get("status").map(String::as_str) != Some(name) {
+                        item.insert("status".to_string(), name.to_string());
+                        changed += 1;
+                    }
+                }
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test;
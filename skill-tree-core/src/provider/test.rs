@@ -0,0 +1,43 @@
+use super::StatusProvider;
+use crate::{SkillTree, Status};
+
+/// Links ending in `/merged` are complete.
+struct Merged;
+
+impl StatusProvider for Merged {
+    fn status(&mut self, href: &str) -> anyhow::Result<Option<Status>> {
+        Ok(if href.ends_with("/merged") {
+            Some(Status::Complete)
+        } else {
+            None
+        })
+    }
+}
+
+#[test]
+fn apply_statuses() {
+    let mut tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "parser"
+items = [
+    { label = "exprs", href = "https://example.org/1/merged", status = "Assigned" },
+    { label = "stmts", href = "https://example.org/2/open", status = "Assigned" },
+    { label = "macros", href = "https://example.org/3/merged", status = "Complete" },
+    { label = "docs" },
+]
+"#,
+    )
+    .unwrap();
+    assert_eq!(tree.apply_statuses(&mut Merged).unwrap(), 1);
+    let group = tree.groups().next().unwrap();
+    let statuses: Vec<_> = group
+        .items()
+        .map(|item| item.get("status").map(String::as_str))
+        .collect();
+    assert_eq!(
+        statuses,
+        [Some("Complete"), Some("Assigned"), Some("Complete"), None]
+    );
+    assert_eq!(group.progress(&tree), (2, 4));
+}
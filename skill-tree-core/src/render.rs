@@ -0,0 +1,87 @@
+//! Output formats, looked up by name. The graphviz `dot` generator is the
+//! one built in; tools built on this crate can register their own (say,
+//! a format some other tool of theirs reads) alongside it, and pick
+//! between them by name the way `skill-tree render --emit` does.
+
+use crate::export::{AdjacencyMatrix, EdgeList};
+use crate::mermaid::Mermaid;
+use crate::suggest;
+use crate::tree::SkillTree;
+use fehler::throws;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+#[cfg(feature = "graphviz")]
+pub use crate::graphviz::Dot;
+
+/// Turns a tree into some output format.
+pub trait Renderer {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()>;
+}
+
+/// Renderers by name.
+pub struct Renderers {
+    by_name: BTreeMap<String, Box<dyn Renderer>>,
+}
+
+impl Renderers {
+    /// A registry without any renderers, not even the built-in ones.
+    pub fn empty() -> Renderers {
+        Renderers {
+            by_name: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `renderer` under `name`, replacing any renderer already
+    /// registered under it.
+    pub fn register(&mut self, name: impl Into<String>, renderer: impl Renderer + 'static) {
+        self.by_name.insert(name.into(), Box::new(renderer));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Renderer> {
+        self.by_name.get(name).map(|renderer| &**renderer)
+    }
+
+    /// The names of the renderers, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.by_name.keys().map(String::as_str)
+    }
+
+    /// Renders `tree` with the renderer registered as `name`.
+    #[throws(anyhow::Error)]
+    pub fn render(&self, name: &str, tree: &SkillTree, output: &mut dyn Write) {
+        match self.get(name) {
+            Some(renderer) => renderer.render(tree, output)?,
+            None => anyhow::bail!(
+                "no renderer is registered as `{}`{}",
+                name,
+                suggest::did_you_mean(name, self.names())
+            ),
+        }
+    }
+
+    /// Like `render`, but returns the output as a string.
+    #[throws(anyhow::Error)]
+    pub fn render_to_string(&self, name: &str, tree: &SkillTree) -> String {
+        let mut output = Vec::new();
+        self.render(name, tree, &mut output)?;
+        String::from_utf8(output)?
+    }
+}
+
+/// The built-in renderers: `dot` (with the `graphviz` feature), and the
+/// CSV exports `edges` and `adjacency`.
+impl Default for Renderers {
+    fn default() -> Renderers {
+        let mut renderers = Renderers::empty();
+        renderers.register("edges", EdgeList);
+        renderers.register("adjacency", AdjacencyMatrix);
+        renderers.register("mermaid", Mermaid);
+        #[cfg(feature = "graphviz")]
+        renderers.register("dot", Dot);
+        renderers
+    }
+}
+
+#[cfg(test)]
+mod test;
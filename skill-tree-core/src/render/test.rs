@@ -0,0 +1,62 @@
+use super::{Renderer, Renderers};
+use crate::SkillTree;
+use std::io::Write;
+
+const TREE: &str = r#"
+[[group]]
+name = "parser"
+items = [{ label = "lex" }, { label = "parse" }]
+
+[[group]]
+name = "types"
+requires = ["parser"]
+items = [{ label = "check" }]
+"#;
+
+/// One line per group: its name and how many items it has.
+struct Outline;
+
+impl Renderer for Outline {
+    fn render(&self, tree: &SkillTree, output: &mut dyn Write) -> anyhow::Result<()> {
+        for group in tree.groups() {
+            writeln!(output, "{} {}", group.name, group.items.len())?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_renderer() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let mut renderers = Renderers::empty();
+    renderers.register("outline", Outline);
+    renderers.register("lines", Outline);
+    assert_eq!(
+        renderers.render_to_string("outline", &tree).unwrap(),
+        "parser 2\ntypes 1\n"
+    );
+    assert_eq!(renderers.names().collect::<Vec<_>>(), ["lines", "outline"]);
+}
+
+#[test]
+#[cfg(feature = "graphviz")]
+fn dot_is_built_in() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    assert_eq!(
+        Renderers::default().render_to_string("dot", &tree).unwrap(),
+        tree.to_graphviz().unwrap()
+    );
+    assert!(Renderers::empty().get("dot").is_none());
+}
+
+#[test]
+fn unknown_renderer() {
+    let tree = SkillTree::parse(TREE).unwrap();
+    let mut renderers = Renderers::empty();
+    renderers.register("outline", Outline);
+    let error = renderers.render_to_string("outlin", &tree).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "no renderer is registered as `outlin`; did you mean `outline`?"
+    );
+}
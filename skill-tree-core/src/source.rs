@@ -0,0 +1,84 @@
+//! Helpers for finding things in the text of a skill-tree file, and
+//! `Source`, which records where something came from.
+//!
+//! The TOML parser doesn't give us spans, so these scan the source line by
+//! line. They only understand the simple `key = "value"` layout used by
+//! hand-written trees.
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// The file, and line (1-based) in it, that a group, cluster or column
+/// was read from. Displayed as `path:line`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Source {
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)
+    }
+}
+
+/// Finds the (0-based) line declaring `name = "<name>"` inside a
+/// `[[group]]` table.
+pub fn group_definition_line(text: &str, name: &str) -> Option<usize> {
+    definition_line(text, "group", name)
+}
+
+/// Finds the (0-based) line declaring `name = "<name>"` inside a
+/// `[[cluster]]` table.
+pub fn cluster_definition_line(text: &str, name: &str) -> Option<usize> {
+    definition_line(text, "cluster", name)
+}
+
+/// Finds the (0-based) line setting `key` in the `[doc]` table.
+pub fn doc_key_line(text: &str, key: &str) -> Option<usize> {
+    let mut in_doc = false;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_doc = line == "[doc]";
+            continue;
+        }
+        let rest = line.strip_prefix(key).map(str::trim_start);
+        if in_doc && rest.is_some_and(|rest| rest.starts_with('=')) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+fn definition_line(text: &str, table: &str, name: &str) -> Option<usize> {
+    let header = format!("[[{}]]", table);
+    let mut in_table = false;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_table = line == header;
+            continue;
+        }
+        if in_table && string_key(line, "name") == Some(name) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// If `line` has the form `key = "value"`, returns `value`.
+fn string_key<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(key)?.trim_start();
+    let rest = rest.strip_prefix('=')?.trim();
+    let quote = rest.chars().next().filter(|&c| is_quote(c))?;
+    let rest = &rest[1..];
+    Some(&rest[..rest.find(quote)?])
+}
+
+pub fn is_quote(c: char) -> bool {
+    c == '"' || c == '\''
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,37 @@
+use super::{cluster_definition_line, doc_key_line, group_definition_line};
+
+const TREE: &str = r#"[[cluster]]
+name = "frontend"
+
+[[group]]
+name = "lexer"
+cluster = "frontend"
+items = []
+
+[[group]]
+name = "parser"
+requires = ["lexer"]
+items = []
+"#;
+
+#[test]
+fn finds_group_definitions() {
+    assert_eq!(group_definition_line(TREE, "lexer"), Some(4));
+    assert_eq!(group_definition_line(TREE, "parser"), Some(9));
+    assert_eq!(group_definition_line(TREE, "missing"), None);
+    assert_eq!(group_definition_line("[[group]]\nname='a'", "a"), Some(1));
+}
+
+#[test]
+fn finds_cluster_definitions() {
+    assert_eq!(cluster_definition_line(TREE, "frontend"), Some(1));
+    // Only `[[cluster]]` tables count.
+    assert_eq!(cluster_definition_line(TREE, "lexer"), None);
+}
+
+#[test]
+fn finds_doc_keys() {
+    let text = "[doc]\ntitle = \"t\"\ncolumns = [\"status\"]\n\n[[group]]\ncolumns = 1\n";
+    assert_eq!(doc_key_line(text, "columns"), Some(2));
+    assert_eq!(doc_key_line(text, "caption"), None);
+}
@@ -0,0 +1,56 @@
+//! "Did you mean" suggestions for names that don't match anything.
+
+/// The candidate that `input` is most likely a typo of: the one fewest
+/// edits away, ignoring case, if that is at most a third of its length
+/// (and at least one edit). Ties go to the first candidate.
+pub fn closest<'c>(input: &str, candidates: impl IntoIterator<Item = &'c str>) -> Option<&'c str> {
+    let input = input.to_lowercase();
+    let mut best: Option<(usize, &str)> = None;
+    for candidate in candidates {
+        let distance = edit_distance(&input, &candidate.to_lowercase());
+        if distance > (candidate.chars().count() / 3).max(1) {
+            continue;
+        }
+        if best.is_none_or(|(best, _)| distance < best) {
+            best = Some((distance, candidate));
+        }
+    }
+    best.map(|(_, candidate)| candidate)
+}
+
+/// `"; did you mean `x`?"` for the closest candidate, or nothing.
+pub fn did_you_mean<'c>(input: &str, candidates: impl IntoIterator<Item = &'c str>) -> String {
+    match closest(input, candidates) {
+        Some(candidate) => format!("; did you mean `{}`?", candidate),
+        None => String::new(),
+    }
+}
+
+/// The number of characters to insert, delete, substitute or swap with
+/// their neighbour to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // `rows[i][j]` is the distance between `a[..i]` and `b[..j]`.
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,20 @@
+use super::{closest, did_you_mean, edit_distance};
+
+#[test]
+fn distances() {
+    assert_eq!(edit_distance("parser", "parser"), 0);
+    assert_eq!(edit_distance("parserr", "parser"), 1);
+    assert_eq!(edit_distance("pasrer", "parser"), 1);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+}
+
+#[test]
+fn picks_the_closest_candidate() {
+    let names = ["parser", "lexer", "parse_tree"];
+    assert_eq!(closest("parserr", names), Some("parser"));
+    assert_eq!(closest("Lexer", names), Some("lexer"));
+    assert_eq!(closest("checker", names), None);
+    assert_eq!(did_you_mean("lexr", names), "; did you mean `lexer`?");
+    assert_eq!(did_you_mean("checker", names), "");
+}
@@ -0,0 +1,33 @@
+//! The small template language used by `doc.group_label_template`:
+//! literal text with `{{name}}` placeholders. There are no conditionals
+//! or loops; anything fancier belongs in a renderer of your own.
+
+use fehler::throws;
+
+/// Expands the `{{name}}` placeholders in `template`, looking each name up
+/// with `value`. Names that `value` doesn't know are an error, as is a
+/// `{{` without a closing `}}`.
+#[throws(anyhow::Error)]
+pub fn render(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = match after.find("}}") {
+            Some(end) => end,
+            None => anyhow::bail!("unclosed `{{{{` in template `{}`", template),
+        };
+        let name = after[..end].trim();
+        match value(name) {
+            Some(v) => output.push_str(&v),
+            None => anyhow::bail!("unknown placeholder `{}` in template `{}`", name, template),
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod test;
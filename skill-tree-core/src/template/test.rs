@@ -0,0 +1,36 @@
+use super::render;
+
+fn value(name: &str) -> Option<String> {
+    match name {
+        "label" => Some("Parser".to_string()),
+        "done" => Some("1".to_string()),
+        "total" => Some("3".to_string()),
+        _ => None,
+    }
+}
+
+#[test]
+fn expands_placeholders() {
+    let text = render("{{label}} ({{ done }}/{{total}})", value).unwrap();
+    assert_eq!(text, "Parser (1/3)");
+}
+
+#[test]
+fn plain_text() {
+    assert_eq!(render("no placeholders", value).unwrap(), "no placeholders");
+}
+
+#[test]
+fn unknown_placeholder() {
+    let error = render("{{label}} by {{owner}}", value).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unknown placeholder `owner` in template `{{label}} by {{owner}}`"
+    );
+}
+
+#[test]
+fn unclosed_placeholder() {
+    let error = render("{{label", value).unwrap_err();
+    assert_eq!(error.to_string(), "unclosed `{{` in template `{{label`");
+}
@@ -0,0 +1,725 @@
+#![cfg(all(test, feature = "graphviz"))]
+
+use std::path::{Path, PathBuf};
+
+use crate::{Edge, EdgeKind, GroupIndex, ItemExt, SkillTree, Status};
+
+const BLESS: bool = false;
+
+fn run_test(file_name: &str) {
+    let toml_file = PathBuf::from(format!("test-data/{}.toml", file_name));
+    let skill_tree = SkillTree::load(&toml_file).unwrap();
+    skill_tree.validate().unwrap();
+    let mut actual_output_buf = Vec::new();
+    skill_tree.write_graphviz(&mut actual_output_buf).unwrap();
+    let actual_output = String::from_utf8(actual_output_buf).unwrap();
+
+    let expected_file = PathBuf::from(format!("test-data/{}.gv", file_name));
+    compare(&expected_file, &actual_output);
+}
+
+/// Like `run_test`, but renders each page of the tree separately into
+/// `test-data/<file_name>.<cluster>.gv`.
+fn run_pages_test(file_name: &str) {
+    let toml_file = PathBuf::from(format!("test-data/{}.toml", file_name));
+    let skill_tree = SkillTree::load(&toml_file).unwrap();
+    skill_tree.validate().unwrap();
+    let page_name = |cluster: Option<&str>| cluster.unwrap_or("unclustered").to_string();
+    for cluster in skill_tree.pages() {
+        let actual_output = skill_tree
+            .page_to_graphviz(cluster, &|c| format!("{}.svg", page_name(c)))
+            .unwrap();
+        let expected_file =
+            PathBuf::from(format!("test-data/{}.{}.gv", file_name, page_name(cluster)));
+        compare(&expected_file, &actual_output);
+    }
+}
+
+fn compare(expected_file: &PathBuf, actual_output: &str) {
+    let expected_output = std::fs::read_to_string(expected_file).unwrap_or_default();
+
+    let expected_lines = expected_output.lines().chain(Some("EOF"));
+    let actual_lines = actual_output.lines().chain(Some("EOF"));
+    if let Some(first_diff) = expected_lines.zip(actual_lines).position(|(e, a)| e != a) {
+        if BLESS || std::env::var("AVD_BLESS").is_ok() {
+            std::fs::write(expected_file, actual_output).unwrap();
+            eprintln!("blessing {}", expected_file.display());
+        } else {
+            eprintln!(
+                "{}",
+                prettydiff::diff_lines(&expected_output, actual_output)
+            );
+            panic!("expected output differs on line {}", first_diff + 1);
+        }
+    }
+}
+
+#[test]
+fn avd_snippet() {
+    run_test("avd_snippet");
+}
+
+#[test]
+#[should_panic(expected = "the group `A` has a dependency on a group `B` that does not exist")]
+fn invalid_requires() {
+    run_test("invalid_requires");
+}
+
+#[test]
+#[should_panic(expected = "item #3 in group `parser` is missing `label`")]
+fn missing_label() {
+    run_test("missing_label");
+}
+
+#[test]
+fn misspelled_requires() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "parser"
+items = []
+
+[[group]]
+name = "checker"
+requires = ["parserr"]
+items = []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        tree.validate().unwrap_err().to_string(),
+        "the group `checker` has a dependency on a group `parserr` that does not exist; \
+         did you mean `parser`?"
+    );
+}
+
+#[test]
+fn group_links() {
+    run_test("group_links");
+}
+
+#[test]
+#[should_panic(
+    expected = "the group `parser` links to `#lexr`, but there is no group `lexr`; did you mean `lexer`?"
+)]
+fn invalid_group_link() {
+    run_test("invalid_group_link");
+}
+
+#[test]
+fn misspelled_status() {
+    let tree = SkillTree::parse(
+        r#"
+[doc.statuses]
+Review = { color = "lightblue" }
+
+[[group]]
+name = "parser"
+status = "Reveiw"
+items = []
+"#,
+    )
+    .unwrap();
+    assert!(tree
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .ends_with("nor defined in `[doc.statuses]`; did you mean `Review`?"));
+}
+
+#[test]
+fn misspelled_item_status() {
+    let tree = SkillTree::parse(
+        r#"
+[doc.statuses]
+Review = { color = "lightblue" }
+
+[[group]]
+name = "parser"
+items = [{ label = "Expressions", status = "Complete" }, { label = "Statements", status = "Reveiw" }]
+"#,
+    )
+    .unwrap();
+    let error = tree.validate().unwrap_err().to_string();
+    assert!(error.contains("item #2 of the group `parser` has the status `Reveiw`"));
+    assert!(error.ends_with("did you mean `Review`?"));
+}
+
+#[test]
+fn free_item_statuses_without_custom_statuses() {
+    let tree = SkillTree::parse(
+        "[[group]]\nname = \"parser\"\nitems = [{ label = \"Expressions\", status = \"design\" }]\n",
+    )
+    .unwrap();
+    tree.validate().unwrap();
+}
+
+#[test]
+fn fonts() {
+    run_test("fonts");
+}
+
+#[test]
+fn background() {
+    run_test("background");
+}
+
+#[test]
+fn edge_labels() {
+    run_test("edge_labels");
+}
+
+#[test]
+fn cross_tree() {
+    run_test("cross_tree");
+}
+
+#[test]
+fn edges() {
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "lexer"
+aliases = ["tokenizer"]
+items = [{ label = "Tokens", port = "tokens" }]
+
+[[group]]
+name = "parser"
+requires = ["tokenizer:tokens", "infra::ci"]
+suggests = [{ group = "lexer", label = "for spans" }, "missing"]
+items = []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        tree.edges(),
+        vec![
+            Edge {
+                from: GroupIndex(1),
+                to: GroupIndex(0),
+                to_port: Some("tokens".to_string()),
+                kind: EdgeKind::Hard,
+            },
+            Edge {
+                from: GroupIndex(1),
+                to: GroupIndex(0),
+                to_port: None,
+                kind: EdgeKind::Soft,
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "the group `A` has a dependency on a group `B` that does not exist")]
+fn invalid_detailed_requires() {
+    run_test("invalid_detailed_requires");
+}
+
+#[test]
+fn soft_dependencies() {
+    run_test("soft_dependencies");
+}
+
+#[test]
+fn aliases() {
+    run_test("aliases");
+
+    let skill_tree = SkillTree::load(&PathBuf::from("test-data/aliases.toml")).unwrap();
+    assert_eq!(
+        skill_tree.warnings(),
+        vec!["the group `parser` refers to `tokenizer` by its old name; use `lexer` instead"],
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "the group `lexer` has an alias `parser` that is also the name of a group"
+)]
+fn alias_clash() {
+    run_test("alias_clash");
+}
+
+#[test]
+fn ports() {
+    run_test("ports");
+
+    let skill_tree = SkillTree::load(&PathBuf::from("test-data/ports.toml")).unwrap();
+    assert_eq!(
+        skill_tree.warnings(),
+        vec![
+            "test-data/ports.toml:13: `\"lexer:tokens\"` is deprecated; \
+             write `{ group = \"lexer\", port = \"tokens\" }` instead"
+        ],
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "the group `parser` has a dependency on a port `spans` of group `lexer`, \
+                but no item there has that port"
+)]
+fn invalid_port() {
+    run_test("invalid_port");
+}
+
+#[test]
+fn label_template() {
+    run_test("label_template");
+}
+
+#[test]
+#[should_panic(expected = "unknown placeholder `owner`")]
+fn invalid_label_template() {
+    run_test("invalid_label_template");
+}
+
+#[test]
+fn group_emoji() {
+    run_test("group_emoji");
+}
+
+#[test]
+fn typed_columns() {
+    run_test("typed_columns");
+}
+
+#[test]
+#[should_panic(
+    expected = "item #2 in group `parser` has `state = \"doign\"`, but expected one of `todo`, `doing`, `done`"
+)]
+fn invalid_column_value() {
+    run_test("invalid_column_value");
+}
+
+#[test]
+fn group_defaults() {
+    run_test("group_defaults");
+
+    let tree = SkillTree::load(&PathBuf::from("test-data/group_defaults.toml")).unwrap();
+    let statuses: Vec<_> = tree.groups().map(|g| g.status.clone()).collect();
+    assert_eq!(
+        statuses,
+        vec![Some(Status::Unassigned), Some(Status::Complete)]
+    );
+}
+
+#[test]
+fn custom_statuses() {
+    run_test("custom_statuses");
+
+    let tree = SkillTree::load(&PathBuf::from("test-data/custom_statuses.toml")).unwrap();
+    let parser = tree.group_named("parser").unwrap();
+    assert_eq!(parser.status, Some(Status::Custom("Review".to_string())));
+    assert_eq!(parser.progress(&tree), (2, 3));
+    let lexer = tree.group_named("lexer").unwrap();
+    assert_eq!(lexer.progress(&tree), (1, 1));
+}
+
+#[test]
+#[should_panic(expected = "the group `parser` has the status `Reveiw`, which is neither built in")]
+fn unknown_status() {
+    run_test("unknown_status");
+}
+
+#[test]
+fn status_history() {
+    let mut tree = SkillTree::load(Path::new("test-data/status_history.toml")).unwrap();
+    tree.validate().unwrap();
+    tree.as_of("2024-02-01".parse().unwrap());
+    let statuses: Vec<_> = tree.groups().map(|g| g.status.clone()).collect();
+    assert_eq!(
+        statuses,
+        vec![Some(Status::Assigned), None, Some(Status::Blocked)]
+    );
+    compare(
+        &PathBuf::from("test-data/status_history.gv"),
+        &tree.to_graphviz().unwrap(),
+    );
+}
+
+#[test]
+fn title() {
+    let mut tree = SkillTree::load(Path::new("test-data/title.toml")).unwrap();
+    tree.validate().unwrap();
+    // Pin `{date}` without touching the environment other tests share.
+    tree.as_of("2024-01-01".parse().unwrap());
+    compare(
+        &PathBuf::from("test-data/title.gv"),
+        &tree.to_graphviz().unwrap(),
+    );
+}
+
+#[test]
+fn split_pages() {
+    let tree = SkillTree::load(&PathBuf::from("test-data/split_pages.toml")).unwrap();
+    assert_eq!(tree.pages(), vec![Some("frontend"), Some("backend"), None]);
+    run_pages_test("split_pages");
+}
+
+#[test]
+fn print_layout() {
+    run_test("print_layout");
+}
+
+#[test]
+fn locale() {
+    run_test("locale");
+}
+
+#[test]
+fn rankdir_alias() {
+    run_test("rankdir_alias");
+}
+
+#[test]
+#[should_panic(expected = "unknown rankdir `sideways`")]
+fn invalid_rankdir() {
+    run_test("invalid_rankdir");
+}
+
+#[test]
+fn spacing() {
+    run_test("spacing");
+}
+
+#[test]
+fn links() {
+    run_test("links");
+}
+
+#[test]
+#[should_panic(expected = r#"`doc.links = "relative"` needs a `doc.base_url`"#)]
+fn links_without_base_url() {
+    run_test("links_without_base_url");
+}
+
+#[test]
+fn palette() {
+    run_test("palette");
+}
+
+#[test]
+#[should_panic(
+    expected = "the `header_color` of group `parser` is `brand_bleu`, which is neither a color \
+                nor in `doc.colors`; did you mean `brand_blue`?"
+)]
+fn unknown_color() {
+    run_test("unknown_color");
+}
+
+#[test]
+fn cluster_colors() {
+    run_test("cluster_colors");
+}
+
+#[test]
+fn quoted_fontname() {
+    run_test("quoted_fontname");
+}
+
+#[test]
+fn quoted_bgcolor() {
+    // Colors are checked by `validate`; an unchecked one still makes valid dot.
+    let tree = SkillTree::parse("[graphviz]\nbgcolor = 'white\"'\n").unwrap();
+    assert!(tree.to_graphviz().unwrap().contains(r#"bgcolor="white\"""#));
+}
+
+#[test]
+fn passthrough_attrs() {
+    run_test("passthrough_attrs");
+}
+
+#[test]
+#[should_panic(expected = "`graphviz.node_attrs` has the attribute `pen width`")]
+fn invalid_passthrough_attrs() {
+    run_test("invalid_passthrough_attrs");
+}
+
+#[test]
+#[should_panic(expected = "unknown splines `wiggly`")]
+fn invalid_splines() {
+    run_test("invalid_splines");
+}
+
+#[test]
+#[should_panic(expected = "expected at least 0.02 inches, found `-0.5`")]
+fn invalid_nodesep() {
+    run_test("invalid_nodesep");
+}
+
+#[test]
+#[should_panic(expected = r#"invalid margin `"wide"`, expected inches like `0.5` or `"0.5,0.25"`"#)]
+fn invalid_margin() {
+    run_test("invalid_margin");
+}
+
+#[test]
+fn merge_files() {
+    let mut skill_tree = SkillTree::load(Path::new("test-data/merge_frontend.toml")).unwrap();
+    for _ in 0..2 {
+        // Merging the same file again is a no-op, as with includes.
+        skill_tree
+            .merge_file(Path::new("test-data/merge_backend.toml"))
+            .unwrap();
+    }
+    skill_tree.validate().unwrap();
+    let actual_output = skill_tree.to_graphviz().unwrap();
+    compare(&PathBuf::from("test-data/merge_files.gv"), &actual_output);
+}
+
+#[test]
+fn goals() {
+    let mut skill_tree = SkillTree::load(Path::new("test-data/goals.toml")).unwrap();
+    skill_tree.validate().unwrap();
+    assert_eq!(
+        skill_tree.warnings(),
+        ["no goal depends on the group `plugins` [unreachable-group]"]
+    );
+
+    skill_tree.remove_unreachable();
+    let actual_output = skill_tree.to_graphviz().unwrap();
+    compare(&PathBuf::from("test-data/goals.gv"), &actual_output);
+}
+
+#[test]
+fn unknown_goal() {
+    let skill_tree = SkillTree::parse("[doc]\ngoals = [\"ship\"]\n").unwrap();
+    let error = skill_tree.validate().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the goal `ship` in `doc.goals` is not a group"
+    );
+}
+
+#[test]
+fn selective_include() {
+    run_test("selective_include");
+}
+
+#[test]
+#[should_panic(expected = "there is no group `cd` to include")]
+fn invalid_selective_include() {
+    run_test("invalid_selective_include");
+}
+
+#[test]
+#[should_panic(expected = "files include each other in a cycle")]
+fn include_cycle() {
+    run_test("include_cycle");
+}
+
+#[test]
+fn exclude_groups() {
+    run_test("exclude_groups");
+
+    let tree = SkillTree::load(Path::new("test-data/exclude_groups.toml")).unwrap();
+    let checker = tree.group_named("checker").unwrap();
+    let dependencies: Vec<(&str, EdgeKind)> = checker
+        .dependencies()
+        .map(|(requirement, kind)| (requirement.group(), kind))
+        .collect();
+    assert_eq!(
+        dependencies,
+        vec![("lexer", EdgeKind::Hard), ("docs", EdgeKind::Soft)]
+    );
+    assert!(tree.group_named("legacy_parser").is_none());
+}
+
+#[test]
+#[should_panic(expected = "there is more than one group named `y`")]
+fn exclude_duplicate() {
+    run_test("exclude_duplicate");
+}
+
+#[test]
+fn override_included() {
+    run_test("override_included");
+
+    let tree = SkillTree::load(Path::new("test-data/override_included.toml")).unwrap();
+    assert_eq!(tree.groups().filter(|g| g.name == "parser").count(), 1);
+    let parser = tree.group_named("parser").unwrap();
+    assert_eq!(parser.status, Some(Status::Blocked));
+    assert_eq!(parser.label.as_deref(), Some("Parser"));
+    assert_eq!(parser.items.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "there is more than one group named `parser`")]
+fn duplicate_includes() {
+    run_test("duplicate_includes");
+}
+
+#[test]
+fn group_without_items() {
+    let tree = SkillTree::parse("[[group]]\nname = \"parser\"\n").unwrap();
+    assert!(tree
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .starts_with("the group `parser` has no `items`"));
+
+    // `items = []` is fine, as it always was.
+    let tree = SkillTree::parse("[[group]]\nname = \"parser\"\nitems = []\n").unwrap();
+    tree.validate().unwrap();
+}
+
+#[test]
+fn templates() {
+    run_test("templates");
+
+    let tree = SkillTree::load(Path::new("test-data/templates.toml")).unwrap();
+    let group = tree.group_named("async_closures").unwrap();
+    let items: Vec<(&str, &str)> = group
+        .items
+        .iter()
+        .map(|item| (&item["label"][..], &item["status"][..]))
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ("Implement", "done"),
+            ("Test", "todo"),
+            ("Document", "todo"),
+            ("Stabilize", "todo"),
+        ]
+    );
+    assert_eq!(group.cluster.as_deref(), Some("language"));
+    assert_eq!(group.header_color.as_deref(), Some("lightblue"));
+}
+
+#[test]
+#[should_panic(expected = "the group `parser` extends `trackng`, which is not a template")]
+fn unknown_template() {
+    run_test("unknown_template");
+}
+
+#[test]
+fn items_from() {
+    run_test("items_from");
+
+    let tree = SkillTree::load(Path::new("test-data/items_from.toml")).unwrap();
+    let labels: Vec<&str> = tree.group_named("targets").unwrap().items[..]
+        .iter()
+        .map(|item| &item["label"][..])
+        .collect();
+    assert_eq!(
+        labels,
+        vec![
+            "Support x86_64",
+            "Support aarch64",
+            "Support wasm32",
+            "Cross-compile tests"
+        ]
+    );
+}
+
+#[test]
+fn matrix() {
+    run_test("matrix");
+
+    let tree = SkillTree::load(Path::new("test-data/matrix.toml")).unwrap();
+    let names: Vec<&str> = tree.groups().map(|group| &group.name[..]).collect();
+    assert_eq!(
+        names,
+        vec![
+            "core",
+            "linux_build",
+            "linux_ship",
+            "windows_build",
+            "windows_ship"
+        ]
+    );
+    let ship = tree.group_named("windows_ship").unwrap();
+    let requires: Vec<&str> = ship.dependencies().map(|(r, _)| r.group()).collect();
+    assert_eq!(requires, vec!["core", "windows_build"]);
+    assert_eq!(ship.items.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "the matrix `{platform}` names more than one group `linux`")]
+fn invalid_matrix() {
+    run_test("invalid_matrix");
+}
+
+#[test]
+fn cluster_summaries() {
+    run_test("cluster_summaries");
+}
+
+#[test]
+fn max_label_chars() {
+    run_test("max_label_chars");
+}
+
+#[test]
+fn wide_text() {
+    run_test("wide_text");
+}
+
+#[test]
+fn provenance() {
+    let tree = SkillTree::load(Path::new("test-data/provenance.toml")).unwrap();
+    let source = |name| {
+        tree.group_named(name)
+            .unwrap()
+            .source()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(source("driver"), "test-data/provenance.toml:5");
+    assert_eq!(source("parser"), "test-data/provenance_team.toml:5");
+    assert_eq!(
+        tree.column_source("status").unwrap().to_string(),
+        "test-data/provenance_team.toml:2"
+    );
+    assert_eq!(
+        tree.validate().unwrap_err().to_string(),
+        "test-data/provenance_team.toml:5: the group `parser` has a dependency on a group \
+         `lexer` that does not exist"
+    );
+}
+
+#[test]
+fn cluster_defaults() {
+    run_test("cluster_defaults");
+
+    let tree = SkillTree::load(Path::new("test-data/cluster_defaults.toml")).unwrap();
+    let assignees: Vec<&str> = tree
+        .groups()
+        .flat_map(|group| group.items().map(move |item| (group, item)))
+        .map(|(group, item)| item.column_value(&tree, group, "assignee"))
+        .collect();
+    assert_eq!(assignees, ["webteam", "ana", "anyone"]);
+}
+
+#[test]
+#[should_panic(
+    expected = "`doc.defaults.cluster` has defaults for `frontnd`, which is not a cluster; did you mean `frontend`?"
+)]
+fn unknown_defaults_cluster() {
+    run_test("unknown_defaults_cluster");
+}
+
+#[test]
+fn custom_css() {
+    let tree = SkillTree::load(Path::new("test-data/custom_css.toml")).unwrap();
+    assert_eq!(
+        tree.custom_css().unwrap().as_deref(),
+        Some("body { font-family: \"Inter\", sans-serif; }\n.node text { fill: #333; }")
+    );
+
+    let tree = SkillTree::parse("[doc]\ncss = \"does-not-exist.css\"\n").unwrap();
+    let error = tree.custom_css().unwrap_err();
+    assert!(format!("{:#}", error).contains("reading `doc.css` from `does-not-exist.css`"));
+}
+
+#[test]
+fn custom_css_from_include() {
+    // The include's `doc.css` is relative to the include, not this file.
+    let tree = SkillTree::load(Path::new("test-data/custom_css_include.toml")).unwrap();
+    assert_eq!(
+        tree.custom_css().unwrap().as_deref(),
+        Some(".node text { fill: #036; }\n")
+    );
+}
@@ -0,0 +1,13 @@
+//! Helpers shared by the unit tests of several modules.
+
+#![cfg(test)]
+
+use std::path::PathBuf;
+
+/// A fresh directory under the system's temporary directory.
+pub(crate) fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("skill-tree-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
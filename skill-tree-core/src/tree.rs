@@ -0,0 +1,2812 @@
+use crate::color;
+use crate::date::{self, Date};
+use crate::deprecated;
+use crate::diagnostic::{Diagnostic, Location, Severity};
+use crate::emoji;
+use crate::link::{self, LinkStyle};
+use crate::lint::{self, Level, Lint};
+use crate::locale;
+use crate::migrate::SCHEMA_VERSION;
+use crate::source::{self, Source};
+use crate::suggest;
+use crate::template;
+use anyhow::Context;
+use fehler::{throw, throws};
+use serde_derive::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct SkillTree {
+    pub group: Option<Vec<Group>>,
+    pub cluster: Option<Vec<Cluster>>,
+    pub template: Option<Vec<Template>>,
+    pub matrix: Option<Vec<Matrix>>,
+    pub graphviz: Option<Graphviz>,
+    pub doc: Option<Doc>,
+
+    /// Warnings noticed while parsing this file and its includes.
+    #[serde(skip)]
+    parse_warnings: Vec<Diagnostic>,
+
+    /// Files loaded into this tree so far, so each is merged only once.
+    #[serde(skip)]
+    loaded: HashSet<PathBuf>,
+
+    /// The date the tree is shown as of (see `as_of`), if not today.
+    #[serde(skip)]
+    as_of: Option<Date>,
+
+    /// Where each column in `doc.columns` was declared, by name.
+    #[serde(skip)]
+    column_sources: HashMap<String, Source>,
+
+    /// Every file this tree was read from, the root file first.
+    #[serde(skip)]
+    files: Vec<PathBuf>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct Graphviz {
+    /// Direction the graph flows in (defaults to left-to-right).
+    pub rankdir: Option<Rankdir>,
+
+    /// Font used for all text in the graph.
+    pub fontname: Option<String>,
+
+    /// Font size for cluster labels and edge labels.
+    pub fontsize: Option<f64>,
+
+    /// Font size for the groups themselves (defaults to 16).
+    pub node_fontsize: Option<f64>,
+
+    /// Background of the whole graph; `"transparent"` lets the output sit
+    /// on dark pages without a white box around it.
+    pub bgcolor: Option<String>,
+
+    /// Maximum size of the drawing in inches, e.g. `"11.7,16.5"`.
+    pub size: Option<String>,
+
+    /// How to fit the drawing into `size` (see graphviz's `ratio`).
+    pub ratio: Option<String>,
+
+    /// Size in inches of the pages to tile the drawing across (PostScript
+    /// output only).
+    pub page: Option<String>,
+
+    /// Margin around the drawing, in inches: `0.5` or `"0.5"` on every
+    /// side, or `"0.5,0.25"` for left and right, then top and bottom.
+    pub margin: Option<Margin>,
+
+    /// Least space between two groups of the same rank, in inches
+    /// (graphviz's default is 0.25).
+    pub nodesep: Option<Inches>,
+
+    /// Least space between ranks, in inches (graphviz's default is 0.5).
+    pub ranksep: Option<Inches>,
+
+    /// Merges edges that run in parallel, which tidies up groups with
+    /// many dependents.
+    pub concentrate: Option<bool>,
+
+    /// How edges are drawn: `spline` (graphviz's default), `curved`,
+    /// `polyline`, `ortho` (horizontal and vertical segments only),
+    /// `line` or `none`.
+    pub splines: Option<Splines>,
+
+    /// How overlapping groups are moved apart; see `Overlap`.
+    pub overlap: Option<Overlap>,
+
+    /// Adds a node next to each cluster counting its groups by status and
+    /// its items done.
+    pub cluster_summaries: Option<bool>,
+
+    /// Gives each cluster without a `color` one of its own, picked from a
+    /// palette by its name.
+    pub cluster_colors: Option<bool>,
+
+    /// Colors the headers of groups without a `header_color` or a status
+    /// color to match their cluster.
+    pub tint_headers: Option<bool>,
+
+    /// Any other graph attributes, written as given after the ones above,
+    /// for settings we don't model.
+    pub graph_attrs: Option<BTreeMap<String, String>>,
+
+    /// Any other attributes for every group, as with `graph_attrs`.
+    pub node_attrs: Option<BTreeMap<String, String>>,
+
+    /// Any other attributes for every edge, as with `graph_attrs`.
+    pub edge_attrs: Option<BTreeMap<String, String>>,
+}
+
+impl Graphviz {
+    /// The `graph_attrs`, `node_attrs` and `edge_attrs` tables, by name.
+    pub fn passthrough_attrs(&self) -> [(&'static str, Option<&BTreeMap<String, String>>); 3] {
+        [
+            ("graph_attrs", self.graph_attrs.as_ref()),
+            ("node_attrs", self.node_attrs.as_ref()),
+            ("edge_attrs", self.edge_attrs.as_ref()),
+        ]
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct Doc {
+    /// The version of the file format this file was written for; files
+    /// without one are version 1. See `migrate`.
+    pub schema_version: Option<u32>,
+
+    /// Shown above the graph; `{date}` is replaced with today's date.
+    pub title: Option<String>,
+
+    /// Shown under the title; `{date}` is replaced as in `title`.
+    pub caption: Option<String>,
+
+    /// Named colors, e.g. `brand_blue = "#0a4f8a"`, which can be used
+    /// anywhere a color is.
+    pub colors: Option<HashMap<String, String>>,
+
+    /// The address the rendered tree is published at, e.g.
+    /// `"https://example.org/roadmap/"`; see `links`.
+    pub base_url: Option<String>,
+
+    /// How hrefs are written into the output; see `LinkStyle`.
+    pub links: Option<LinkStyle>,
+
+    /// A stylesheet for `--emit html` pages, relative to this file, applied
+    /// after the built-in styles.
+    pub css: Option<PathBuf>,
+
+    /// More CSS for `--emit html` pages, written inline; applied after
+    /// `css`.
+    pub style: Option<String>,
+
+    /// Language for status names and other text we generate, e.g. `"de"`.
+    /// Keys and values in the file stay in English.
+    pub locale: Option<String>,
+
+    /// Translations of generated text, keyed by the English text; these
+    /// win over the built-in tables for `locale`.
+    pub translations: Option<HashMap<String, String>>,
+    pub columns: Option<Vec<Column>>,
+    pub defaults: Option<Defaults>,
+
+    /// Values for groups in this file that don't set them themselves.
+    pub group_defaults: Option<GroupDefaults>,
+    pub emoji: Option<HashMap<String, EmojiMap>>,
+    pub include: Option<Vec<Include>>,
+
+    /// Groups to leave out once every include is merged, by name, where
+    /// `*` matches any run of characters and `?` any one character, e.g.
+    /// `["legacy_*"]`. Groups that depended on a left-out group depend on
+    /// what it depended on instead. Only read from the root file.
+    pub exclude_groups: Option<Vec<String>>,
+
+    /// Longest an item label can be before it is cut short with `…` in
+    /// the drawing; the full label is then shown as the cell's tooltip.
+    /// Emoji and wide (e.g. CJK) characters count as two.
+    pub max_label_chars: Option<usize>,
+
+    /// Text for each group's header, e.g. `"{{label}} ({{done}}/{{total}})"`.
+    /// See `SkillTree::group_header` for the placeholders.
+    pub group_label_template: Option<String>,
+
+    /// Levels for lints, by name, e.g. `orphan-group = "deny"`.
+    pub lints: Option<HashMap<String, Level>>,
+
+    /// The groups the tree is working towards, e.g. `["ship_v1"]` (groups
+    /// can also set `goal = true`). Goals are drawn more prominently, and
+    /// groups that none of them depend on, even indirectly, are reported
+    /// by the `unreachable-group` lint.
+    pub goals: Option<Vec<String>>,
+
+    /// Statuses besides `Blocked`, `Unassigned`, `Assigned` and
+    /// `Complete`, by name.
+    pub statuses: Option<HashMap<String, StatusDefinition>>,
+
+    /// The GitHub label for each status, for `skill-tree sync`, e.g.
+    /// `Blocked = "S-blocked"`. Statuses not listed use their own name.
+    pub issue_labels: Option<HashMap<String, String>>,
+}
+
+pub type EmojiMap = HashMap<String, String>;
+
+/// `[doc.defaults]`: values for the columns of items that don't set
+/// them, e.g. `status = "todo"`, and `[doc.defaults.cluster.<name>]`
+/// tables of values for the items of groups in that cluster, which win
+/// over the document-wide ones. (So no column can be called `cluster`.)
+#[derive(Clone, Default, Debug, Deserialize)]
+pub struct Defaults {
+    #[serde(default)]
+    pub cluster: HashMap<String, HashMap<String, String>>,
+
+    #[serde(flatten)]
+    pub columns: HashMap<String, String>,
+}
+
+/// `[doc.group_defaults]`: the fields of `Group` that can be given a
+/// document-wide default.
+#[derive(Clone, Default, Debug, Deserialize)]
+pub struct GroupDefaults {
+    pub status: Option<Status>,
+    pub header_color: Option<String>,
+    pub description_color: Option<String>,
+    pub width: Option<f64>,
+}
+
+/// An entry in `doc.include`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Include {
+    /// `"infra.toml"`: everything in the file.
+    Path(PathBuf),
+
+    /// `{ path = "infra.toml", groups = ["ci"], clusters = ["releases"] }`:
+    /// only the named groups and the groups in the named clusters.
+    Selected {
+        path: PathBuf,
+        groups: Option<Vec<String>>,
+        clusters: Option<Vec<String>>,
+    },
+}
+
+impl Include {
+    pub fn path(&self) -> &Path {
+        match self {
+            Include::Path(path) | Include::Selected { path, .. } => path,
+        }
+    }
+}
+
+/// The colors of a cluster; see `SkillTree::cluster_colors`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ClusterColor<'t> {
+    pub background: &'t str,
+
+    /// For the headers of the cluster's groups, with
+    /// `graphviz.tint_headers`.
+    pub header: &'t str,
+}
+
+/// An entry in `doc.columns`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Column {
+    /// `"team"`: any value is accepted.
+    Name(String),
+
+    /// `{ name = "state", type = "enum", values = ["todo", "doing", "done"] }`
+    Typed {
+        name: String,
+        #[serde(rename = "type", default)]
+        kind: ColumnType,
+
+        /// The allowed values of an `enum` column.
+        values: Option<Vec<String>>,
+    },
+}
+
+/// Direction of the graph. Besides graphviz's own `TB`, `LR`, `BT` and
+/// `RL`, it accepts `TD` and spelled-out names like `left-to-right`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Rankdir {
+    TopToBottom,
+    #[default]
+    LeftToRight,
+    BottomToTop,
+    RightToLeft,
+}
+
+impl Rankdir {
+    /// The value of graphviz's `rankdir` attribute.
+    pub fn dot(self) -> &'static str {
+        match self {
+            Rankdir::TopToBottom => "TB",
+            Rankdir::LeftToRight => "LR",
+            Rankdir::BottomToTop => "BT",
+            Rankdir::RightToLeft => "RL",
+        }
+    }
+
+    /// The direction of a Mermaid `flowchart`, which uses the same
+    /// abbreviations as graphviz.
+    pub fn mermaid(self) -> &'static str {
+        self.dot()
+    }
+}
+
+impl std::convert::TryFrom<String> for Rankdir {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match &value.to_ascii_lowercase().replace('_', "-")[..] {
+            "tb" | "td" | "top-to-bottom" | "top-down" => Ok(Rankdir::TopToBottom),
+            "lr" | "left-to-right" => Ok(Rankdir::LeftToRight),
+            "bt" | "bottom-to-top" | "bottom-up" => Ok(Rankdir::BottomToTop),
+            "rl" | "right-to-left" => Ok(Rankdir::RightToLeft),
+            _ => Err(format!(
+                "unknown rankdir `{}`, expected one of `TB`, `LR`, `BT`, `RL` \
+                 (or `top-to-bottom`, `left-to-right`, `bottom-to-top`, `right-to-left`)",
+                value
+            )),
+        }
+    }
+}
+
+/// `splines` in `[graphviz]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Splines {
+    Spline,
+    Curved,
+    Polyline,
+    Ortho,
+    Line,
+    None,
+}
+
+impl Splines {
+    /// The value of graphviz's `splines` attribute.
+    pub fn dot(self) -> &'static str {
+        match self {
+            Splines::Spline => "spline",
+            Splines::Curved => "curved",
+            Splines::Polyline => "polyline",
+            Splines::Ortho => "ortho",
+            Splines::Line => "line",
+            Splines::None => "none",
+        }
+    }
+}
+
+impl std::convert::TryFrom<String> for Splines {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match &value.to_ascii_lowercase()[..] {
+            "spline" | "splines" | "true" => Ok(Splines::Spline),
+            "curved" => Ok(Splines::Curved),
+            "polyline" => Ok(Splines::Polyline),
+            "ortho" | "orthogonal" => Ok(Splines::Ortho),
+            "line" | "false" => Ok(Splines::Line),
+            "none" => Ok(Splines::None),
+            _ => Err(format!(
+                "unknown splines `{}`, expected one of `spline`, `curved`, `polyline`, \
+                 `ortho`, `line`, `none`",
+                value
+            )),
+        }
+    }
+}
+
+/// `overlap` in `[graphviz]`: what to do about nodes that would overlap.
+/// `dot` itself never overlaps nodes; this matters when the tree is laid
+/// out with another engine, such as `neato` or `fdp`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "toml::Value")]
+pub enum Overlap {
+    /// `true`: leave the overlaps.
+    Allow,
+
+    /// `false` or `"prism"`: remove them, keeping the layout's shape.
+    Prism,
+
+    /// `"scale"`: spread the whole layout out until they're gone.
+    Scale,
+
+    /// `"compress"`: remove them while keeping the layout compact.
+    Compress,
+
+    /// `"vpsc"`: remove them with as little movement as possible.
+    Vpsc,
+
+    /// `"ortho"`: remove them while keeping nodes' relative positions.
+    Ortho,
+}
+
+impl Overlap {
+    /// The value of graphviz's `overlap` attribute.
+    pub fn dot(self) -> &'static str {
+        match self {
+            Overlap::Allow => "true",
+            Overlap::Prism => "prism",
+            Overlap::Scale => "scale",
+            Overlap::Compress => "compress",
+            Overlap::Vpsc => "vpsc",
+            Overlap::Ortho => "ortho",
+        }
+    }
+}
+
+impl std::convert::TryFrom<toml::Value> for Overlap {
+    type Error = String;
+
+    fn try_from(value: toml::Value) -> Result<Self, String> {
+        let text = match &value {
+            toml::Value::Boolean(allow) => allow.to_string(),
+            toml::Value::String(text) => text.to_ascii_lowercase(),
+            _ => String::new(),
+        };
+        match &text[..] {
+            "true" => Ok(Overlap::Allow),
+            "false" | "prism" => Ok(Overlap::Prism),
+            "scale" => Ok(Overlap::Scale),
+            "compress" => Ok(Overlap::Compress),
+            "vpsc" => Ok(Overlap::Vpsc),
+            "ortho" => Ok(Overlap::Ortho),
+            _ => Err(format!(
+                "unknown overlap `{}`, expected `true`, `false`, or one of `prism`, `scale`, \
+                 `compress`, `vpsc`, `ortho`",
+                value
+            )),
+        }
+    }
+}
+
+/// A distance in inches for the `[graphviz]` layout settings: a number of
+/// at least 0.02, the smallest graphviz accepts.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "f64")]
+pub struct Inches(f64);
+
+impl Inches {
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl std::convert::TryFrom<f64> for Inches {
+    type Error = String;
+
+    fn try_from(value: f64) -> Result<Self, String> {
+        if value.is_finite() && value >= 0.02 {
+            Ok(Inches(value))
+        } else {
+            Err(format!("expected at least 0.02 inches, found `{}`", value))
+        }
+    }
+}
+
+impl fmt::Display for Inches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// `margin` in `[graphviz]`: the same on every side, or different
+/// horizontally and vertically.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "toml::Value")]
+pub struct Margin {
+    /// Left and right.
+    pub x: f64,
+
+    /// Top and bottom.
+    pub y: f64,
+}
+
+impl Margin {
+    pub fn uniform(inches: f64) -> Margin {
+        Margin {
+            x: inches,
+            y: inches,
+        }
+    }
+}
+
+impl std::convert::TryFrom<toml::Value> for Margin {
+    type Error = String;
+
+    fn try_from(value: toml::Value) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "invalid margin `{}`, expected inches like `0.5` or `\"0.5,0.25\"`",
+                value
+            )
+        };
+        let inches = |text: &str| match text.trim().parse::<f64>() {
+            Ok(inches) if inches.is_finite() && inches >= 0.0 => Ok(inches),
+            _ => Err(invalid()),
+        };
+        match &value {
+            toml::Value::Integer(i) => inches(&i.to_string()).map(Margin::uniform),
+            toml::Value::Float(f) => inches(&f.to_string()).map(Margin::uniform),
+            toml::Value::String(text) => match text.split_once(',') {
+                Some((x, y)) => Ok(Margin {
+                    x: inches(x)?,
+                    y: inches(y)?,
+                }),
+                None => inches(text).map(Margin::uniform),
+            },
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// As graphviz takes it: `x` or `x,y`.
+impl fmt::Display for Margin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.x == self.y {
+            write!(f, "{}", self.x)
+        } else {
+            write!(f, "{},{}", self.x, self.y)
+        }
+    }
+}
+
+/// The kind of values a column holds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    #[default]
+    String,
+    Enum,
+    Int,
+    Url,
+    Bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Cluster {
+    pub name: String,
+    pub label: String,
+    pub color: Option<String>,
+    pub style: Option<String>,
+
+    /// Where this cluster is declared, when it was loaded from a file.
+    #[serde(skip)]
+    source: Option<Source>,
+}
+
+/// A `[[matrix]]`: a group repeated for every combination of the values
+/// of some axes. Besides the keys below it takes any key a group does,
+/// and `{axis}` in any text is replaced by that axis's value, e.g. `name =
+/// "{platform}_{feature}"`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Matrix {
+    pub name: String,
+    pub axes: Vec<Axis>,
+
+    /// An axis along which each group requires the one for the previous
+    /// value, e.g. `"stage"` for stages that happen in order.
+    pub chain: Option<String>,
+
+    /// The rest of the group.
+    #[serde(flatten)]
+    pub group: toml::value::Table,
+}
+
+/// One of a matrix's `axes`: `{ name = "platform", values = ["linux", "macos"] }`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Axis {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+impl Matrix {
+    /// The groups of the matrix, varying the last axis fastest.
+    #[throws(anyhow::Error)]
+    pub fn groups(&self) -> Vec<Group> {
+        if let Some(chain) = &self.chain {
+            if !self.axes.iter().any(|axis| axis.name == *chain) {
+                anyhow::bail!(
+                    "the matrix `{}` chains along `{}`, which is not one of its axes",
+                    self.name,
+                    chain
+                );
+            }
+        }
+
+        let mut cells: Vec<Vec<(&str, usize)>> = vec![vec![]];
+        for axis in &self.axes {
+            cells = cells
+                .into_iter()
+                .flat_map(|cell| {
+                    (0..axis.values.len()).map(move |index| {
+                        let mut cell = cell.clone();
+                        cell.push((&axis.name[..], index));
+                        cell
+                    })
+                })
+                .collect();
+        }
+
+        let name = |cell: &[(&str, usize)]| self.fill(&self.name, cell);
+        let mut names = HashSet::new();
+        let mut groups = vec![];
+        for cell in &cells {
+            let mut table = self.group.clone();
+            for (_, value) in table.iter_mut() {
+                self.fill_value(value, cell);
+            }
+            table.insert("name".to_string(), toml::Value::String(name(cell)));
+
+            if let Some(chain) = &self.chain {
+                // The first value along the chain has nothing before it.
+                let previous: Option<Vec<(&str, usize)>> = cell
+                    .iter()
+                    .map(|&(axis, index)| {
+                        if axis == chain {
+                            index.checked_sub(1).map(|index| (axis, index))
+                        } else {
+                            Some((axis, index))
+                        }
+                    })
+                    .collect();
+                if let Some(previous) = previous {
+                    let requires = table
+                        .entry("requires")
+                        .or_insert_with(|| toml::Value::Array(vec![]));
+                    if let toml::Value::Array(requires) = requires {
+                        requires.push(toml::Value::String(name(&previous)));
+                    }
+                }
+            }
+
+            let group: Group = toml::Value::Table(table)
+                .try_into()
+                .with_context(|| format!("expanding the matrix `{}`", self.name))?;
+            if !names.insert(group.name.clone()) {
+                anyhow::bail!(
+                    "the matrix `{}` names more than one group `{}`; \
+                     its name should use every axis",
+                    self.name,
+                    group.name
+                );
+            }
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// `text` with `{axis}` replaced by the axis's value in `cell`.
+    fn fill(&self, text: &str, cell: &[(&str, usize)]) -> String {
+        let mut text = text.to_string();
+        for (axis, &(_, index)) in self.axes.iter().zip(cell) {
+            text = text.replace(&format!("{{{}}}", axis.name), &axis.values[index]);
+        }
+        text
+    }
+
+    fn fill_value(&self, value: &mut toml::Value, cell: &[(&str, usize)]) {
+        match value {
+            toml::Value::String(text) => *text = self.fill(text, cell),
+            toml::Value::Array(values) => {
+                for value in values {
+                    self.fill_value(value, cell);
+                }
+            }
+            toml::Value::Table(table) => {
+                for (_, value) in table.iter_mut() {
+                    self.fill_value(value, cell);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A `[[template]]`: what a family of similar groups has in common, for
+/// groups to pick up with `extends`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Template {
+    pub name: String,
+
+    /// Another template this one builds on; this one's fields win.
+    pub extends: Option<String>,
+    pub cluster: Option<String>,
+    pub description: Option<Vec<String>>,
+    pub status: Option<Status>,
+    pub header_color: Option<String>,
+    pub description_color: Option<String>,
+    pub width: Option<f64>,
+
+    /// Items every group extending the template starts with. A group's
+    /// own item with the same `label` adds to the template's item rather
+    /// than repeating it.
+    #[serde(default, deserialize_with = "deserialize_items")]
+    pub items: Vec<Item>,
+
+    /// Column values for the items of groups extending the template,
+    /// where they don't set their own.
+    pub defaults: Option<HashMap<String, String>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Group {
+    pub name: String,
+
+    /// The `[[template]]` this group fills its unset fields from.
+    pub extends: Option<String>,
+
+    /// Items generated one per value of a list, before `items`; see
+    /// `ItemsFrom`.
+    pub items_from: Option<ItemsFrom>,
+    pub cluster: Option<String>,
+    pub label: Option<String>,
+    pub requires: Option<Vec<Requirement>>,
+
+    /// Nice-to-have orderings: drawn as dashed edges that don't affect
+    /// the layout.
+    pub suggests: Option<Vec<Requirement>>,
+    pub description: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_items")]
+    pub items: Vec<Item>,
+
+    /// The minimum width of the group's table, in points. Without it, the
+    /// width is worked out from the text in the table.
+    pub width: Option<f64>,
+    pub status: Option<Status>,
+    pub href: Option<String>,
+
+    /// Former names of this group, so that `requires` entries written
+    /// before a rename keep resolving.
+    pub aliases: Option<Vec<String>>,
+
+    /// Emoji tables for this group's items, keyed by column like
+    /// `doc.emoji`. They take precedence over `doc.emoji`.
+    pub emoji: Option<HashMap<String, EmojiMap>>,
+    pub header_color: Option<String>,
+    pub description_color: Option<String>,
+
+    /// Marks a group that is meant to have no dependencies either way,
+    /// which silences the `orphan-group` lint for it.
+    pub standalone: Option<bool>,
+
+    /// Makes this group a goal, as if it were listed in `doc.goals`.
+    pub goal: Option<bool>,
+
+    /// When the group's status changed, in any order, e.g.
+    /// `[{ status = "Assigned", date = "2024-01-10" }]`. Used to show
+    /// the tree as it was on an earlier date; see `SkillTree::as_of`.
+    pub status_history: Option<Vec<StatusChange>>,
+
+    /// When work on the group starts and is due, for the timeline view.
+    pub start: Option<Date>,
+    pub due: Option<Date>,
+
+    /// Where this group is declared, when it was loaded from a file.
+    #[serde(skip)]
+    source: Option<Source>,
+
+    /// Set when the group leaves out `items` with nothing to fill them,
+    /// which only a group overriding one from an include may do.
+    #[serde(skip)]
+    without_items: bool,
+}
+
+/// `items_from = { list = ["x86_64", "wasm32"], label = "Support {value}" }`:
+/// an item for each value in `list`, with `{value}` replaced by the value
+/// in each of the other keys. An item in `items` with the same `label` as
+/// a generated one adds to it instead of being a separate item.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ItemsFrom {
+    pub list: Vec<String>,
+    #[serde(flatten)]
+    pub columns: HashMap<String, String>,
+}
+
+impl ItemsFrom {
+    /// The generated items, in the order of `list`.
+    pub fn items(&self) -> Vec<Item> {
+        self.list
+            .iter()
+            .map(|value| {
+                self.columns
+                    .iter()
+                    .map(|(key, text)| (key.clone(), text.replace("{value}", value)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// An entry in a group's `status_history`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusChange {
+    pub status: Status,
+    pub date: Date,
+}
+
+/// An entry in a group's `requires` list. The group may be in another
+/// tree of the workspace (see `SkillTree::load_workspace`), written as
+/// `"tree::group"`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Requirement {
+    /// `"group"` or `"group:port"`
+    Name(String),
+
+    /// `{ group = "group", port = "port", label = "needs API freeze" }`
+    Detailed {
+        group: String,
+
+        /// The `port` of an item in `group` that the edge should point at.
+        port: Option<String>,
+
+        /// Text shown on the dependency edge.
+        label: Option<String>,
+    },
+}
+
+/// Whether a dependency comes from `requires` or `suggests`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    Hard,
+    Soft,
+}
+
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct GroupIndex(pub usize);
+
+/// A dependency of one group on another in the same tree, with the group
+/// looked up and the port split off (see `SkillTree::edges`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edge {
+    /// The group with the dependency.
+    pub from: GroupIndex,
+
+    /// The group depended on. (Rendered graphs draw the arrow the other
+    /// way, from `to` to `from`.)
+    pub to: GroupIndex,
+
+    /// The `port` of an item in `to` that the dependency names, if any.
+    pub to_port: Option<String>,
+
+    pub kind: EdgeKind,
+}
+
+pub type Item = HashMap<String, String>;
+
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct ItemIndex(pub usize);
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum Status {
+    /// Can't work on it now
+    Blocked,
+
+    /// Would like to work on it, but need someone
+    Unassigned,
+
+    /// People are actively working on it
+    Assigned,
+
+    /// This is done!
+    Complete,
+
+    /// A status defined in `[doc.statuses]` (checked by `validate`).
+    Custom(String),
+}
+
+/// An entry in `[doc.statuses]`, defining a status besides the built-in
+/// ones, e.g. `Review = { color = "lightblue", emoji = "👀" }`.
+#[derive(Clone, Default, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusDefinition {
+    /// Background of the header of groups, and the cells of items, with
+    /// this status.
+    pub color: Option<String>,
+
+    /// Shown in the `status` column for items with this status, unless
+    /// `doc.emoji` says otherwise.
+    pub emoji: Option<String>,
+
+    /// Whether items with this status count as done.
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl SkillTree {
+    pub fn load(path: &Path) -> anyhow::Result<SkillTree> {
+        let mut loaded = HashSet::default();
+        loaded.insert(path.to_owned());
+        let mut tree = Self::load_included_path(path, &mut loaded, &mut vec![])?;
+        tree.loaded = loaded;
+        tree.exclude_groups();
+        Ok(tree)
+    }
+
+    /// Like `load`, but takes the text of the root file from `text`
+    /// rather than reading it from `path` (e.g., an unsaved editor buffer).
+    /// Includes are still resolved relative to `path`.
+    pub fn load_text(path: &Path, text: &str) -> anyhow::Result<SkillTree> {
+        let mut loaded = HashSet::default();
+        loaded.insert(path.to_owned());
+        let mut tree = SkillTree::parse_file(path, text)?;
+        tree.import(path, &mut loaded, &mut vec![canonical(path)])?;
+        tree.loaded = loaded;
+        tree.exclude_groups();
+        Ok(tree)
+    }
+
+    /// Loads the file at `path` and its includes. `loading` holds the
+    /// (canonical) paths of the files whose includes are being loaded,
+    /// outermost first, to catch a file that ends up including itself.
+    fn load_included_path(
+        path: &Path,
+        loaded: &mut HashSet<PathBuf>,
+        loading: &mut Vec<PathBuf>,
+    ) -> anyhow::Result<SkillTree> {
+        fn load(
+            path: &Path,
+            loaded: &mut HashSet<PathBuf>,
+            loading: &mut Vec<PathBuf>,
+        ) -> anyhow::Result<SkillTree> {
+            let skill_tree_text = std::fs::read_to_string(path)?;
+            let mut tree = SkillTree::parse_file(path, &skill_tree_text)?;
+            tree.import(path, loaded, loading)?;
+            Ok(tree)
+        }
+
+        let canonical = canonical(path);
+        if let Some(start) = loading.iter().position(|p| *p == canonical) {
+            let cycle: Vec<String> = loading[start..]
+                .iter()
+                .chain(Some(&canonical))
+                .map(|p| format!("`{}`", p.display()))
+                .collect();
+            anyhow::bail!(
+                "files include each other in a cycle: {}",
+                cycle.join(" -> ")
+            );
+        }
+        loading.push(canonical);
+        let result = load(path, loaded, loading)
+            .with_context(|| format!("loading skill tree from `{}`", path.display()));
+        loading.pop();
+        result
+    }
+
+    fn import(
+        &mut self,
+        root_path: &Path,
+        loaded: &mut HashSet<PathBuf>,
+        loading: &mut Vec<PathBuf>,
+    ) -> anyhow::Result<()> {
+        // Only groups this file declares itself override included groups
+        // of the same name, and each only one of them.
+        let mut overrides: HashSet<String> = self.groups().map(|g| g.name.clone()).collect();
+        if let Some(doc) = &mut self.doc {
+            if let Some(include) = &mut doc.include {
+                let include = include.clone();
+                for include in include {
+                    let include_path = include.path().to_owned();
+                    // A file included in part may still be wanted in full
+                    // (or in another part) elsewhere, so only whole files
+                    // count as loaded. `loading` catches cycles through
+                    // partial includes.
+                    if loaded.contains(&include_path) {
+                        continue;
+                    }
+                    if let Include::Path(_) = include {
+                        loaded.insert(include_path.clone());
+                    }
+
+                    let tree_path = root_path.parent().unwrap().join(&include_path);
+                    let mut toml: SkillTree =
+                        SkillTree::load_included_path(&tree_path, loaded, loading)?;
+                    if let Include::Selected {
+                        groups, clusters, ..
+                    } = &include
+                    {
+                        toml.select(groups.as_deref(), clusters.as_deref())
+                            .with_context(|| format!("including `{}`", tree_path.display()))?;
+                    }
+                    self.merge(toml, &mut overrides);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps only the groups named in `groups` and those in the clusters
+    /// named in `clusters`, and the clusters those groups are in.
+    #[throws(anyhow::Error)]
+    fn select(&mut self, groups: Option<&[String]>, clusters: Option<&[String]>) {
+        let groups = groups.unwrap_or_default();
+        let clusters = clusters.unwrap_or_default();
+        for name in groups {
+            if self.group_named(name).is_none() {
+                anyhow::bail!("there is no group `{}` to include", name);
+            }
+        }
+        for name in clusters {
+            if self.cluster_named(name).is_none() {
+                anyhow::bail!("there is no cluster `{}` to include", name);
+            }
+        }
+
+        let in_selected_cluster =
+            |group: &Group| group.cluster.as_ref().is_some_and(|c| clusters.contains(c));
+        if let Some(all) = &mut self.group {
+            all.retain(|group| {
+                groups.contains(&group.name)
+                    || group.aliases().any(|alias| groups.contains(alias))
+                    || in_selected_cluster(group)
+            });
+        }
+        let used: HashSet<String> = self
+            .groups()
+            .filter_map(|group| group.cluster.clone())
+            .collect();
+        if let Some(all) = &mut self.cluster {
+            all.retain(|cluster| used.contains(&cluster.name) || clusters.contains(&cluster.name));
+        }
+    }
+
+    /// Drops the groups matched by `doc.exclude_groups`, splicing their
+    /// edges: a group that required a dropped group now requires what the
+    /// dropped group required. A path through a `suggests` edge stays a
+    /// suggestion.
+    fn exclude_groups(&mut self) {
+        let patterns = match self.doc.as_ref().and_then(|d| d.exclude_groups.as_ref()) {
+            Some(patterns) => patterns,
+            None => return,
+        };
+        let excluded: HashSet<String> = self
+            .groups()
+            .filter(|group| patterns.iter().any(|p| glob_matches(p, &group.name)))
+            .map(|group| group.name.clone())
+            .collect();
+        self.remove_groups(&excluded);
+    }
+
+    /// Drops the groups named in `excluded`, splicing their edges as
+    /// `exclude_groups` describes.
+    pub(crate) fn remove_groups(&mut self, excluded: &HashSet<String>) {
+        if excluded.is_empty() {
+            return;
+        }
+
+        // By position, since names aren't checked to be unique until
+        // the tree is validated.
+        let spliced: Vec<Option<Vec<(Requirement, EdgeKind)>>> = self
+            .groups()
+            .map(|group| {
+                if excluded.contains(&group.name) {
+                    return None;
+                }
+                let mut dependencies = vec![];
+                let mut visited = HashSet::new();
+                self.splice(
+                    group,
+                    EdgeKind::Hard,
+                    excluded,
+                    &mut visited,
+                    &mut dependencies,
+                );
+                Some(dependencies)
+            })
+            .collect();
+
+        let groups = self.group.take().unwrap_or_default();
+        let kept = groups
+            .into_iter()
+            .zip(spliced)
+            .filter_map(|(group, dependencies)| {
+                dependencies.map(|dependencies| (group, dependencies))
+            });
+        let mut groups = vec![];
+        for (mut group, dependencies) in kept {
+            let (requires, suggests): (Vec<_>, Vec<_>) = dependencies
+                .into_iter()
+                .partition(|(_, kind)| *kind == EdgeKind::Hard);
+            let strip = |dependencies: Vec<(Requirement, EdgeKind)>| -> Vec<Requirement> {
+                dependencies.into_iter().map(|(r, _)| r).collect()
+            };
+            if group.requires.is_some() || !requires.is_empty() {
+                group.requires = Some(strip(requires));
+            }
+            if group.suggests.is_some() || !suggests.is_empty() {
+                group.suggests = Some(strip(suggests));
+            }
+            groups.push(group);
+        }
+        self.group = Some(groups);
+    }
+
+    /// Collects into `found` the dependencies of `group`, reached through
+    /// an edge of `kind`, looking through the groups in `excluded`. Each
+    /// required group appears once, as a hard dependency if any path to
+    /// it is all hard.
+    fn splice<'t>(
+        &'t self,
+        group: &'t Group,
+        kind: EdgeKind,
+        excluded: &HashSet<String>,
+        visited: &mut HashSet<(&'t str, EdgeKind)>,
+        found: &mut Vec<(Requirement, EdgeKind)>,
+    ) {
+        // Dependencies on other trees are kept as they are.
+        for (requirement, edge) in group.all_dependencies() {
+            let kind = if kind == EdgeKind::Hard && edge == EdgeKind::Hard {
+                EdgeKind::Hard
+            } else {
+                EdgeKind::Soft
+            };
+            let target = match requirement.tree() {
+                Some(_) => None,
+                None => self.group_named(requirement.group()),
+            };
+            if let Some(target) = target.filter(|t| excluded.contains(&t.name)) {
+                if visited.insert((&target.name, kind)) {
+                    self.splice(target, kind, excluded, visited, found);
+                }
+                continue;
+            }
+            let same_group = |(r, _): &&mut (Requirement, EdgeKind)| {
+                r.tree() == requirement.tree() && r.group() == requirement.group()
+            };
+            match found.iter_mut().find(same_group) {
+                Some(existing) if kind == EdgeKind::Hard => existing.1 = EdgeKind::Hard,
+                Some(_) => {}
+                None => found.push((requirement.clone(), kind)),
+            }
+        }
+    }
+
+    /// Loads the tree at `path` (with its includes) and merges it into
+    /// this one, as if this tree's root file included it.
+    pub fn merge_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        let mut loaded = std::mem::take(&mut self.loaded);
+        let result = if loaded.insert(path.to_owned()) {
+            SkillTree::load_included_path(path, &mut loaded, &mut vec![])
+                .map(|tree| self.merge(tree, &mut HashSet::default()))
+        } else {
+            Ok(())
+        };
+        self.loaded = loaded;
+        result
+    }
+
+    /// Merges the groups and clusters of `toml`, an included or merged
+    /// file, into this tree. A group of `toml` named in `overrides` is
+    /// merged into this tree's group of that name (and taken out of
+    /// `overrides`); any other is added, so a name used twice is reported
+    /// by `validate`.
+    fn merge(&mut self, mut toml: SkillTree, overrides: &mut HashSet<String>) {
+        // merge columns, and any defaults/emojis associated with the new columns
+        let self_doc = self.doc.get_or_insert(Doc::default());
+        let toml_doc = toml.doc.get_or_insert(Doc::default());
+        for column in toml_doc.columns.get_or_insert(vec![]).iter() {
+            let columns = self_doc.columns.get_or_insert(vec![]);
+            if !columns.iter().any(|c| c.name() == column.name()) {
+                columns.push(column.clone());
+                let column = column.name();
+                if let Some(source) = toml.column_sources.remove(column) {
+                    self.column_sources.insert(column.to_string(), source);
+                }
+
+                if let Some(value) = toml_doc.emoji.get_or_insert(HashMap::default()).get(column) {
+                    self_doc
+                        .emoji
+                        .get_or_insert(HashMap::default())
+                        .insert(column.to_string(), value.clone());
+                }
+
+                if let Some(defaults) = &toml_doc.defaults {
+                    let self_defaults = self_doc.defaults.get_or_insert_with(Defaults::default);
+                    if let Some(value) = defaults.columns.get(column) {
+                        self_defaults
+                            .columns
+                            .insert(column.to_string(), value.clone());
+                    }
+                    for (cluster, values) in &defaults.cluster {
+                        if let Some(value) = values.get(column) {
+                            self_defaults
+                                .cluster
+                                .entry(cluster.clone())
+                                .or_default()
+                                .insert(column.to_string(), value.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        // A stylesheet from an include applies unless this file has its
+        // own.
+        if self_doc.css.is_none() {
+            self_doc.css = toml_doc.css.take();
+        }
+
+        // A group declared again here overrides the included one field by
+        // field, so a shared group can be adjusted without editing the file
+        // it comes from.
+        let groups = self.group.get_or_insert(vec![]);
+        for group in toml.group.into_iter().flatten() {
+            let local = match overrides.remove(&group.name) {
+                true => groups.iter_mut().find(|g| g.name == group.name),
+                false => None,
+            };
+            match local {
+                Some(local) => local.fill_from(group),
+                None => groups.push(group),
+            }
+        }
+
+        self.cluster
+            .get_or_insert(vec![])
+            .extend(toml.cluster.into_iter().flatten());
+
+        self.parse_warnings.extend(toml.parse_warnings);
+        self.files.extend(toml.files);
+    }
+
+    /// Parses a single file. Its matrices are expanded, and its generated
+    /// items, templates and `doc.group_defaults` filled into its groups,
+    /// here, so they don't reach groups from included files.
+    #[throws(anyhow::Error)]
+    pub fn parse(text: &str) -> SkillTree {
+        let mut tree: SkillTree = toml::from_str(text)?;
+        tree.find_groups_without_items(text)?;
+        tree.expand_matrices()?;
+        tree.generate_items();
+        tree.apply_templates()?;
+        tree.apply_group_defaults();
+        tree
+    }
+
+    /// Marks the groups of the file `text` that leave out `items` and have
+    /// no `extends` or `items_from` to fill them.
+    #[throws(anyhow::Error)]
+    fn find_groups_without_items(&mut self, text: &str) {
+        let candidate =
+            |g: &Group| g.items.is_empty() && g.extends.is_none() && g.items_from.is_none();
+        // Telling a missing `items` from `items = []` takes a second parse,
+        // so only do it when it might matter.
+        if !self.groups().any(candidate) {
+            return;
+        }
+        let value: toml::Value = toml::from_str(text)?;
+        let tables = value.get("group").and_then(|g| g.as_array());
+        for (group, table) in self
+            .group
+            .iter_mut()
+            .flatten()
+            .zip(tables.into_iter().flatten())
+        {
+            group.without_items = candidate(group) && table.get("items").is_none();
+        }
+    }
+
+    /// Adds the groups of each `[[matrix]]` after the file's own groups.
+    #[throws(anyhow::Error)]
+    fn expand_matrices(&mut self) {
+        for matrix in self.matrix.iter().flatten() {
+            let groups = matrix.groups()?;
+            self.group.get_or_insert(vec![]).extend(groups);
+        }
+    }
+
+    /// Adds the items of each group's `items_from`.
+    fn generate_items(&mut self) {
+        for group in self.group.iter_mut().flatten() {
+            if let Some(items_from) = &group.items_from {
+                group.items = merge_items(items_from.items(), &group.items);
+            }
+        }
+    }
+
+    /// Fills each group that `extends` a template from it and the
+    /// templates it builds on.
+    #[throws(anyhow::Error)]
+    fn apply_templates(&mut self) {
+        let templates = self.template.as_deref().unwrap_or_default();
+        for group in self.group.iter_mut().flatten() {
+            let name = match &group.extends {
+                Some(name) => name,
+                None => continue,
+            };
+            // The chain of templates, most specific first.
+            let mut chain: Vec<&Template> = vec![];
+            let mut next = Some(name);
+            while let Some(name) = next {
+                let template = match templates.iter().find(|t| t.name == *name) {
+                    Some(template) => template,
+                    None if chain.is_empty() => anyhow::bail!(
+                        "the group `{}` extends `{}`, which is not a template",
+                        group.name,
+                        name
+                    ),
+                    None => anyhow::bail!(
+                        "the template `{}` extends `{}`, which is not a template",
+                        chain[chain.len() - 1].name,
+                        name
+                    ),
+                };
+                if chain.iter().any(|t| t.name == template.name) {
+                    anyhow::bail!("the template `{}` extends itself", template.name);
+                }
+                chain.push(template);
+                next = template.extends.as_ref();
+            }
+
+            let mut items: Vec<Item> = vec![];
+            let mut defaults = HashMap::new();
+            for template in chain.iter().rev() {
+                items = merge_items(items, &template.items);
+                defaults.extend(template.defaults.iter().flatten());
+            }
+            group.items = merge_items(items, &group.items);
+            for item in &mut group.items {
+                for (&column, &value) in &defaults {
+                    item.entry(column.clone()).or_insert_with(|| value.clone());
+                }
+            }
+
+            for template in chain {
+                if group.cluster.is_none() {
+                    group.cluster = template.cluster.clone();
+                }
+                if group.description.is_none() {
+                    group.description = template.description.clone();
+                }
+                if group.status.is_none() {
+                    group.status = template.status.clone();
+                }
+                if group.header_color.is_none() {
+                    group.header_color = template.header_color.clone();
+                }
+                if group.description_color.is_none() {
+                    group.description_color = template.description_color.clone();
+                }
+                if group.width.is_none() {
+                    group.width = template.width;
+                }
+            }
+        }
+    }
+
+    fn apply_group_defaults(&mut self) {
+        let defaults = match self
+            .doc
+            .as_ref()
+            .and_then(|doc| doc.group_defaults.as_ref())
+        {
+            Some(defaults) => defaults,
+            None => return,
+        };
+        for group in self.group.iter_mut().flatten() {
+            if group.status.is_none() {
+                group.status = defaults.status.clone();
+            }
+            if group.header_color.is_none() {
+                group.header_color = defaults.header_color.clone();
+            }
+            if group.description_color.is_none() {
+                group.description_color = defaults.description_color.clone();
+            }
+            if group.width.is_none() {
+                group.width = defaults.width;
+            }
+        }
+    }
+
+    /// Parses `text`, which was read from `path`, noting any deprecated
+    /// spellings it uses.
+    #[throws(anyhow::Error)]
+    fn parse_file(path: &Path, text: &str) -> SkillTree {
+        let mut tree = SkillTree::parse(text)?;
+        tree.files.push(path.to_owned());
+        // `doc.css` is relative to the file that sets it, which may be an
+        // include in another directory.
+        if let Some(css) = tree.doc.as_mut().and_then(|doc| doc.css.as_mut()) {
+            if let Some(dir) = path.parent() {
+                *css = dir.join(&css);
+            }
+        }
+        let source = |index: Option<usize>| {
+            index.map(|index| Source {
+                path: path.to_owned(),
+                line: index + 1,
+            })
+        };
+        for cluster in tree.cluster.iter_mut().flatten() {
+            cluster.source = source(source::cluster_definition_line(text, &cluster.name));
+        }
+        for group in tree.group.iter_mut().flatten() {
+            group.source = source(source::group_definition_line(text, &group.name));
+        }
+        if let Some(line) = source(source::doc_key_line(text, "columns")) {
+            let names: Vec<String> = tree
+                .columns()
+                .iter()
+                .map(|c| c.name().to_string())
+                .collect();
+            for name in names {
+                tree.column_sources.insert(name, line.clone());
+            }
+        }
+        for deprecation in deprecated::find(&tree, text) {
+            let message = format!(
+                "`{}` is deprecated; write `{}` instead",
+                deprecation.old, deprecation.new,
+            );
+            tree.parse_warnings
+                .push(
+                    Diagnostic::warning("deprecated", message).at(Location::Line {
+                        path: path.to_owned(),
+                        line: deprecation.line,
+                    }),
+                );
+        }
+        tree
+    }
+
+    /// The schema version this tree was written for.
+    pub fn schema_version(&self) -> u32 {
+        self.doc
+            .as_ref()
+            .and_then(|doc| doc.schema_version)
+            .unwrap_or(1)
+    }
+
+    #[throws(anyhow::Error)]
+    pub fn validate(&self) {
+        if let Some(error) = self.validate_all().into_iter().next() {
+            throw!(error);
+        }
+    }
+
+    /// Like `validate`, but keeps going after the first error and
+    /// returns every error found.
+    pub fn validate_all(&self) -> Vec<anyhow::Error> {
+        let mut errors: Vec<anyhow::Error> = self
+            .errors()
+            .into_iter()
+            .map(|(error, location)| match location {
+                Some(Location::Line { path, line }) => {
+                    anyhow::anyhow!("{}:{}: {:#}", path.display(), line, error)
+                }
+                _ => error,
+            })
+            .collect();
+        errors.extend(
+            lint::run(self)
+                .into_iter()
+                .filter(|diagnostic| diagnostic.level == Severity::Error)
+                .map(|diagnostic| anyhow::anyhow!(diagnostic.to_string())),
+        );
+        errors
+    }
+
+    /// Every problem found in this tree, fatal or not, in the order
+    /// errors, warnings, lints.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self
+            .errors()
+            .into_iter()
+            .map(|(error, location)| Diagnostic {
+                location,
+                ..Diagnostic::error(format!("{:#}", error))
+            })
+            .collect();
+        diagnostics.extend(self.warning_diagnostics());
+        diagnostics.extend(lint::run(self));
+        diagnostics
+    }
+
+    /// Errors other than those from lints, with what they're about when
+    /// that's a single group.
+    fn errors(&self) -> Vec<(anyhow::Error, Option<Location>)> {
+        let mut errors = vec![];
+        if self.schema_version() > SCHEMA_VERSION {
+            errors.push(anyhow::anyhow!(
+                "this tree uses schema version {}, but this version of skill-tree \
+                 only understands up to version {}",
+                self.schema_version(),
+                SCHEMA_VERSION,
+            ));
+        }
+
+        let mut column_errors = vec![];
+        for column in self.columns() {
+            if let Err(error) = column.validate() {
+                column_errors.push((error, self.column_source(column.name())));
+            }
+        }
+        if let Some(defaults) = self.doc.as_ref().and_then(|doc| doc.defaults.as_ref()) {
+            for column in self.columns() {
+                if let Some(value) = defaults.columns.get(column.name()) {
+                    if let Err(expected) = column.check(value) {
+                        errors.push(anyhow::anyhow!(
+                            "the default `{}` for column `{}` is invalid: expected {}",
+                            value,
+                            column.name(),
+                            expected,
+                        ));
+                    }
+                }
+            }
+            let mut clusters: Vec<_> = defaults.cluster.iter().collect();
+            clusters.sort_by_key(|(cluster, _)| *cluster);
+            for (cluster, values) in clusters {
+                if self.cluster_named(cluster).is_none() {
+                    errors.push(anyhow::anyhow!(
+                        "`doc.defaults.cluster` has defaults for `{}`, which is not a cluster{}",
+                        cluster,
+                        suggest::did_you_mean(
+                            cluster,
+                            self.cluster.iter().flatten().map(|c| &c.name[..])
+                        ),
+                    ));
+                }
+                for column in self.columns() {
+                    if let Some(value) = values.get(column.name()) {
+                        if let Err(expected) = column.check(value) {
+                            errors.push(anyhow::anyhow!(
+                                "the default `{}` for column `{}` in cluster `{}` is invalid: expected {}",
+                                value,
+                                column.name(),
+                                cluster,
+                                expected,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(graphviz) = &self.graphviz {
+            for (table, attrs) in graphviz.passthrough_attrs() {
+                for name in attrs.into_iter().flat_map(|attrs| attrs.keys()) {
+                    if !is_dot_identifier(name) {
+                        errors.push(anyhow::anyhow!(
+                            "`graphviz.{}` has the attribute `{}`, but attribute names \
+                             are letters, digits and `_`",
+                            table,
+                            name,
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(doc) = &self.doc {
+            match (&doc.base_url, self.link_style()) {
+                (Some(base_url), _) if !link::is_absolute(base_url) => {
+                    errors.push(anyhow::anyhow!(
+                        "`doc.base_url` should be an absolute URL like `https://example.org/`, \
+                         found `{}`",
+                        base_url
+                    ))
+                }
+                (None, style @ (LinkStyle::Absolute | LinkStyle::Relative)) => {
+                    errors.push(anyhow::anyhow!(
+                        "`doc.links = \"{}\"` needs a `doc.base_url`",
+                        if style == LinkStyle::Absolute {
+                            "absolute"
+                        } else {
+                            "relative"
+                        }
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        let palette = self.doc.as_ref().and_then(|doc| doc.colors.as_ref());
+        let mut names: Vec<_> = palette.into_iter().flatten().collect();
+        names.sort();
+        for (name, value) in names {
+            if !color::is_valid(value) {
+                errors.push(anyhow::anyhow!(
+                    "`doc.colors.{}` is `{}`, which is not a color{}",
+                    name,
+                    value,
+                    suggest::did_you_mean(value, color::names()),
+                ));
+            }
+        }
+        let palette_names: Vec<&str> = palette
+            .into_iter()
+            .flat_map(|palette| palette.keys().map(String::as_str))
+            .collect();
+        for (place, value) in self.colors_used() {
+            let in_palette = palette.is_some_and(|palette| palette.contains_key(value));
+            if !in_palette && !color::is_valid(value) {
+                errors.push(anyhow::anyhow!(
+                    "{} is `{}`, which is neither a color nor in `doc.colors`{}",
+                    place,
+                    value,
+                    suggest::did_you_mean(
+                        value,
+                        palette_names.iter().copied().chain(color::names())
+                    ),
+                ));
+            }
+        }
+
+        // The template doesn't depend on the group, so one check is enough.
+        if let Some(group) = self.groups().next() {
+            if let Err(error) = self.group_header(group) {
+                errors.push(error.context("invalid `doc.group_label_template`"));
+            }
+        }
+
+        if let Some(lints) = self.doc.as_ref().and_then(|doc| doc.lints.as_ref()) {
+            let mut names: Vec<&String> = lints.keys().collect();
+            names.sort();
+            for name in names {
+                if lint::find(name).is_none() {
+                    errors.push(anyhow::anyhow!(
+                        "unknown lint `{}` in `doc.lints`; the lints are {}",
+                        name,
+                        lint::LINTS
+                            .iter()
+                            .map(|lint| format!("`{}`", lint.name))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ));
+                }
+            }
+        }
+
+        if let Some(statuses) = self.doc.as_ref().and_then(|doc| doc.statuses.as_ref()) {
+            for status in &Status::BUILTIN {
+                if statuses.contains_key(status.name()) {
+                    errors.push(anyhow::anyhow!(
+                        "`doc.statuses` defines `{}`, which is a built-in status",
+                        status.name()
+                    ));
+                }
+            }
+        }
+
+        if let Some(labels) = self.doc.as_ref().and_then(|doc| doc.issue_labels.as_ref()) {
+            let known = self.status_names();
+            let mut names: Vec<&String> = labels.keys().collect();
+            names.sort();
+            for name in names {
+                if !known.contains(&&name[..]) {
+                    errors.push(anyhow::anyhow!(
+                        "`doc.issue_labels` has a label for `{}`, which is not a status{}",
+                        name,
+                        suggest::did_you_mean(name, known.iter().copied())
+                    ));
+                }
+            }
+        }
+
+        for goal in self.goals() {
+            if self.group_named(goal).is_none() {
+                errors.push(anyhow::anyhow!(
+                    "the goal `{}` in `doc.goals` is not a group",
+                    goal
+                ));
+            }
+        }
+
+        // gather: valid requires entries
+
+        let mut errors: Vec<_> = errors.into_iter().map(|error| (error, None)).collect();
+        for (error, source) in column_errors {
+            errors.push((error, source.map(Location::from)));
+        }
+        errors.extend(self.groups().filter_map(|group| {
+            let location = match group.source() {
+                Some(source) => Location::from(source),
+                None => Location::Group(group.name.clone()),
+            };
+            group
+                .validate(self)
+                .err()
+                .map(|error| (error, Some(location)))
+        }));
+        errors
+    }
+
+    /// The level of `lint` in this tree: from `doc.lints`, or its default.
+    pub fn lint_level(&self, lint: &Lint) -> Level {
+        self.doc
+            .as_ref()
+            .and_then(|doc| doc.lints.as_ref())
+            .and_then(|lints| lints.get(lint.name))
+            .copied()
+            .unwrap_or(lint.default)
+    }
+
+    /// Sets the level of the lint `name`, overriding `doc.lints` (as
+    /// `--allow`, `--warn` and `--deny` do).
+    #[throws(anyhow::Error)]
+    pub fn set_lint_level(&mut self, name: &str, level: Level) {
+        if lint::find(name).is_none() {
+            throw!(anyhow::anyhow!("unknown lint `{}`", name));
+        }
+        self.doc
+            .get_or_insert_with(Doc::default)
+            .lints
+            .get_or_insert_with(HashMap::default)
+            .insert(name.to_string(), level);
+    }
+
+    /// Returns problems that don't stop the tree from rendering but that
+    /// the author should fix.
+    pub fn warnings(&self) -> Vec<String> {
+        let lints = lint::run(self);
+        self.warning_diagnostics()
+            .into_iter()
+            .chain(lints)
+            .filter(|diagnostic| diagnostic.level == Severity::Warning)
+            .map(|diagnostic| diagnostic.to_string())
+            .collect()
+    }
+
+    /// Warnings other than those from lints.
+    fn warning_diagnostics(&self) -> Vec<Diagnostic> {
+        let mut warnings = self.parse_warnings.clone();
+        if let Some(doc) = &self.doc {
+            if let Some(locale) = &doc.locale {
+                if !locale::LOCALES.contains(&&locale[..]) && doc.translations.is_none() {
+                    warnings.push(Diagnostic::warning(
+                        "unknown-locale",
+                        format!(
+                            "there are no built-in translations for the locale `{}` \
+                             (there are for {}); add them under `doc.translations`",
+                            locale,
+                            locale::LOCALES.join(", "),
+                        ),
+                    ));
+                }
+            }
+        }
+        for group in self.groups() {
+            for (requirement, _) in group.dependencies() {
+                let target = requirement.group();
+                if let Some(other) = self.group_named(target) {
+                    if other.name != target {
+                        let message = format!(
+                            "the group `{}` refers to `{}` by its old name; use `{}` instead",
+                            group.name, target, other.name,
+                        );
+                        warnings.push(
+                            Diagnostic::warning("old-group-name", message)
+                                .at(Location::Group(group.name.clone())),
+                        );
+                    }
+                }
+            }
+        }
+        warnings
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &Group> {
+        match self.group {
+            Some(ref g) => g.iter(),
+            None => [].iter(),
+        }
+    }
+
+    /// The names in `doc.goals`.
+    pub fn goals(&self) -> impl Iterator<Item = &String> {
+        self.doc.iter().flat_map(|doc| doc.goals.iter().flatten())
+    }
+
+    /// Whether `group` is a goal: listed in `doc.goals` (perhaps by an
+    /// alias) or marked `goal = true`.
+    pub fn is_goal(&self, group: &Group) -> bool {
+        group.goal == Some(true)
+            || self
+                .goals()
+                .any(|goal| self.group_named(goal).is_some_and(|g| g.name == group.name))
+    }
+
+    /// Whether the tree declares any goals, even an empty `doc.goals`.
+    fn has_goals(&self) -> bool {
+        self.doc.as_ref().is_some_and(|doc| doc.goals.is_some())
+            || self.groups().any(|group| group.goal == Some(true))
+    }
+
+    /// The groups, goals first and otherwise in the order they appear.
+    pub fn groups_goals_first(&self) -> impl Iterator<Item = &Group> {
+        let goals = self.groups().filter(move |group| self.is_goal(group));
+        let others = self.groups().filter(move |group| !self.is_goal(group));
+        goals.chain(others)
+    }
+
+    /// The names of the groups that some goal depends on, directly or
+    /// through other groups (via `requires` or `suggests`), including the
+    /// goals themselves. Returns `None` if the tree has no goals.
+    pub fn reachable_from_goals(&self) -> Option<HashSet<&str>> {
+        if !self.has_goals() {
+            return None;
+        }
+        let mut reachable = HashSet::new();
+        let mut stack: Vec<&Group> = self.groups().filter(|g| self.is_goal(g)).collect();
+        while let Some(group) = stack.pop() {
+            if !reachable.insert(&group.name[..]) {
+                continue;
+            }
+            for (requirement, _) in group.dependencies() {
+                if let Some(other) = self.group_named(requirement.group()) {
+                    stack.push(other);
+                }
+            }
+        }
+        Some(reachable)
+    }
+
+    /// Drops the groups that no goal depends on (see
+    /// `reachable_from_goals`). Does nothing if the tree has no goals.
+    pub fn remove_unreachable(&mut self) {
+        let reachable: HashSet<String> = match self.reachable_from_goals() {
+            Some(reachable) => reachable.into_iter().map(str::to_string).collect(),
+            None => return,
+        };
+        if let Some(groups) = &mut self.group {
+            groups.retain(|group| reachable.contains(&group.name));
+        }
+    }
+
+    /// The groups in `cluster`, or outside any cluster if it is `None`.
+    pub fn groups_in(&self, cluster: Option<&str>) -> impl Iterator<Item = &Group> + '_ {
+        let cluster = cluster.map(str::to_string);
+        self.groups().filter(move |group| group.cluster == cluster)
+    }
+
+    /// Every file this tree was read from (the root file, then its
+    /// includes), e.g. to tell whether any of them changed.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Where the column `name` was declared, if it was loaded from a file.
+    pub fn column_source(&self, name: &str) -> Option<&Source> {
+        self.column_sources.get(name)
+    }
+
+    /// Finds the cluster called `name`.
+    pub fn cluster_named(&self, name: &str) -> Option<&Cluster> {
+        self.cluster.iter().flatten().find(|c| c.name == name)
+    }
+
+    /// Like `Group::progress`, summed over `groups`.
+    pub fn progress<'g>(&self, groups: impl IntoIterator<Item = &'g Group>) -> (usize, usize) {
+        groups
+            .into_iter()
+            .map(|group| group.progress(self))
+            .fold((0, 0), |(done, total), (d, t)| (done + d, total + t))
+    }
+
+    /// The default value of `column` for the items of `group`: the one for
+    /// its cluster in `doc.defaults.cluster`, if any, else the one in
+    /// `doc.defaults`.
+    pub fn column_default(&self, group: &Group, column: &str) -> Option<&str> {
+        let defaults = self.doc.as_ref()?.defaults.as_ref()?;
+        group
+            .cluster
+            .as_ref()
+            .and_then(|cluster| defaults.cluster.get(cluster))
+            .and_then(|values| values.get(column))
+            .or_else(|| defaults.columns.get(column))
+            .map(String::as_str)
+    }
+
+    /// The group at `index` in the order the groups are declared.
+    pub fn group_at(&self, index: GroupIndex) -> Option<&Group> {
+        self.groups().nth(index.0)
+    }
+
+    /// Every dependency between groups of this tree, by the group with
+    /// the dependency in the order the groups are declared, then
+    /// `requires` before `suggests`. Dependencies on groups that don't
+    /// exist, or that are in other trees, are left out.
+    pub fn edges(&self) -> Vec<Edge> {
+        let mut indices: HashMap<&str, GroupIndex> = HashMap::new();
+        for (index, group) in self.groups().enumerate() {
+            indices.entry(&group.name).or_insert(GroupIndex(index));
+        }
+        for (index, group) in self.groups().enumerate() {
+            for alias in group.aliases() {
+                indices.entry(&alias[..]).or_insert(GroupIndex(index));
+            }
+        }
+
+        let mut edges = vec![];
+        for (index, group) in self.groups().enumerate() {
+            for (requirement, kind) in group.dependencies() {
+                if let Some(&to) = indices.get(requirement.group()) {
+                    edges.push(Edge {
+                        from: GroupIndex(index),
+                        to,
+                        to_port: requirement.port().map(str::to_string),
+                        kind,
+                    });
+                }
+            }
+        }
+        edges
+    }
+
+    /// Finds the group called `name`, falling back to a group that lists
+    /// `name` among its `aliases`.
+    pub fn group_named(&self, name: &str) -> Option<&Group> {
+        self.groups()
+            .find(|g| g.name == name)
+            .or_else(|| self.groups().find(|g| g.aliases().any(|a| a == name)))
+    }
+
+    /// Returns the expected columns for each item (excluding the label).
+    pub fn columns(&self) -> &[Column] {
+        if let Some(doc) = &self.doc {
+            if let Some(columns) = &doc.columns {
+                return columns;
+            }
+        }
+
+        &[]
+    }
+
+    /// Translates `text`, one of the strings we generate, into
+    /// `doc.locale`, using `doc.translations` before the built-in tables.
+    /// Returns `text` unchanged if there's no translation.
+    pub fn translate<'a>(&'a self, text: &'a str) -> &'a str {
+        let doc = match &self.doc {
+            Some(doc) => doc,
+            None => return text,
+        };
+        if let Some(translated) = doc.translations.as_ref().and_then(|t| t.get(text)) {
+            return translated;
+        }
+        doc.locale
+            .as_deref()
+            .and_then(|locale| locale::builtin(locale, text))
+            .unwrap_or(text)
+    }
+
+    /// The name of `status` in `doc.locale`.
+    pub fn status_name<'a>(&'a self, status: &'a Status) -> &'a str {
+        self.translate(status.name())
+    }
+
+    /// `color`, or the color it names in `doc.colors`.
+    pub fn color<'a>(&'a self, color: &'a str) -> &'a str {
+        self.doc
+            .as_ref()
+            .and_then(|doc| doc.colors.as_ref())
+            .and_then(|colors| colors.get(color))
+            .map_or(color, String::as_str)
+    }
+
+    /// Every color set in the tree, with a description of where, in the
+    /// order they are checked.
+    fn colors_used(&self) -> Vec<(String, &str)> {
+        let mut colors = vec![];
+        if let Some(bgcolor) = self.graphviz.as_ref().and_then(|g| g.bgcolor.as_deref()) {
+            colors.push(("`graphviz.bgcolor`".to_string(), bgcolor));
+        }
+        for cluster in self.cluster.iter().flatten() {
+            if let Some(color) = &cluster.color {
+                colors.push((format!("the `color` of cluster `{}`", cluster.name), color));
+            }
+        }
+        let statuses = self.doc.as_ref().and_then(|doc| doc.statuses.as_ref());
+        let mut statuses: Vec<_> = statuses.into_iter().flatten().collect();
+        statuses.sort_by_key(|(name, _)| *name);
+        for (name, status) in statuses {
+            if let Some(color) = &status.color {
+                colors.push((format!("the `color` of status `{}`", name), color));
+            }
+        }
+        for group in self.groups() {
+            if let Some(color) = &group.header_color {
+                colors.push((
+                    format!("the `header_color` of group `{}`", group.name),
+                    color,
+                ));
+            }
+            if let Some(color) = &group.description_color {
+                colors.push((
+                    format!("the `description_color` of group `{}`", group.name),
+                    color,
+                ));
+            }
+        }
+        colors
+    }
+
+    /// The background of `group`'s header: its `header_color`, else the
+    /// color of its custom status, else its cluster's color if
+    /// `graphviz.tint_headers` is set, else the default. Names from
+    /// `doc.colors` are resolved.
+    pub fn header_color<'a>(&'a self, group: &'a Group) -> &'a str {
+        let status_color = group
+            .status
+            .as_ref()
+            .and_then(|status| self.status_definition(status.name()))
+            .and_then(|status| status.color.as_deref());
+        let tint = || {
+            if self.graphviz.as_ref().and_then(|g| g.tint_headers) != Some(true) {
+                return None;
+            }
+            let cluster = group.cluster.as_deref()?;
+            Some(self.cluster_colors().remove(cluster)?.header)
+        };
+        match group.header_color.as_deref().or(status_color) {
+            Some(color) => self.color(color),
+            None => tint().unwrap_or("darkgoldenrod"),
+        }
+    }
+
+    /// The colors of each cluster that has any: its own `color`, or with
+    /// `graphviz.cluster_colors` set, one from a palette. A cluster's
+    /// place in the palette comes from a hash of its name, so it keeps
+    /// its color as other clusters come and go, unless another cluster
+    /// already took that color.
+    pub fn cluster_colors(&self) -> HashMap<&str, ClusterColor<'_>> {
+        let auto = self.graphviz.as_ref().and_then(|g| g.cluster_colors) == Some(true);
+        let mut colors = HashMap::new();
+        let mut taken = vec![false; color::CLUSTER_PALETTE.len()];
+        for cluster in self.cluster.iter().flatten() {
+            let color = match &cluster.color {
+                Some(color) => {
+                    let color = self.color(color);
+                    ClusterColor {
+                        background: color,
+                        header: color,
+                    }
+                }
+                None if auto => {
+                    let len = taken.len();
+                    let start = color::stable_hash(&cluster.name) as usize % len;
+                    let index = (start..start + len)
+                        .map(|index| index % len)
+                        .find(|&index| !taken[index])
+                        .unwrap_or(start);
+                    taken[index] = true;
+                    let (header, background) = color::CLUSTER_PALETTE[index];
+                    ClusterColor { background, header }
+                }
+                None => continue,
+            };
+            colors.insert(&cluster.name[..], color);
+        }
+        colors
+    }
+
+    /// The definition of the custom status `name` in `[doc.statuses]`.
+    pub fn status_definition(&self, name: &str) -> Option<&StatusDefinition> {
+        self.doc.as_ref()?.statuses.as_ref()?.get(name)
+    }
+
+    /// Every status, built-in ones first, then those in `[doc.statuses]`
+    /// in name order.
+    pub fn status_names(&self) -> Vec<&str> {
+        let mut defined: Vec<&str> = self
+            .doc
+            .as_ref()
+            .and_then(|doc| doc.statuses.as_ref())
+            .into_iter()
+            .flat_map(|statuses| statuses.keys().map(|name| &name[..]))
+            .collect();
+        defined.sort_unstable();
+        Status::BUILTIN
+            .iter()
+            .map(Status::name)
+            .chain(defined)
+            .collect()
+    }
+
+    /// The GitHub label for the status `name`: its entry in
+    /// `doc.issue_labels`, or the name itself.
+    pub fn issue_label<'a>(&'a self, name: &'a str) -> &'a str {
+        self.doc
+            .as_ref()
+            .and_then(|doc| doc.issue_labels.as_ref())
+            .and_then(|labels| labels.get(name))
+            .map_or(name, |label| &label[..])
+    }
+
+    /// Whether an item with the status `name` counts as done: it is
+    /// `Complete`, or a custom status with `done = true`.
+    pub fn is_done(&self, name: &str) -> bool {
+        name == Status::Complete.name() || self.status_definition(name).is_some_and(|s| s.done)
+    }
+
+    /// `doc.title`, with `{date}` expanded.
+    pub fn title(&self) -> Option<String> {
+        let title = self.doc.as_ref()?.title.as_ref()?;
+        Some(title.replace("{date}", &self.date()))
+    }
+
+    /// `doc.caption`, with `{date}` expanded.
+    pub fn caption(&self) -> Option<String> {
+        let caption = self.doc.as_ref()?.caption.as_ref()?;
+        Some(caption.replace("{date}", &self.date()))
+    }
+
+    /// The CSS from `doc.css` and `doc.style`, in that order, or `None` if
+    /// neither is set.
+    #[throws(anyhow::Error)]
+    pub fn custom_css(&self) -> Option<String> {
+        let doc = match &self.doc {
+            Some(doc) if doc.css.is_some() || doc.style.is_some() => doc,
+            _ => return None,
+        };
+        let mut css = String::new();
+        if let Some(path) = &doc.css {
+            css.push_str(
+                &std::fs::read_to_string(path)
+                    .with_context(|| format!("reading `doc.css` from `{}`", path.display()))?,
+            );
+        }
+        if let Some(style) = &doc.style {
+            if !css.is_empty() && !css.ends_with('\n') {
+                css.push('\n');
+            }
+            css.push_str(style);
+        }
+        Some(css)
+    }
+
+    /// The date for `{date}`: today, or the date given to `as_of`.
+    fn date(&self) -> String {
+        match self.as_of {
+            Some(date) => date.to_string(),
+            None => date::today(),
+        }
+    }
+
+    /// Rewinds the tree to how it stood on `date`: each group with a
+    /// `status_history` gets the last status it had changed to by then,
+    /// or none if its first change came later. Groups without a history
+    /// keep their status.
+    pub fn as_of(&mut self, date: Date) {
+        for group in self.group.iter_mut().flatten() {
+            group.status = group.status_on(date).cloned();
+        }
+        self.as_of = Some(date);
+    }
+
+    /// The text of `group`'s header: its label, or `doc.group_label_template`
+    /// expanded with the placeholders `name`, `label`, `status`, `done`
+    /// (items that are done; see `is_done`), `total` and `percent`.
+    #[throws(anyhow::Error)]
+    pub fn group_header(&self, group: &Group) -> String {
+        let label = group.label.as_ref().unwrap_or(&group.name);
+        let template_text = match self
+            .doc
+            .as_ref()
+            .and_then(|d| d.group_label_template.as_ref())
+        {
+            Some(t) => t,
+            None => return label.clone(),
+        };
+
+        let (done, total) = group.progress(self);
+        template::render(template_text, |name| match name {
+            "name" => Some(group.name.clone()),
+            "label" => Some(label.clone()),
+            "status" => Some(
+                group
+                    .status
+                    .as_ref()
+                    .map(|s| self.status_name(s).to_string())
+                    .unwrap_or_default(),
+            ),
+            "done" => Some(done.to_string()),
+            "total" => Some(total.to_string()),
+            "percent" => Some((done * 100).checked_div(total).unwrap_or(0).to_string()),
+            _ => None,
+        })?
+    }
+
+    /// Like `emoji`, but for an item of `group`: the group's own `emoji`
+    /// table for `column` is consulted first.
+    pub fn group_emoji<'me>(
+        &'me self,
+        group: &'me Group,
+        column: &str,
+        input: &'me str,
+    ) -> &'me str {
+        if let Some(emoji_maps) = &group.emoji {
+            if let Some(emoji_map) = emoji_maps.get(column) {
+                if let Some(output) = emoji::lookup(emoji_map, input) {
+                    return output;
+                }
+            }
+        }
+        self.emoji(column, input)
+    }
+
+    /// Translates an "input" into an emoji, returning "input" if not found.
+    /// Keys of the emoji table may be patterns; see the `emoji` module.
+    pub fn emoji<'me>(&'me self, column: &str, input: &'me str) -> &'me str {
+        if let Some(doc) = &self.doc {
+            if let Some(emoji_maps) = &doc.emoji {
+                if let Some(emoji_map) = emoji_maps.get(column) {
+                    if let Some(output) = emoji::lookup(emoji_map, input) {
+                        return output;
+                    }
+                }
+            }
+        }
+        if let Some(emoji) = self.status_emoji(column, input) {
+            return emoji;
+        }
+        input
+    }
+
+    /// The `emoji` of the custom status `input`, if `column` is `status`.
+    pub fn status_emoji(&self, column: &str, input: &str) -> Option<&str> {
+        if column != "status" {
+            return None;
+        }
+        self.status_definition(input)?.emoji.as_deref()
+    }
+}
+
+impl Group {
+    /// Fills in the fields this group doesn't set from `other`, a group
+    /// of the same name from an included file. Items are taken from `other`
+    /// only if this group has none.
+    fn fill_from(&mut self, other: Group) {
+        fn fill<T>(field: &mut Option<T>, other: Option<T>) {
+            if field.is_none() {
+                *field = other;
+            }
+        }
+        fill(&mut self.cluster, other.cluster);
+        fill(&mut self.label, other.label);
+        fill(&mut self.requires, other.requires);
+        fill(&mut self.suggests, other.suggests);
+        fill(&mut self.description, other.description);
+        if self.items.is_empty() {
+            self.items = other.items;
+        }
+        self.without_items = false;
+        fill(&mut self.width, other.width);
+        fill(&mut self.status, other.status);
+        fill(&mut self.href, other.href);
+        fill(&mut self.aliases, other.aliases);
+        fill(&mut self.emoji, other.emoji);
+        fill(&mut self.header_color, other.header_color);
+        fill(&mut self.description_color, other.description_color);
+        fill(&mut self.standalone, other.standalone);
+        fill(&mut self.goal, other.goal);
+        fill(&mut self.status_history, other.status_history);
+        fill(&mut self.start, other.start);
+        fill(&mut self.due, other.due);
+    }
+
+    #[throws(anyhow::Error)]
+    pub fn validate(&self, tree: &SkillTree) {
+        // check: that `name` is a valid graphviz identifier
+
+        // check: each of the things in requires has the form
+        //        `identifier` or `identifier:port` and that all those
+        //        identifiers map to groups
+
+        if tree.groups().filter(|g| g.name == self.name).count() > 1 {
+            anyhow::bail!("there is more than one group named `{}`", self.name)
+        }
+
+        if self.without_items {
+            anyhow::bail!(
+                "the group `{}` has no `items`; only a group that overrides one from an \
+                 include can leave them out",
+                self.name
+            )
+        }
+
+        for alias in self.aliases() {
+            if tree.groups().any(|g| g.name == *alias) {
+                anyhow::bail!(
+                    "the group `{}` has an alias `{}` that is also the name of a group",
+                    self.name,
+                    alias,
+                )
+            }
+        }
+
+        let defined = tree.doc.as_ref().and_then(|doc| doc.statuses.as_ref());
+        let unknown_status = |owner: String, name: &str| {
+            let defined = defined
+                .into_iter()
+                .flat_map(|statuses| statuses.keys().map(|name| &name[..]));
+            let known = Status::BUILTIN.iter().map(Status::name).chain(defined);
+            anyhow::anyhow!(
+                "{} has the status `{}`, which is neither built in ({}) \
+                 nor defined in `[doc.statuses]`{}",
+                owner,
+                name,
+                Status::BUILTIN
+                    .iter()
+                    .map(|status| format!("`{}`", status.name()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                suggest::did_you_mean(name, known),
+            )
+        };
+        let history = self.status_history.iter().flatten();
+        for status in self
+            .status
+            .iter()
+            .chain(history.map(|change| &change.status))
+        {
+            if let Status::Custom(name) = status {
+                if tree.status_definition(name).is_none() {
+                    throw!(unknown_status(format!("the group `{}`", self.name), name));
+                }
+            }
+        }
+        // Items' statuses are free text, often mapped to emoji, unless the
+        // tree defines its own statuses.
+        if defined.is_some() {
+            for (index, item) in self.items().enumerate() {
+                let name = match item.get("status") {
+                    Some(name) => name,
+                    None => continue,
+                };
+                if let Status::Custom(name) = Status::from(name.clone()) {
+                    if tree.status_definition(&name).is_none() {
+                        throw!(unknown_status(
+                            format!("item #{} of the group `{}`", index + 1, self.name),
+                            &name,
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let (Some(start), Some(due)) = (self.start, self.due) {
+            if start > due {
+                anyhow::bail!(
+                    "the group `{}` starts on {}, after it is due on {}",
+                    self.name,
+                    start,
+                    due,
+                );
+            }
+        }
+
+        for (requirement, _) in self.dependencies() {
+            let group_name = requirement.group();
+            let required = match tree.group_named(group_name) {
+                Some(required) => required,
+                None => {
+                    let names = tree
+                        .groups()
+                        .flat_map(|group| std::iter::once(&group.name).chain(group.aliases()));
+                    anyhow::bail!(
+                        "the group `{}` has a dependency on a group `{}` that does not exist{}",
+                        self.name,
+                        group_name,
+                        suggest::did_you_mean(group_name, names.map(|name| &name[..])),
+                    )
+                }
+            };
+            if let Some(port) = requirement.port() {
+                if !required.items().any(|item| item.port() == Some(port)) {
+                    anyhow::bail!(
+                        "the group `{}` has a dependency on a port `{}` of group `{}`, \
+                         but no item there has that port",
+                        self.name,
+                        port,
+                        group_name,
+                    )
+                }
+            }
+        }
+
+        let hrefs = self
+            .href
+            .iter()
+            .chain(self.items().filter_map(|item| item.href()));
+        for href in hrefs {
+            let target = match href.strip_prefix('#') {
+                Some(target) if tree.group_named(target).is_none() => target,
+                _ => continue,
+            };
+            let names = tree
+                .groups()
+                .flat_map(|group| std::iter::once(&group.name).chain(group.aliases()));
+            anyhow::bail!(
+                "the group `{}` links to `{}`, but there is no group `{}`{}",
+                self.name,
+                href,
+                target,
+                suggest::did_you_mean(target, names.map(|name| &name[..])),
+            )
+        }
+
+        for (index, item) in self.items.iter().enumerate() {
+            self.item_label(index)?;
+            item.validate()?;
+            for column in tree.columns() {
+                if let Some(value) = item.get(column.name()) {
+                    if let Err(expected) = column.check(value) {
+                        anyhow::bail!(
+                            "item #{} in group `{}` has `{} = \"{}\"`, but expected {}",
+                            index + 1,
+                            self.name,
+                            column.name(),
+                            value,
+                            expected,
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter()
+    }
+
+    /// Returns how many items are done, and how many there are. An item is
+    /// done if its `status` is done (see `SkillTree::is_done`), or if the
+    /// whole group's is.
+    pub fn progress(&self, tree: &SkillTree) -> (usize, usize) {
+        let total = self.items.len();
+        if self.status.as_ref().is_some_and(|s| tree.is_done(s.name())) {
+            return (total, total);
+        }
+        let done = self
+            .items()
+            .filter(|item| tree.is_done(item.column_value(tree, self, "status")))
+            .count();
+        (done, total)
+    }
+
+    pub fn aliases(&self) -> impl Iterator<Item = &String> {
+        self.aliases.iter().flatten()
+    }
+
+    /// Where this group is declared, if it was loaded from a file (which
+    /// may be an include rather than the root file).
+    pub fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+
+    /// The status this group had on `date`: the last one in its
+    /// `status_history` from on or before then (or none, if the history
+    /// starts later), or its current status if it has no history.
+    pub fn status_on(&self, date: Date) -> Option<&Status> {
+        let history = match &self.status_history {
+            Some(history) => history,
+            None => return self.status.as_ref(),
+        };
+        history
+            .iter()
+            .filter(|change| change.date <= date)
+            .max_by_key(|change| change.date)
+            .map(|change| &change.status)
+    }
+
+    /// Iterates over the `requires` entries, then the `suggests` entries,
+    /// leaving out those on groups in other trees.
+    pub fn dependencies(&self) -> impl Iterator<Item = (&Requirement, EdgeKind)> {
+        self.all_dependencies().filter(|(r, _)| r.tree().is_none())
+    }
+
+    /// Like `dependencies`, but only those on groups in other trees.
+    pub fn external_dependencies(&self) -> impl Iterator<Item = (&Requirement, EdgeKind)> {
+        self.all_dependencies().filter(|(r, _)| r.tree().is_some())
+    }
+
+    fn all_dependencies(&self) -> impl Iterator<Item = (&Requirement, EdgeKind)> {
+        let hard = self.requires.iter().flatten().map(|r| (r, EdgeKind::Hard));
+        let soft = self.suggests.iter().flatten().map(|r| (r, EdgeKind::Soft));
+        hard.chain(soft)
+    }
+
+    /// Returns the label of the item at `index`, or an error naming the
+    /// group if the item has no `label` key.
+    #[throws(anyhow::Error)]
+    pub fn item_label(&self, index: usize) -> &String {
+        match self.items[index].label() {
+            Some(label) => label,
+            None => anyhow::bail!(
+                "item #{} in group `{}` is missing `label`",
+                index + 1,
+                self.name,
+            ),
+        }
+    }
+}
+
+impl Cluster {
+    /// Where this cluster is declared, if it was loaded from a file.
+    pub fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+}
+
+impl Status {
+    /// The built-in statuses.
+    pub const BUILTIN: [Status; 4] = [
+        Status::Blocked,
+        Status::Unassigned,
+        Status::Assigned,
+        Status::Complete,
+    ];
+
+    /// The name used for this status in skill-tree files.
+    pub fn name(&self) -> &str {
+        match self {
+            Status::Blocked => "Blocked",
+            Status::Unassigned => "Unassigned",
+            Status::Assigned => "Assigned",
+            Status::Complete => "Complete",
+            Status::Custom(name) => name,
+        }
+    }
+}
+
+impl From<String> for Status {
+    fn from(name: String) -> Status {
+        Status::BUILTIN
+            .iter()
+            .find(|status| status.name() == name)
+            .cloned()
+            .unwrap_or(Status::Custom(name))
+    }
+}
+
+impl Column {
+    pub fn name(&self) -> &str {
+        match self {
+            Column::Name(name) | Column::Typed { name, .. } => name,
+        }
+    }
+
+    pub fn kind(&self) -> ColumnType {
+        match self {
+            Column::Name(_) => ColumnType::String,
+            Column::Typed { kind, .. } => *kind,
+        }
+    }
+
+    /// Checks that the declaration itself makes sense.
+    #[throws(anyhow::Error)]
+    pub fn validate(&self) {
+        if let Column::Typed { name, kind, values } = self {
+            match (kind, values) {
+                (ColumnType::Enum, None) => {
+                    anyhow::bail!("the `enum` column `{}` has no `values`", name)
+                }
+                (ColumnType::Enum, Some(_)) | (_, None) => {}
+                (_, Some(_)) => anyhow::bail!(
+                    "the column `{}` has `values`, but only `enum` columns can",
+                    name
+                ),
+            }
+        }
+    }
+
+    /// Checks `value` against the column's type, returning a description
+    /// of what was expected if it doesn't fit.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        let ok = match self.kind() {
+            ColumnType::String => true,
+            ColumnType::Enum => match self {
+                Column::Typed {
+                    values: Some(values),
+                    ..
+                } => values.iter().any(|v| v == value),
+                _ => true,
+            },
+            ColumnType::Int => value.parse::<i64>().is_ok(),
+            ColumnType::Url => {
+                value.starts_with("https://")
+                    || value.starts_with("http://")
+                    || value.starts_with("mailto:")
+            }
+            ColumnType::Bool => value == "true" || value == "false",
+        };
+        if ok {
+            return Ok(());
+        }
+        Err(match (self.kind(), self) {
+            (
+                ColumnType::Enum,
+                Column::Typed {
+                    values: Some(values),
+                    ..
+                },
+            ) => format!(
+                "one of {}",
+                values
+                    .iter()
+                    .map(|v| format!("`{}`", v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            (ColumnType::Int, _) => "an integer".to_string(),
+            (ColumnType::Url, _) => "an `http(s)://` or `mailto:` URL".to_string(),
+            _ => "`true` or `false`".to_string(),
+        })
+    }
+}
+
+/// `base` followed by `own`, except that an item of `own` with the same
+/// `label` as one in `base` is merged into it, its values winning.
+fn merge_items(mut base: Vec<Item>, own: &[Item]) -> Vec<Item> {
+    for item in own {
+        let label = item.label();
+        match base
+            .iter_mut()
+            .find(|b| label.is_some() && b.label() == label)
+        {
+            Some(existing) => existing.extend(item.clone()),
+            None => base.push(item.clone()),
+        }
+    }
+    base
+}
+
+/// Reads `items`, accepting numbers and booleans as cell values (so that
+/// typed columns can be written `done = true`) and storing them as text.
+fn deserialize_items<'de, D>(deserializer: D) -> Result<Vec<Item>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    use serde::Deserialize;
+
+    let raw: Vec<HashMap<String, toml::Value>> = Vec::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|item| {
+            item.into_iter()
+                .map(|(key, value)| {
+                    let text = match value {
+                        toml::Value::String(s) => s,
+                        toml::Value::Integer(i) => i.to_string(),
+                        toml::Value::Float(f) => f.to_string(),
+                        toml::Value::Boolean(b) => b.to_string(),
+                        toml::Value::Datetime(d) => d.to_string(),
+                        _ => {
+                            return Err(D::Error::custom(format!(
+                                "the value of `{}` must be a string, number or boolean",
+                                key
+                            )))
+                        }
+                    };
+                    Ok((key, text))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl Requirement {
+    /// The tree the required group is in, if it is another tree of the
+    /// workspace.
+    pub fn tree(&self) -> Option<&str> {
+        self.split_tree().0
+    }
+
+    /// The name of the required group.
+    pub fn group(&self) -> &str {
+        match self {
+            Requirement::Name(_) => self.split_tree().1.split(':').next().unwrap(),
+            Requirement::Detailed { .. } => self.split_tree().1,
+        }
+    }
+
+    /// The name of the required group, prefixed with its tree and `::` if
+    /// it is in another tree of the workspace.
+    pub fn qualified_group(&self) -> &str {
+        let name = match self {
+            Requirement::Name(name) => name,
+            Requirement::Detailed { group, .. } => group,
+        };
+        let prefix = self.tree().map_or(0, |tree| tree.len() + "::".len());
+        &name[..prefix + self.group().len()]
+    }
+
+    /// The port within the required group, if any.
+    pub fn port(&self) -> Option<&str> {
+        match self {
+            Requirement::Name(_) => self.split_tree().1.split_once(':').map(|(_, port)| port),
+            Requirement::Detailed { port, .. } => port.as_deref(),
+        }
+    }
+
+    /// The tree named before `::`, if any, and the rest.
+    fn split_tree(&self) -> (Option<&str>, &str) {
+        let name = match self {
+            Requirement::Name(name) => name,
+            Requirement::Detailed { group, .. } => group,
+        };
+        match name.split_once("::") {
+            Some((tree, rest)) => (Some(tree), rest),
+            None => (None, name),
+        }
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Requirement::Name(_) => None,
+            Requirement::Detailed { label, .. } => label.as_deref(),
+        }
+    }
+}
+
+pub trait ItemExt {
+    fn href(&self) -> Option<&String>;
+    fn label(&self) -> Option<&String>;
+    fn port(&self) -> Option<&str>;
+    /// The item's value for the column `c`, or else the default for it
+    /// in the cluster of `group` (the group the item is in), or else the
+    /// default in `doc.defaults`, or else `""`.
+    fn column_value<'me>(&'me self, tree: &'me SkillTree, group: &Group, c: &str) -> &'me str;
+
+    #[allow(redundant_semicolons)] // bug in "throws"
+    #[throws(anyhow::Error)]
+    fn validate(&self);
+}
+
+impl ItemExt for Item {
+    fn href(&self) -> Option<&String> {
+        self.get("href")
+    }
+
+    fn label(&self) -> Option<&String> {
+        self.get("label")
+    }
+
+    fn port(&self) -> Option<&str> {
+        self.get("port").map(String::as_str)
+    }
+
+    fn column_value<'me>(&'me self, tree: &'me SkillTree, group: &Group, c: &str) -> &'me str {
+        if let Some(v) = self.get(c) {
+            return v;
+        }
+
+        tree.column_default(group, c).unwrap_or("")
+    }
+
+    #[throws(anyhow::Error)]
+    fn validate(&self) {
+        // check: each of the things in requires has the form
+        //        `identifier` or `identifier:port` and that all those
+        //        identifiers map to groups
+
+        // check: only contains known keys
+    }
+}
+
+/// `path` with symlinks and `..` resolved, if it exists, so the same file
+/// reached two ways compares equal.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_owned())
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of
+/// characters and `?` any one character.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some((&c, rest)) => match name.split_first() {
+                Some((&n, name)) if c == '?' || c == n => matches(rest, name),
+                _ => false,
+            },
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
+
+/// Whether `name` can be written unquoted as a dot attribute name.
+fn is_dot_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,108 @@
+//! `SkillTree::walk`, which visits everything in a tree in a fixed order,
+//! with item values already resolved, so exporters and analyses don't
+//! each need their own loops.
+
+use crate::tree::{Cluster, EdgeKind, Group, Item, ItemExt, Requirement, SkillTree};
+
+/// What `SkillTree::walk` calls. Every method does nothing unless
+/// overridden.
+pub trait TreeVisitor<'t> {
+    fn visit_cluster(&mut self, _cluster: &'t Cluster) {}
+
+    fn visit_group(&mut self, _group: &'t Group) {}
+
+    fn visit_item(&mut self, _group: &'t Group, _item: &ItemVisit<'t>) {}
+
+    fn visit_edge(&mut self, _edge: &Edge<'t>) {}
+}
+
+/// An item, with the value of each column worked out.
+#[derive(Debug)]
+pub struct ItemVisit<'t> {
+    /// Its index (0-based) in the group's `items`.
+    pub index: usize,
+    pub item: &'t Item,
+    pub label: Option<&'t str>,
+
+    /// Each of the tree's columns, in order, with the item's value for it
+    /// (or the column's default, or `""`).
+    pub values: Vec<(&'t str, &'t str)>,
+}
+
+/// A dependency of `to` on `from`.
+#[derive(Copy, Clone, Debug)]
+pub struct Edge<'t> {
+    pub from: &'t Group,
+    pub to: &'t Group,
+    pub kind: EdgeKind,
+    pub requirement: &'t Requirement,
+}
+
+impl SkillTree {
+    /// Visits the tree in this order:
+    ///
+    /// * each `[[cluster]]` as declared, followed by its groups, each
+    ///   followed by its items;
+    /// * then the groups outside any cluster (or in one that isn't
+    ///   declared), likewise followed by their items;
+    /// * then every dependency, by the group that has it, `requires`
+    ///   before `suggests`. Dependencies on groups that don't exist are
+    ///   skipped.
+    ///
+    /// Groups are in the order they are declared, except that goals come
+    /// first, as in `groups_goals_first`.
+    pub fn walk<'t>(&'t self, visitor: &mut impl TreeVisitor<'t>) {
+        for cluster in self.cluster.iter().flatten() {
+            visitor.visit_cluster(cluster);
+            for group in self.groups_goals_first() {
+                if group.cluster.as_ref() == Some(&cluster.name) {
+                    self.walk_group(group, visitor);
+                }
+            }
+        }
+        for group in self.groups_goals_first() {
+            let clustered = group
+                .cluster
+                .as_deref()
+                .is_some_and(|name| self.cluster_named(name).is_some());
+            if !clustered {
+                self.walk_group(group, visitor);
+            }
+        }
+        for to in self.groups_goals_first() {
+            for (requirement, kind) in to.dependencies() {
+                if let Some(from) = self.group_named(requirement.group()) {
+                    visitor.visit_edge(&Edge {
+                        from,
+                        to,
+                        kind,
+                        requirement,
+                    });
+                }
+            }
+        }
+    }
+
+    fn walk_group<'t>(&'t self, group: &'t Group, visitor: &mut impl TreeVisitor<'t>) {
+        visitor.visit_group(group);
+        for (index, item) in group.items().enumerate() {
+            let values = self
+                .columns()
+                .iter()
+                .map(|column| (column.name(), item.column_value(self, group, column.name())))
+                .collect();
+            visitor.visit_item(
+                group,
+                &ItemVisit {
+                    index,
+                    item,
+                    label: item.label().map(String::as_str),
+                    values,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,116 @@
+use super::{Edge, ItemVisit, TreeVisitor};
+use crate::{Cluster, Group, SkillTree};
+
+/// Writes down everything it visits.
+#[derive(Default)]
+struct Trace(Vec<String>);
+
+impl<'t> TreeVisitor<'t> for Trace {
+    fn visit_cluster(&mut self, cluster: &'t Cluster) {
+        self.0.push(format!("cluster {}", cluster.name));
+    }
+
+    fn visit_group(&mut self, group: &'t Group) {
+        self.0.push(format!("group {}", group.name));
+    }
+
+    fn visit_item(&mut self, group: &'t Group, item: &ItemVisit<'t>) {
+        let values: Vec<String> = item
+            .values
+            .iter()
+            .map(|(column, value)| format!("{}={}", column, value))
+            .collect();
+        self.0.push(format!(
+            "item {}#{} {} {}",
+            group.name,
+            item.index,
+            item.label.unwrap_or("-"),
+            values.join(",")
+        ));
+    }
+
+    fn visit_edge(&mut self, edge: &Edge<'t>) {
+        self.0.push(format!(
+            "edge {} -> {} {:?}",
+            edge.from.name, edge.to.name, edge.kind
+        ));
+    }
+}
+
+#[test]
+fn walk_order() {
+    let tree = SkillTree::parse(
+        r#"
+[doc]
+columns = ["status"]
+defaults = { status = "todo" }
+
+[[group]]
+name = "docs"
+suggests = ["types", "nowhere"]
+items = [{ label = "guide", status = "done" }]
+
+[[group]]
+name = "types"
+cluster = "core"
+requires = ["parser"]
+items = [{ label = "check" }]
+
+[[group]]
+name = "parser"
+cluster = "core"
+items = [{ label = "lex" }, { label = "parse", status = "wip" }]
+
+[[cluster]]
+name = "core"
+label = "Core"
+
+[[cluster]]
+name = "empty"
+label = "Empty"
+"#,
+    )
+    .unwrap();
+    let mut trace = Trace::default();
+    tree.walk(&mut trace);
+    assert_eq!(
+        trace.0,
+        [
+            "cluster core",
+            "group types",
+            "item types#0 check status=todo",
+            "group parser",
+            "item parser#0 lex status=todo",
+            "item parser#1 parse status=wip",
+            "cluster empty",
+            "group docs",
+            "item docs#0 guide status=done",
+            "edge types -> docs Soft",
+            "edge parser -> types Hard",
+        ]
+    );
+}
+
+/// Visitors can keep what they visit, for as long as the tree lives.
+#[test]
+fn borrow_from_tree() {
+    struct Labels<'t>(Vec<&'t str>);
+
+    impl<'t> TreeVisitor<'t> for Labels<'t> {
+        fn visit_item(&mut self, _group: &'t Group, item: &ItemVisit<'t>) {
+            self.0.extend(item.label);
+        }
+    }
+
+    let tree = SkillTree::parse(
+        r#"
+[[group]]
+name = "parser"
+items = [{ label = "lex" }, { label = "parse" }]
+"#,
+    )
+    .unwrap();
+    let mut labels = Labels(vec![]);
+    tree.walk(&mut labels);
+    assert_eq!(labels.0, ["lex", "parse"]);
+}
@@ -0,0 +1,150 @@
+//! `SkillTree::load_workspace`: every tree under a directory, for
+//! organizations that keep one tree per team. A tree is any file named
+//! `<name>.skill-tree.toml`, at any depth; hidden directories (`.git`,
+//! say) are skipped. Each tree is loaded on its own, with its includes,
+//! and is known by its `<name>`.
+//!
+//! A group can depend on a group of another tree as `"tree::group"` (or
+//! `{ group = "tree::group", port = "..." }`). Validating one tree on its
+//! own leaves those alone; `Workspace::validate` checks them.
+
+use crate::suggest;
+use crate::tree::{ItemExt, SkillTree};
+use anyhow::Context;
+use fehler::throws;
+use std::path::{Path, PathBuf};
+
+/// What a file's name ends with if it holds a tree of a workspace.
+pub const SUFFIX: &str = ".skill-tree.toml";
+
+#[derive(Debug)]
+pub struct Workspace {
+    /// The trees, by name.
+    pub trees: Vec<NamedTree>,
+}
+
+#[derive(Debug)]
+pub struct NamedTree {
+    /// The file's name without `.skill-tree.toml`.
+    pub name: String,
+    pub path: PathBuf,
+    pub tree: SkillTree,
+}
+
+impl Workspace {
+    pub fn tree_named(&self, name: &str) -> Option<&NamedTree> {
+        self.trees.iter().find(|tree| tree.name == name)
+    }
+
+    /// Validates each tree, and that the groups (and ports) they depend
+    /// on in other trees exist.
+    #[throws(anyhow::Error)]
+    pub fn validate(&self) {
+        for named in &self.trees {
+            named
+                .tree
+                .validate()
+                .with_context(|| format!("validating `{}`", named.path.display()))?;
+            for group in named.tree.groups() {
+                for (requirement, _) in group.external_dependencies() {
+                    let tree_name = requirement.tree().unwrap();
+                    let other = match self.tree_named(tree_name) {
+                        Some(other) => other,
+                        None => anyhow::bail!(
+                            "the group `{}` of `{}` has a dependency on a tree `{}` \
+                             that is not in the workspace{}",
+                            group.name,
+                            named.name,
+                            tree_name,
+                            suggest::did_you_mean(
+                                tree_name,
+                                self.trees.iter().map(|tree| &tree.name[..])
+                            ),
+                        ),
+                    };
+                    let group_name = requirement.group();
+                    let required = match other.tree.group_named(group_name) {
+                        Some(required) => required,
+                        None => anyhow::bail!(
+                            "the group `{}` of `{}` has a dependency on a group `{}` \
+                             that does not exist in `{}`{}",
+                            group.name,
+                            named.name,
+                            group_name,
+                            tree_name,
+                            suggest::did_you_mean(
+                                group_name,
+                                other.tree.groups().map(|group| &group.name[..])
+                            ),
+                        ),
+                    };
+                    if let Some(port) = requirement.port() {
+                        if !required.items().any(|item| item.port() == Some(port)) {
+                            anyhow::bail!(
+                                "the group `{}` of `{}` has a dependency on a port `{}` \
+                                 of group `{}` in `{}`, but no item there has that port",
+                                group.name,
+                                named.name,
+                                port,
+                                group_name,
+                                tree_name,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SkillTree {
+    /// Loads every tree under `dir`. Two trees can't have the same name,
+    /// even in different directories.
+    #[throws(anyhow::Error)]
+    pub fn load_workspace(dir: &Path) -> Workspace {
+        let mut paths = vec![];
+        discover(dir, &mut paths)?;
+
+        let mut trees: Vec<NamedTree> = vec![];
+        for path in paths {
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            let name = file_name[..file_name.len() - SUFFIX.len()].to_string();
+            if let Some(other) = trees.iter().find(|tree| tree.name == name) {
+                anyhow::bail!(
+                    "`{}` and `{}` are both named `{}`",
+                    other.path.display(),
+                    path.display(),
+                    name
+                );
+            }
+            let tree = SkillTree::load(&path)?;
+            trees.push(NamedTree { name, path, tree });
+        }
+        trees.sort_by(|a, b| a.name.cmp(&b.name));
+        Workspace { trees }
+    }
+}
+
+/// Adds the trees under `dir` to `paths`, in name order.
+#[throws(anyhow::Error)]
+fn discover(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let entries = std::fs::read_dir(dir).with_context(|| format!("reading `{}`", dir.display()))?;
+    let mut entries = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for path in entries {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if file_name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            discover(&path, paths)?;
+        } else if file_name.ends_with(SUFFIX) && file_name.len() > SUFFIX.len() {
+            paths.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
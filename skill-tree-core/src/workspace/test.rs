@@ -0,0 +1,73 @@
+use crate::test_support::scratch;
+use crate::SkillTree;
+use std::path::Path;
+
+#[test]
+fn finds_trees_at_any_depth() {
+    let workspace = SkillTree::load_workspace(Path::new("test-data/workspace")).unwrap();
+    let names: Vec<&str> = workspace.trees.iter().map(|t| &t.name[..]).collect();
+    assert_eq!(names, ["compiler", "tooling"]);
+
+    let tooling = workspace.tree_named("tooling").unwrap();
+    assert_eq!(
+        tooling.path,
+        Path::new("test-data/workspace/platform/tooling.skill-tree.toml")
+    );
+    assert_eq!(tooling.tree.groups().count(), 2);
+    tooling.tree.validate().unwrap();
+}
+
+#[test]
+fn names_are_unique() {
+    let dir = scratch("workspace-clash");
+    std::fs::create_dir_all(dir.join("b")).unwrap();
+    for path in ["a.skill-tree.toml", "b/a.skill-tree.toml"] {
+        std::fs::write(dir.join(path), "[[group]]\nname = \"x\"\nitems = []\n").unwrap();
+    }
+    let error = SkillTree::load_workspace(&dir).unwrap_err();
+    assert!(
+        error.to_string().ends_with("are both named `a`"),
+        "{}",
+        error
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn checks_references_across_trees() {
+    let workspace = SkillTree::load_workspace(Path::new("test-data/workspace")).unwrap();
+    workspace.validate().unwrap();
+
+    let dir = scratch("workspace-references");
+    std::fs::write(
+        dir.join("infra.skill-tree.toml"),
+        "[[group]]\nname = \"ci\"\nitems = [{ label = \"Linux\", port = \"linux\" }]\n",
+    )
+    .unwrap();
+    let error = |requires: &str| {
+        let text = format!(
+            "[[group]]\nname = \"release\"\nrequires = [{}]\nitems = []\n",
+            requires
+        );
+        std::fs::write(dir.join("app.skill-tree.toml"), text).unwrap();
+        let workspace = SkillTree::load_workspace(&dir).unwrap();
+        workspace.validate().err().map(|error| error.to_string())
+    };
+    assert_eq!(error(r#""infra::ci:linux""#), None);
+    assert_eq!(
+        error(r#""infar::ci""#).unwrap(),
+        "the group `release` of `app` has a dependency on a tree `infar` \
+         that is not in the workspace; did you mean `infra`?"
+    );
+    assert_eq!(
+        error(r#""infra::cj""#).unwrap(),
+        "the group `release` of `app` has a dependency on a group `cj` \
+         that does not exist in `infra`; did you mean `ci`?"
+    );
+    assert_eq!(
+        error(r#"{ group = "infra::ci", port = "macos" }"#).unwrap(),
+        "the group `release` of `app` has a dependency on a port `macos` \
+         of group `ci` in `infra`, but no item there has that port"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -1,4 +1,5 @@
-mod graphviz;
-mod tree;
-pub use tree::*;
-mod test;
+//! The `skill-tree` crate re-exports [`skill_tree_core`], which holds the
+//! data model, loading, validation and rendering. The command-line tool
+//! lives in the `skill-tree-cli` crate.
+
+pub use skill_tree_core::*;